@@ -0,0 +1,352 @@
+use crate::dynamic::{kind_code, kind_from_code, move_to_coord, move_to_index};
+use crate::save::{config_flags, config_from_flags};
+use crate::{
+    CanonicalPiece, CetkaikRepresentation, GameRecord, IsAbsoluteField, IsBoard, IsField,
+    PlayedMove, PureMove, COLS, ROWS,
+};
+use cetkaik_fundamental::{AbsoluteSide, ColorAndProf, PureMove_};
+use rkyv::{Archive, Deserialize, Serialize};
+
+/// The code of an empty square in [`FieldData::squares`]
+const EMPTY: u8 = 0xff;
+
+/// A field written in canonical form, so that it can be archived by rkyv and accessed without copying
+/// ／rkyv で保存し、コピーせずに参照できるように、正準な形で書かれた `Field`
+///
+/// Pieces are written by their codes, as in [`encode_journal`](crate::encode_journal),
+/// and pieces in a hop1zuo1 as `10 * color + prof`, as in [`save`](crate::save).
+/// ／盤上の駒は [`encode_journal`](crate::encode_journal) と同様にその番号で、
+/// 手駒の駒は [`save`](crate::save) と同様に `10 * color + prof` で書かれる。
+#[derive(Archive, Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[rkyv(derive(Debug))]
+pub struct FieldData {
+    /// the code of the piece on each square in canonical order, 255 for an empty square
+    /// ／正準な順に並べた各マスの駒の番号。空きマスは 255
+    pub squares: [u8; ROWS * COLS],
+    /// the pieces in the hop1zuo1 of `ASide`／`ASide` の手駒
+    pub a_side_hop1zuo1: Vec<u8>,
+    /// the pieces in the hop1zuo1 of `IASide`／`IASide` の手駒
+    pub ia_side_hop1zuo1: Vec<u8>,
+}
+
+impl FieldData {
+    /// Writes `field` in canonical form
+    /// ／`field` を正準な形で書く
+    pub fn from_field<R: CetkaikRepresentation>(field: &R::AbsoluteField) -> Self {
+        let board = field.as_board();
+        let mut squares = [EMPTY; ROWS * COLS];
+        for c in board.occupied_squares() {
+            if let Some(piece) = board.peek(c) {
+                squares[usize::from(R::coord_to_index(c))] =
+                    CanonicalPiece::from_piece(piece).code();
+            }
+        }
+        let hop1zuo1 = |side| field.hop1zuo1_of(side).map(kind_code).collect();
+        Self {
+            squares,
+            a_side_hop1zuo1: hop1zuo1(AbsoluteSide::ASide),
+            ia_side_hop1zuo1: hop1zuo1(AbsoluteSide::IASide),
+        }
+    }
+
+    /// Reads the field back into the representation `R`, or returns `None` if a code is out of range
+    /// ／表現 `R` の `Field` に読み戻す。範囲外の番号があれば `None` を返す。
+    #[must_use]
+    pub fn to_field<R: CetkaikRepresentation>(&self) -> Option<R::AbsoluteField> {
+        field_from_codes::<R>(&self.squares, &self.a_side_hop1zuo1, &self.ia_side_hop1zuo1)
+    }
+}
+
+impl ArchivedFieldData {
+    /// Reads the archived field into the representation `R` without deserializing it first, or returns `None` if a code is out of range
+    /// ／保存された `Field` を、デシリアライズせずに表現 `R` の `Field` に読み込む。範囲外の番号があれば `None` を返す。
+    #[must_use]
+    pub fn to_field<R: CetkaikRepresentation>(&self) -> Option<R::AbsoluteField> {
+        field_from_codes::<R>(&self.squares, &self.a_side_hop1zuo1, &self.ia_side_hop1zuo1)
+    }
+}
+
+fn field_from_codes<R: CetkaikRepresentation>(
+    squares: &[u8; ROWS * COLS],
+    a_side_hop1zuo1: &[u8],
+    ia_side_hop1zuo1: &[u8],
+) -> Option<R::AbsoluteField> {
+    let mut field = R::AbsoluteField::empty();
+    for (index, &code) in (0..).zip(squares) {
+        if code != EMPTY {
+            let piece = CanonicalPiece::from_code(code)?.to_piece::<R>();
+            field
+                .as_board_mut()
+                .put(R::index_to_coord(index)?, Some(piece));
+        }
+    }
+    for (side, hop1zuo1) in [
+        (AbsoluteSide::ASide, a_side_hop1zuo1),
+        (AbsoluteSide::IASide, ia_side_hop1zuo1),
+    ] {
+        for &code in hop1zuo1 {
+            field.add_to_hop1zuo1(side, kind_from_code(code)?);
+        }
+    }
+    Some(field)
+}
+
+/// A move written in canonical form, with squares as their canonical indices and a parachuted piece as `10 * color + prof`
+/// ／正準な形で書かれた指し手。マスは正準な番号で、打つ駒は `10 * color + prof` で表す。
+///
+/// The variants correspond to those of `PureMove_`.
+/// ／列挙子は `PureMove_` のものに対応する。
+#[derive(Archive, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[rkyv(derive(Debug))]
+#[allow(missing_docs)]
+pub enum MoveData {
+    NonTamMoveSrcDst {
+        src: u8,
+        dest: u8,
+        is_water_entry_ciurl: bool,
+    },
+    NonTamMoveSrcStepDstFinite {
+        src: u8,
+        step: u8,
+        dest: u8,
+        is_water_entry_ciurl: bool,
+    },
+    InfAfterStep {
+        src: u8,
+        step: u8,
+        planned_direction: u8,
+    },
+    NonTamMoveFromHopZuo {
+        piece: u8,
+        dest: u8,
+    },
+    TamMoveNoStep {
+        src: u8,
+        first_dest: u8,
+        second_dest: u8,
+    },
+    TamMoveStepsDuringFormer {
+        src: u8,
+        step: u8,
+        first_dest: u8,
+        second_dest: u8,
+    },
+    TamMoveStepsDuringLatter {
+        src: u8,
+        first_dest: u8,
+        step: u8,
+        second_dest: u8,
+    },
+}
+
+impl MoveData {
+    /// Writes `mv` in canonical form
+    /// ／`mv` を正準な形で書く
+    pub fn from_move<R: CetkaikRepresentation>(mv: PureMove<R>) -> Self {
+        match move_to_index::<R>(mv) {
+            PureMove_::NonTamMoveSrcDst {
+                src,
+                dest,
+                is_water_entry_ciurl,
+            } => Self::NonTamMoveSrcDst {
+                src,
+                dest,
+                is_water_entry_ciurl,
+            },
+            PureMove_::NonTamMoveSrcStepDstFinite {
+                src,
+                step,
+                dest,
+                is_water_entry_ciurl,
+            } => Self::NonTamMoveSrcStepDstFinite {
+                src,
+                step,
+                dest,
+                is_water_entry_ciurl,
+            },
+            PureMove_::InfAfterStep {
+                src,
+                step,
+                planned_direction,
+            } => Self::InfAfterStep {
+                src,
+                step,
+                planned_direction,
+            },
+            PureMove_::NonTamMoveFromHopZuo { color, prof, dest } => Self::NonTamMoveFromHopZuo {
+                piece: kind_code(ColorAndProf { color, prof }),
+                dest,
+            },
+            PureMove_::TamMoveNoStep {
+                src,
+                first_dest,
+                second_dest,
+            } => Self::TamMoveNoStep {
+                src,
+                first_dest,
+                second_dest,
+            },
+            PureMove_::TamMoveStepsDuringFormer {
+                src,
+                step,
+                first_dest,
+                second_dest,
+            } => Self::TamMoveStepsDuringFormer {
+                src,
+                step,
+                first_dest,
+                second_dest,
+            },
+            PureMove_::TamMoveStepsDuringLatter {
+                src,
+                first_dest,
+                step,
+                second_dest,
+            } => Self::TamMoveStepsDuringLatter {
+                src,
+                first_dest,
+                step,
+                second_dest,
+            },
+        }
+    }
+
+    /// Reads the move back into the representation `R`, or returns `None` if an index or a piece is out of range
+    /// ／表現 `R` の指し手に読み戻す。番号や駒が範囲外なら `None` を返す。
+    #[must_use]
+    pub fn to_move<R: CetkaikRepresentation>(self) -> Option<PureMove<R>> {
+        let mv = match self {
+            Self::NonTamMoveSrcDst {
+                src,
+                dest,
+                is_water_entry_ciurl,
+            } => PureMove_::NonTamMoveSrcDst {
+                src,
+                dest,
+                is_water_entry_ciurl,
+            },
+            Self::NonTamMoveSrcStepDstFinite {
+                src,
+                step,
+                dest,
+                is_water_entry_ciurl,
+            } => PureMove_::NonTamMoveSrcStepDstFinite {
+                src,
+                step,
+                dest,
+                is_water_entry_ciurl,
+            },
+            Self::InfAfterStep {
+                src,
+                step,
+                planned_direction,
+            } => PureMove_::InfAfterStep {
+                src,
+                step,
+                planned_direction,
+            },
+            Self::NonTamMoveFromHopZuo { piece, dest } => {
+                let ColorAndProf { color, prof } = kind_from_code(piece)?;
+                PureMove_::NonTamMoveFromHopZuo { color, prof, dest }
+            }
+            Self::TamMoveNoStep {
+                src,
+                first_dest,
+                second_dest,
+            } => PureMove_::TamMoveNoStep {
+                src,
+                first_dest,
+                second_dest,
+            },
+            Self::TamMoveStepsDuringFormer {
+                src,
+                step,
+                first_dest,
+                second_dest,
+            } => PureMove_::TamMoveStepsDuringFormer {
+                src,
+                step,
+                first_dest,
+                second_dest,
+            },
+            Self::TamMoveStepsDuringLatter {
+                src,
+                first_dest,
+                step,
+                second_dest,
+            } => PureMove_::TamMoveStepsDuringLatter {
+                src,
+                first_dest,
+                step,
+                second_dest,
+            },
+        };
+        move_to_coord::<R>(mv).ok()
+    }
+}
+
+/// A [`PlayedMove`] written in canonical form
+/// ／正準な形で書かれた [`PlayedMove`]
+#[derive(Archive, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[rkyv(derive(Debug))]
+pub struct PlayedMoveData {
+    /// the move／指し手
+    pub mv: MoveData,
+    /// how many sticks fell face up, if recorded／表になった投げ棒の本数（記録されていれば）
+    pub ciurl: Option<u8>,
+}
+
+/// A [`GameRecord`] written in canonical form
+/// ／正準な形で書かれた [`GameRecord`]
+#[derive(Archive, Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[rkyv(derive(Debug))]
+pub struct RecordData {
+    /// the field the game started from／ゲームの開始時の `Field`
+    pub initial: FieldData,
+    /// whether `IASide` moved first／`IASide` が先手であったかどうか
+    pub ia_side_first: bool,
+    /// the rule configuration, packed as in [`save`](crate::save)／[`save`](crate::save) と同様に詰めたルール設定
+    pub config: u8,
+    /// the moves played, in order／指された手。順に並ぶ。
+    pub moves: Vec<PlayedMoveData>,
+}
+
+impl RecordData {
+    /// Writes `record` in canonical form
+    /// ／`record` を正準な形で書く
+    pub fn from_record<R: CetkaikRepresentation>(record: &GameRecord<R>) -> Self {
+        Self {
+            initial: FieldData::from_field::<R>(&record.initial),
+            ia_side_first: record.first_mover == AbsoluteSide::IASide,
+            config: config_flags(record.config),
+            moves: record
+                .moves
+                .iter()
+                .map(|&PlayedMove { mv, ciurl }| PlayedMoveData {
+                    mv: MoveData::from_move::<R>(mv),
+                    ciurl,
+                })
+                .collect(),
+        }
+    }
+
+    /// Reads the record back into the representation `R`, or returns `None` if anything is out of range
+    /// ／表現 `R` の記録に読み戻す。範囲外のものがあれば `None` を返す。
+    #[must_use]
+    pub fn to_record<R: CetkaikRepresentation>(&self) -> Option<GameRecord<R>> {
+        let first_mover = if self.ia_side_first {
+            AbsoluteSide::IASide
+        } else {
+            AbsoluteSide::ASide
+        };
+        let mut record = GameRecord::new(
+            self.initial.to_field::<R>()?,
+            first_mover,
+            config_from_flags(self.config)?,
+        );
+        for &PlayedMoveData { mv, ciurl } in &self.moves {
+            record.push(mv.to_move::<R>()?, ciurl);
+        }
+        Some(record)
+    }
+}
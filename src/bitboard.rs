@@ -0,0 +1,54 @@
+use crate::{CetkaikRepresentation, IsBoard, IsPieceWithSide, SQUARE_COUNT};
+use cetkaik_fundamental::{AbsoluteSide, Profession};
+
+/// A trait that signifies that the representation can expose the occupancy of its boards as bit masks
+/// ／盤面の占有状況をビットマスクとして取り出せる表現を表すトレイト
+///
+/// Bit `i` of a mask stands for the square whose canonical index (see [`CetkaikRepresentation::coord_to_index`]) is `i`.
+/// The default methods scan the board square by square, so any representation can opt in with an empty `impl`;
+/// a bitboard-based representation should override them with its native masks.
+/// ／マスクのビット `i` は、正準な番号（[`CetkaikRepresentation::coord_to_index`] を参照）が `i` のマスを表す。
+/// 既定のメソッドは盤面を一マスずつ走査するので、どの表現も空の `impl` で対応できる。ビットボードに基づく表現は、固有のマスクで上書きすべきである。
+pub trait IsBitBoard: CetkaikRepresentation {
+    /// The squares that hold a piece
+    /// ／駒のあるマス
+    fn occupancy(board: &Self::AbsoluteBoard) -> u128
+    where
+        Self: Sized,
+    {
+        mask_where::<Self>(board, |_| true)
+    }
+
+    /// The squares that hold a piece belonging to `side`; Tam2 belongs to neither side
+    /// ／`side` に属する駒のあるマス。皇はどちらにも属さない。
+    fn occupancy_of_side(board: &Self::AbsoluteBoard, side: AbsoluteSide) -> u128
+    where
+        Self: Sized,
+    {
+        mask_where::<Self>(board, |piece| {
+            piece.match_on_piece_and_apply(&|| false, &|_, _, s| s == side)
+        })
+    }
+
+    /// The squares that hold a piece of the profession `prof`, of either side
+    /// ／職種 `prof` の駒のあるマス。どちらの陣営のものも含む。
+    fn mask_of_prof(board: &Self::AbsoluteBoard, prof: Profession) -> u128
+    where
+        Self: Sized,
+    {
+        mask_where::<Self>(board, |piece| piece.has_prof(prof))
+    }
+}
+
+/// The mask of every square on the board
+pub(crate) const ALL_SQUARES: u128 = (1 << SQUARE_COUNT) - 1;
+
+fn mask_where<R: CetkaikRepresentation>(
+    board: &R::AbsoluteBoard,
+    pred: impl Fn(R::AbsolutePiece) -> bool,
+) -> u128 {
+    board
+        .occupied_squares()
+        .filter(|&c| board.peek(c).is_some_and(&pred))
+        .fold(0, |mask, c| mask | 1 << R::coord_to_index(c))
+}
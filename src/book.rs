@@ -0,0 +1,110 @@
+use crate::{move_to_policy_index, policy_index_to_move, CetkaikRepresentation, PureMove};
+use cetkaik_fundamental::AbsoluteSide;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+/// The key under which an opening book stores a position
+/// ／定跡が局面を格納する際のキー
+///
+/// The low 64 bits are [`CetkaikRepresentation::repetition_key`], which covers the field and whose turn it is,
+/// and the high 64 bits are the season counted from 0, so the key agrees across every representation.
+/// ／下位 64 ビットは `Field` と手番を表す [`CetkaikRepresentation::repetition_key`] であり、
+/// 上位 64 ビットは 0 から数えた季節であるので、キーはどの表現でも一致する。
+pub fn book_key<R: CetkaikRepresentation>(
+    field: &R::AbsoluteField,
+    whose_turn: AbsoluteSide,
+    season_index: usize,
+) -> u128 {
+    combine(R::repetition_key(field, whose_turn), season_index)
+}
+
+pub(crate) const fn combine(repetition_key: u64, season_index: usize) -> u128 {
+    (season_index as u128) << 64 | repetition_key as u128
+}
+
+/// A move suggested by an opening book, together with how often it should be played
+/// ／定跡が示す指し手と、それを指すべき頻度
+///
+/// The move is kept as its [`move_to_policy_index`], so that a book can be shared by engines built on different representations.
+/// ／指し手は [`move_to_policy_index`] として保持されるので、定跡は異なる表現に基づくエンジンの間で共有できる。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct BookMove {
+    /// the move, as its [`move_to_policy_index`]／指し手。[`move_to_policy_index`] で表す。
+    pub policy_index: usize,
+    /// the relative frequency with which the move should be played／指し手を指すべき相対的な頻度
+    pub weight: u32,
+}
+
+impl BookMove {
+    /// A book move for `mv` with the given weight
+    /// ／`mv` を与えられた重みで指す定跡手
+    #[must_use]
+    pub fn new<R: CetkaikRepresentation>(mv: PureMove<R>, weight: u32) -> Self {
+        Self {
+            policy_index: move_to_policy_index::<R>(mv),
+            weight,
+        }
+    }
+
+    /// The move in the representation `R`, or `None` if `policy_index` does not designate a move
+    /// ／表現 `R` での指し手。`policy_index` が指し手を指していなければ `None`
+    #[must_use]
+    pub fn to_move<R: CetkaikRepresentation>(self) -> Option<PureMove<R>> {
+        policy_index_to_move::<R>(self.policy_index)
+    }
+}
+
+/// The iterator returned by [`OpeningBook::probe`]
+/// ／[`OpeningBook::probe`] が返すイテレータ
+#[derive(Clone, Debug)]
+pub struct BookMoves(std::vec::IntoIter<BookMove>);
+
+impl Iterator for BookMoves {
+    type Item = BookMove;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for BookMoves {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl ExactSizeIterator for BookMoves {}
+
+impl core::iter::FusedIterator for BookMoves {}
+
+impl FromIterator<BookMove> for BookMoves {
+    fn from_iter<I: IntoIterator<Item = BookMove>>(iter: I) -> Self {
+        Self(iter.into_iter().collect::<Vec<_>>().into_iter())
+    }
+}
+
+/// A trait that signifies that you can look up positions in it as an opening book
+/// ／定跡として局面を引ける型を表すトレイト
+///
+/// Positions are looked up by [`book_key`] (or [`IsGameState::book_key`](crate::IsGameState::book_key)),
+/// which does not depend on the representation.
+/// ／局面は表現に依らない [`book_key`]（または [`IsGameState::book_key`](crate::IsGameState::book_key)）によって引く。
+pub trait OpeningBook {
+    /// The moves stored for the position with `key`, or `None` if the position is out of the book
+    /// ／キーが `key` である局面に対して格納されている指し手。局面が定跡にないなら `None`
+    fn probe(&self, key: u128) -> Option<BookMoves>;
+}
+
+impl<S: BuildHasher> OpeningBook for HashMap<u128, Vec<BookMove>, S> {
+    fn probe(&self, key: u128) -> Option<BookMoves> {
+        self.get(&key).map(|moves| moves.iter().copied().collect())
+    }
+}
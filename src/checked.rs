@@ -0,0 +1,200 @@
+use crate::setup::census;
+use crate::{IsAbsoluteField, IsBoard, IsField, IsPieceWithSide};
+use cetkaik_fundamental::{Color, Profession};
+
+/// A wrapper that validates every operation on a board or a field, panicking with a report in debug builds
+/// ／盤面や `Field` への操作をすべて検証し、デバッグビルドでは報告とともにパニックするラッパー
+///
+/// As a board, it panics when a piece is put onto an occupied square, when the number of pieces on the board
+/// does not change the way the operation implies, or when more than one Tam2 appears.
+/// As a field, it panics when a move or a parachute changes the census of the pieces on the board and in the hop1zuo1s.
+/// In release builds, it simply forwards every operation.
+/// ／盤面としては、駒のあるマスに駒を置いたとき、盤上の駒の数が操作から予想されるとおりに変化しなかったとき、皇が二つ以上現れたときにパニックする。
+/// `Field` としては、駒の移動や打ち込みによって盤上と手駒の駒の内訳が変化したときにパニックする。
+/// リリースビルドでは単にすべての操作を転送する。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Checked<T>(pub T);
+
+impl<T> Checked<T> {
+    /// Unwraps the inner value
+    /// ／中身を取り出す
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+fn tam2_count<B>(board: &B) -> usize
+where
+    B: IsBoard,
+    B::PieceWithSide: IsPieceWithSide,
+{
+    board
+        .occupied_squares()
+        .filter(|&c| {
+            board
+                .peek(c)
+                .is_some_and(|p| p.match_on_piece_and_apply(&|| true, &|_, _, _| false))
+        })
+        .count()
+}
+
+impl<B> Checked<B>
+where
+    B: IsBoard + core::fmt::Debug,
+    B::PieceWithSide: IsPieceWithSide,
+{
+    fn check(&self, operation: core::fmt::Arguments<'_>, count_before: usize, expected: usize) {
+        let count_after = self.0.occupied_count();
+        assert!(
+            count_after == expected,
+            "Checked: after {operation}, the board has {count_after} pieces \
+             where {expected} were expected (there were {count_before} before)\nboard: {:?}",
+            self.0
+        );
+        let tam2 = tam2_count(&self.0);
+        assert!(
+            tam2 <= 1,
+            "Checked: after {operation}, the board has {tam2} Tam2s\nboard: {:?}",
+            self.0
+        );
+    }
+}
+
+impl<B> IsBoard for Checked<B>
+where
+    B: IsBoard + core::fmt::Debug,
+    B::PieceWithSide: IsPieceWithSide,
+{
+    type PieceWithSide = B::PieceWithSide;
+    type Coord = B::Coord;
+
+    fn peek(&self, c: Self::Coord) -> Option<Self::PieceWithSide> {
+        self.0.peek(c)
+    }
+    fn pop(&mut self, c: Self::Coord) -> Option<Self::PieceWithSide> {
+        if !cfg!(debug_assertions) {
+            return self.0.pop(c);
+        }
+        let before = self.0.occupied_count();
+        let piece = self.0.pop(c);
+        let expected = if piece.is_some() { before - 1 } else { before };
+        self.check(format_args!("`pop({c:?})`"), before, expected);
+        piece
+    }
+    fn put(&mut self, c: Self::Coord, p: Option<Self::PieceWithSide>) {
+        if !cfg!(debug_assertions) {
+            return self.0.put(c, p);
+        }
+        let before = self.0.occupied_count();
+        let occupied = self.0.peek(c).is_some();
+        assert!(
+            !(occupied && p.is_some()),
+            "Checked: `put({c:?}, Some(..))` onto an occupied square\nboard: {:?}",
+            self.0
+        );
+        self.0.put(c, p);
+        let expected = match (occupied, p.is_some()) {
+            (false, true) => before + 1,
+            (true, false) => before - 1,
+            _ => before,
+        };
+        self.check(format_args!("`put({c:?}, ..)`"), before, expected);
+    }
+    fn assert_empty(&self, c: Self::Coord) {
+        self.0.assert_empty(c);
+    }
+    fn assert_occupied(&self, c: Self::Coord) {
+        self.0.assert_occupied(c);
+    }
+    fn mov(&mut self, from: Self::Coord, to: Self::Coord) {
+        if !cfg!(debug_assertions) {
+            return self.0.mov(from, to);
+        }
+        let before = self.0.occupied_count();
+        self.0.mov(from, to);
+        assert!(
+            self.0.peek(from).is_none() && self.0.peek(to).is_some(),
+            "Checked: after `mov({from:?}, {to:?})`, the origin is not empty or the destination is empty\nboard: {:?}",
+            self.0
+        );
+        self.check(format_args!("`mov({from:?}, {to:?})`"), before, before);
+    }
+
+    type EmptySquaresIter = B::EmptySquaresIter;
+    fn empty_squares(&self) -> Self::EmptySquaresIter {
+        self.0.empty_squares()
+    }
+
+    type OccupiedSquaresIter = B::OccupiedSquaresIter;
+    fn occupied_squares(&self) -> Self::OccupiedSquaresIter {
+        self.0.occupied_squares()
+    }
+}
+
+impl<F> Checked<F>
+where
+    F: IsAbsoluteField + core::fmt::Debug,
+    F::PieceWithSide: IsPieceWithSide,
+{
+    fn check_census(&self, operation: core::fmt::Arguments<'_>, result: &F) {
+        if cfg!(debug_assertions) {
+            let (before, after) = (census(&self.0), census(result));
+            assert!(
+                before == after,
+                "Checked: {operation} changed the census of pieces from {before:?} to {after:?}\nbefore: {:?}\nafter: {result:?}",
+                self.0
+            );
+        }
+    }
+}
+
+impl<F> IsField for Checked<F>
+where
+    F: IsAbsoluteField + core::fmt::Debug,
+    F::Coord: Copy,
+    F::PieceWithSide: IsPieceWithSide,
+{
+    type Board = F::Board;
+    type Coord = F::Coord;
+    type PieceWithSide = F::PieceWithSide;
+    type Side = F::Side;
+
+    fn move_nontam_piece_from_src_to_dest_while_taking_opponent_piece_if_needed(
+        &self,
+        from: Self::Coord,
+        to: Self::Coord,
+        whose_turn: Self::Side,
+    ) -> Result<Self, &'static str> {
+        let result = self
+            .0
+            .move_nontam_piece_from_src_to_dest_while_taking_opponent_piece_if_needed(
+                from, to, whose_turn,
+            )?;
+        self.check_census(format_args!("moving from {from:?} to {to:?}"), &result);
+        Ok(Self(result))
+    }
+
+    fn search_from_hop1zuo1_and_parachute_at(
+        &self,
+        color: Color,
+        prof: Profession,
+        side: Self::Side,
+        dest: Self::Coord,
+    ) -> Option<Self> {
+        let result = self
+            .0
+            .search_from_hop1zuo1_and_parachute_at(color, prof, side, dest)?;
+        self.check_census(format_args!("parachuting at {dest:?}"), &result);
+        Some(Self(result))
+    }
+
+    fn as_board(&self) -> &Self::Board {
+        self.0.as_board()
+    }
+
+    /// Mutably borrows the board; operations through this borrow are not checked
+    /// ／盤面を可変で借用する。この借用を通じた操作は検証されない。
+    fn as_board_mut(&mut self) -> &mut Self::Board {
+        self.0.as_board_mut()
+    }
+}
@@ -0,0 +1,51 @@
+/// A trait that signifies that you can use it to cast the five sticks (ciurl)
+/// ／五本の投げ棒（ciurl）を投げるのに使える型を表すトレイト
+///
+/// A search engine would plug in a [`FixedCiurl`], a client of a server a [`FnCiurl`] that asks the server, and a local game a random source.
+/// ／探索エンジンなら [`FixedCiurl`]、サーバーのクライアントならサーバーに問い合わせる [`FnCiurl`]、ローカルの対局なら乱数による実装を使うとよい。
+pub trait IsCiurlSource {
+    /// Casts the sticks and returns how many of them fell face up, which is at most 5
+    /// ／投げ棒を投げ、表になった本数を返す。5 以下である。
+    fn cast(&mut self) -> u8;
+}
+
+impl<C: IsCiurlSource + ?Sized> IsCiurlSource for &mut C {
+    fn cast(&mut self) -> u8 {
+        (**self).cast()
+    }
+}
+
+/// A source that always casts the same result
+/// ／常に同じ結果を出す投げ棒
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FixedCiurl(pub u8);
+
+impl IsCiurlSource for FixedCiurl {
+    fn cast(&mut self) -> u8 {
+        self.0
+    }
+}
+
+/// A source that asks a closure, e.g. one that receives the result from a server
+/// ／クロージャに問い合わせる投げ棒。サーバーから結果を受け取るクロージャなどを想定している。
+#[derive(Clone, Copy, Debug)]
+pub struct FnCiurl<F: FnMut() -> u8>(pub F);
+
+impl<F: FnMut() -> u8> IsCiurlSource for FnCiurl<F> {
+    fn cast(&mut self) -> u8 {
+        (self.0)()
+    }
+}
+
+/// A source that casts each of the five sticks with a fair coin drawn from a random number generator
+/// ／乱数生成器を用いて、五本の投げ棒をそれぞれ公平に投げる投げ棒
+#[cfg(feature = "rand")]
+#[derive(Clone, Debug)]
+pub struct RngCiurl<Rn: rand::Rng>(pub Rn);
+
+#[cfg(feature = "rand")]
+impl<Rn: rand::Rng> IsCiurlSource for RngCiurl<Rn> {
+    fn cast(&mut self) -> u8 {
+        (0..5).map(|_| u8::from(self.0.random::<bool>())).sum()
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{IsAbsoluteCoord, IsAbsoluteField, IsField, IsHop1Zuo1, IsPieceWithSide};
+use cetkaik_fundamental::{AbsoluteSide, Color, ColorAndProf, Profession};
+use std::sync::Arc;
+
+/// A field that shares its contents with its clones until one of them is mutated
+/// ／変更されるまでクローンどうしで中身を共有する `Field`
+///
+/// Cloning only bumps a reference count, and the underlying field is cloned on the first mutation of a shared copy,
+/// so that a wide search tree keeping many sibling positions does not pay for copies it never modifies.
+/// ／クローンは参照カウントを増やすだけで、共有されている複製が初めて変更されたときに元の `Field` がクローンされる。
+/// そのため、兄弟局面を多数保持する幅の広い探索木でも、変更しない複製の費用はかからない。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CowField<F>(Arc<F>);
+
+impl<F> CowField<F> {
+    /// Wraps `field`
+    /// ／`field` を包む
+    pub fn new(field: F) -> Self {
+        Self(Arc::new(field))
+    }
+
+    /// The wrapped field
+    /// ／包まれている `Field`
+    #[must_use]
+    pub fn get_ref(&self) -> &F {
+        &self.0
+    }
+
+    /// Whether the contents are currently shared with another clone
+    /// ／中身が現在ほかのクローンと共有されているかどうか
+    #[must_use]
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.0) > 1
+    }
+}
+
+impl<F: Clone> CowField<F> {
+    /// Unwraps the field, cloning it only if it is shared
+    /// ／`Field` を取り出す。共有されている場合に限りクローンする。
+    #[must_use]
+    pub fn into_inner(self) -> F {
+        Arc::try_unwrap(self.0).unwrap_or_else(|shared| (*shared).clone())
+    }
+
+    /// Mutably borrows the field, cloning it first if it is shared
+    /// ／`Field` を可変で借用する。共有されている場合は先にクローンする。
+    pub fn make_mut(&mut self) -> &mut F {
+        Arc::make_mut(&mut self.0)
+    }
+}
+
+impl<F: IsField + Clone> IsField for CowField<F> {
+    type Board = F::Board;
+    type Coord = F::Coord;
+    type PieceWithSide = F::PieceWithSide;
+    type Side = F::Side;
+
+    fn move_nontam_piece_from_src_to_dest_while_taking_opponent_piece_if_needed(
+        &self,
+        from: Self::Coord,
+        to: Self::Coord,
+        whose_turn: Self::Side,
+    ) -> Result<Self, &'static str> {
+        self.0
+            .move_nontam_piece_from_src_to_dest_while_taking_opponent_piece_if_needed(
+                from, to, whose_turn,
+            )
+            .map(Self::new)
+    }
+
+    fn search_from_hop1zuo1_and_parachute_at(
+        &self,
+        color: Color,
+        prof: Profession,
+        side: Self::Side,
+        dest: Self::Coord,
+    ) -> Option<Self> {
+        self.0
+            .search_from_hop1zuo1_and_parachute_at(color, prof, side, dest)
+            .map(Self::new)
+    }
+
+    fn as_board(&self) -> &Self::Board {
+        self.0.as_board()
+    }
+
+    /// Mutably borrows the board, cloning the field first if it is shared
+    /// ／盤面を可変で借用する。`Field` が共有されている場合は先にクローンする。
+    fn as_board_mut(&mut self) -> &mut Self::Board {
+        self.make_mut().as_board_mut()
+    }
+}
+
+impl<F: IsAbsoluteField + Clone> IsAbsoluteField for CowField<F> {
+    fn yhuap_initial() -> Self
+    where
+        F::Coord: IsAbsoluteCoord,
+        F::PieceWithSide: IsPieceWithSide<Side = AbsoluteSide>,
+    {
+        Self::new(F::yhuap_initial())
+    }
+
+    fn empty() -> Self {
+        Self::new(F::empty())
+    }
+
+    type Hop1Zuo1 = F::Hop1Zuo1;
+    fn hop1zuo1(&self, side: AbsoluteSide) -> &Self::Hop1Zuo1 {
+        self.0.hop1zuo1(side)
+    }
+
+    fn add_to_hop1zuo1(&mut self, side: AbsoluteSide, piece: ColorAndProf) {
+        self.make_mut().add_to_hop1zuo1(side, piece);
+    }
+
+    fn remove_from_hop1zuo1(&mut self, side: AbsoluteSide, piece: ColorAndProf) -> bool {
+        // Avoid cloning a shared field only to find that the piece is not there
+        if !self.0.hop1zuo1(side).contains(piece.color, piece.prof) {
+            return false;
+        }
+        self.make_mut().remove_from_hop1zuo1(side, piece)
+    }
+}
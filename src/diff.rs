@@ -0,0 +1,119 @@
+use crate::dynamic::sort_hop1zuo1;
+use crate::IsAbsoluteField;
+use cetkaik_fundamental::{AbsoluteSide, ColorAndProf};
+
+/// A square whose contents differ between two boards
+/// ／二つの盤面で中身が異なるマス
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SquareDiff<C, P> {
+    /// the square／マス
+    pub coord: C,
+    /// what was there before／変化前の中身
+    pub before: Option<P>,
+    /// what is there after／変化後の中身
+    pub after: Option<P>,
+}
+
+/// An iterator over the squares whose contents differ between two boards
+/// ／二つの盤面で中身が異なるマスを列挙するイテレータ
+#[derive(Clone, Debug)]
+pub struct DiffIter<C, P>(std::vec::IntoIter<SquareDiff<C, P>>);
+
+impl<C, P> Iterator for DiffIter<C, P> {
+    type Item = SquareDiff<C, P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<C, P> FromIterator<SquareDiff<C, P>> for DiffIter<C, P> {
+    fn from_iter<I: IntoIterator<Item = SquareDiff<C, P>>>(iter: I) -> Self {
+        Self(iter.into_iter().collect::<Vec<_>>().into_iter())
+    }
+}
+
+/// The pieces that entered and left a hop1zuo1
+/// ／手駒に加わった駒と手駒から出た駒
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Hop1Zuo1Diff {
+    /// pieces that are newly in the hop1zuo1／新たに手駒に加わった駒
+    pub added: Vec<ColorAndProf>,
+    /// pieces that are no longer in the hop1zuo1／手駒から出た駒
+    pub removed: Vec<ColorAndProf>,
+}
+
+impl Hop1Zuo1Diff {
+    /// Compares two hop1zuo1s as multisets.
+    /// The pieces are listed in the order they appear in `before` and `after` respectively.
+    /// ／二つの手駒を多重集合として比較する。駒はそれぞれ `before`、`after` に現れる順に並ぶ。
+    pub fn between(
+        before: impl IntoIterator<Item = ColorAndProf>,
+        after: impl IntoIterator<Item = ColorAndProf>,
+    ) -> Self {
+        let mut removed = before.into_iter().collect::<Vec<_>>();
+        let mut added = vec![];
+        for piece in after {
+            if let Some(i) = removed.iter().position(|p| *p == piece) {
+                removed.remove(i);
+            } else {
+                added.push(piece);
+            }
+        }
+        Self { added, removed }
+    }
+
+    /// Checks whether the hop1zuo1 is unchanged
+    /// ／手駒に変化がないかどうか
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// The difference between two fields: the squares that changed and the changes in both hop1zuo1s
+/// ／二つの `Field` の差分。変化したマスと、両者の手駒の変化からなる。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FieldDiff<C, P> {
+    /// squares whose contents differ／中身が異なるマス
+    pub squares: Vec<SquareDiff<C, P>>,
+    /// changes in the hop1zuo1 of `ASide`／`ASide` の手駒の変化
+    pub a_side_hop1zuo1: Hop1Zuo1Diff,
+    /// changes in the hop1zuo1 of `IASide`／`IASide` の手駒の変化
+    pub ia_side_hop1zuo1: Hop1Zuo1Diff,
+}
+
+impl<C, P> FieldDiff<C, P> {
+    /// Checks whether the two fields were identical
+    /// ／二つの `Field` が同一であったかどうか
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.squares.is_empty()
+            && self.a_side_hop1zuo1.is_empty()
+            && self.ia_side_hop1zuo1.is_empty()
+    }
+}
+
+/// The pieces that entered either hop1zuo1 between `before` and `after`, that is, the pieces taken in between
+/// ／`before` から `after` までの間にいずれかの手駒に加わった駒、すなわちその間に取られた駒
+///
+/// The pieces are sorted by color (Kok1 first) and then by profession in the order Nuak1, Kauk2, Gua2, Kaun1, Dau2, Maun1, Kua2, Tuk2, Uai1, Io,
+/// so the result does not depend on the order in which the representation lists its hop1zuo1s.
+/// ／駒は色（赤が先）、次に職種（船・兵・弓・車・虎・馬・筆・巫・将・王の順）で整列されるので、
+/// 結果は表現が手駒を列挙する順序に依らない。
+pub fn captures_between<F: IsAbsoluteField>(
+    before: &F,
+    after: &F,
+) -> impl Iterator<Item = ColorAndProf> {
+    let mut captured = vec![];
+    for side in [AbsoluteSide::ASide, AbsoluteSide::IASide] {
+        captured
+            .extend(Hop1Zuo1Diff::between(before.hop1zuo1_of(side), after.hop1zuo1_of(side)).added);
+    }
+    sort_hop1zuo1(&mut captured);
+    captured.into_iter()
+}
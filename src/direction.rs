@@ -0,0 +1,171 @@
+use crate::movement::offset_index;
+use crate::{CetkaikRepresentation, COLS_U8};
+
+/// One of the eight directions in which a coordinate can be stepped
+/// ／座標を進めることのできる八方向のうちの一つ
+///
+/// In absolute coordinates, `Up` points toward the row A and `Right` toward the column P.
+/// In relative coordinates, `Up` points away from the player holding the perspective, and `Right` to their right.
+/// ／絶対座標では `Up` は A の行へ、`Right` は P の列へ向かう。
+/// 相対座標では `Up` は視点を持つプレイヤーから遠ざかる向き、`Right` はそのプレイヤーの右へ向かう。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// 上
+    Up,
+    /// 右上
+    UpRight,
+    /// 右
+    Right,
+    /// 右下
+    DownRight,
+    /// 下
+    Down,
+    /// 左下
+    DownLeft,
+    /// 左
+    Left,
+    /// 左上
+    UpLeft,
+}
+
+impl Direction {
+    /// All eight directions, clockwise from `Up`
+    /// ／八方向すべて。`Up` から時計回り
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// The unit step, as `[row, column]`, in the convention of [`MovementPattern::dir`](crate::MovementPattern::dir)
+    /// ／一歩の変位。[`MovementPattern::dir`](crate::MovementPattern::dir) と同じく `[行, 列]` で表す。
+    #[must_use]
+    pub const fn delta(self) -> [i8; 2] {
+        match self {
+            Self::Up => [-1, 0],
+            Self::UpRight => [-1, 1],
+            Self::Right => [0, 1],
+            Self::DownRight => [1, 1],
+            Self::Down => [1, 0],
+            Self::DownLeft => [1, -1],
+            Self::Left => [0, -1],
+            Self::UpLeft => [-1, -1],
+        }
+    }
+
+    /// The direction whose unit step is `delta`, or `None` if there is none
+    /// ／一歩の変位が `delta` である方向。なければ `None`
+    #[must_use]
+    pub const fn from_delta(delta: [i8; 2]) -> Option<Self> {
+        Some(match delta {
+            [-1, 0] => Self::Up,
+            [-1, 1] => Self::UpRight,
+            [0, 1] => Self::Right,
+            [1, 1] => Self::DownRight,
+            [1, 0] => Self::Down,
+            [1, -1] => Self::DownLeft,
+            [0, -1] => Self::Left,
+            [-1, -1] => Self::UpLeft,
+            _ => return None,
+        })
+    }
+
+    /// The direction pointing the other way
+    /// ／逆の方向
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::UpRight => Self::DownLeft,
+            Self::Right => Self::Left,
+            Self::DownRight => Self::UpLeft,
+            Self::Down => Self::Up,
+            Self::DownLeft => Self::UpRight,
+            Self::Left => Self::Right,
+            Self::UpLeft => Self::DownRight,
+        }
+    }
+
+    /// Whether this is one of the four diagonal directions
+    /// ／斜めの四方向のいずれかであるかどうか
+    #[must_use]
+    pub const fn is_diagonal(self) -> bool {
+        matches!(
+            self,
+            Self::UpRight | Self::DownRight | Self::DownLeft | Self::UpLeft
+        )
+    }
+}
+
+pub(crate) fn step_absolute<R: CetkaikRepresentation>(
+    coord: R::AbsoluteCoord,
+    dir: Direction,
+    n: u8,
+) -> Option<R::AbsoluteCoord> {
+    // anything longer than 8 steps leaves the board
+    let n = i8::try_from(n).ok().filter(|&n| n <= 8)?;
+    let [dr, dc] = dir.delta();
+    offset_index(R::coord_to_index(coord), [dr * n, dc * n]).and_then(R::index_to_coord)
+}
+
+pub(crate) fn direction_between_absolute<R: CetkaikRepresentation>(
+    a: R::AbsoluteCoord,
+    b: R::AbsoluteCoord,
+) -> Option<Direction> {
+    let (a, b) = (R::coord_to_index(a), R::coord_to_index(b));
+    let dr = i16::from(b / COLS_U8) - i16::from(a / COLS_U8);
+    let dc = i16::from(b % COLS_U8) - i16::from(a % COLS_U8);
+    if dr != 0 && dc != 0 && dr.abs() != dc.abs() {
+        return None;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    Direction::from_delta([dr.signum() as i8, dc.signum() as i8])
+}
+
+/// The row and column offsets from `a` to `b` in absolute coordinates
+pub(crate) fn delta_absolute<R: CetkaikRepresentation>(
+    a: R::AbsoluteCoord,
+    b: R::AbsoluteCoord,
+) -> [i32; 2] {
+    let (a, b) = (R::coord_to_index(a), R::coord_to_index(b));
+    [
+        i32::from(b / COLS_U8) - i32::from(a / COLS_U8),
+        i32::from(b % COLS_U8) - i32::from(a % COLS_U8),
+    ]
+}
+
+/// The row and column offsets from `a` to `b` in relative coordinates, found by trying every offset with [`CetkaikRepresentation::add_delta`]
+pub(crate) fn delta_relative<R: CetkaikRepresentation>(
+    a: R::RelativeCoord,
+    b: R::RelativeCoord,
+) -> [i32; 2] {
+    (-8..=8)
+        .flat_map(|dr| (-8..=8).map(move |dc| [dr, dc]))
+        .find(|&[dr, dc]| R::add_delta(a, dr, dc) == Some(b))
+        .map(|[dr, dc]| [dr, dc].map(|d| i32::try_from(d).expect("at most 8")))
+        .expect("any two squares on the board are at most 8 apart in each direction")
+}
+
+pub(crate) fn step_relative<R: CetkaikRepresentation>(
+    coord: R::RelativeCoord,
+    dir: Direction,
+    n: u8,
+) -> Option<R::RelativeCoord> {
+    let [dr, dc] = dir.delta();
+    let n = isize::from(n);
+    R::add_delta(coord, isize::from(dr) * n, isize::from(dc) * n)
+}
+
+pub(crate) fn direction_between_relative<R: CetkaikRepresentation>(
+    a: R::RelativeCoord,
+    b: R::RelativeCoord,
+) -> Option<Direction> {
+    Direction::ALL
+        .into_iter()
+        .find(|&dir| (1..=8).any(|n| step_relative::<R>(a, dir, n) == Some(b)))
+}
@@ -0,0 +1,519 @@
+use crate::repetition::side_index;
+use crate::{
+    apply_move, CetkaikRepresentation, IsAbsoluteBoard, IsAbsoluteField, IsBoard, IsField,
+    IsPieceWithSide,
+};
+use cetkaik_fundamental::{AbsoluteSide, Color, ColorAndProf, Profession, PureMove_};
+
+/// A piece written in a form that does not depend on the representation
+/// ／表現に依らない形で書かれた駒
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CanonicalPiece {
+    /// Tam2／皇
+    Tam2,
+    /// A piece other than Tam2／皇以外の駒
+    NonTam2Piece {
+        /// color of the piece／駒の色
+        color: Color,
+        /// profession of the piece／駒の職種
+        prof: Profession,
+        /// which side the piece belongs to／駒の所属側
+        side: AbsoluteSide,
+    },
+}
+
+impl CanonicalPiece {
+    /// Erases the representation of `piece`
+    /// ／`piece` の表現を消去する
+    pub fn from_piece<P: IsPieceWithSide<Side = AbsoluteSide>>(piece: P) -> Self {
+        piece.match_on_piece_and_apply(&|| Self::Tam2, &|color, prof, side| Self::NonTam2Piece {
+            color,
+            prof,
+            side,
+        })
+    }
+
+    /// Converts back into the representation `R`
+    /// ／表現 `R` に戻す
+    #[must_use]
+    pub fn to_piece<R: CetkaikRepresentation>(self) -> R::AbsolutePiece {
+        match self {
+            Self::Tam2 => R::absolute_tam2(),
+            Self::NonTam2Piece { color, prof, side } => {
+                R::AbsolutePiece::non_tam2_piece(color, prof, side)
+            }
+        }
+    }
+
+    /// Numbers Tam2 as 0 and the other pieces from 1 to 40
+    pub(crate) const fn code(self) -> u8 {
+        match self {
+            Self::Tam2 => 0,
+            Self::NonTam2Piece { color, prof, side } => {
+                1 + 20 * side_index(side) + 10 * color_index(color) + prof_index(prof)
+            }
+        }
+    }
+
+    /// The inverse of `code`
+    pub(crate) const fn from_code(code: u8) -> Option<Self> {
+        if code == 0 {
+            return Some(Self::Tam2);
+        }
+        let (side, kind) = match code - 1 {
+            k @ 0..=19 => (AbsoluteSide::ASide, k),
+            k @ 20..=39 => (AbsoluteSide::IASide, k - 20),
+            _ => return None,
+        };
+        let (Some(color), Some(prof)) = (color_from_index(kind / 10), prof_from_index(kind % 10))
+        else {
+            return None;
+        };
+        Some(Self::NonTam2Piece { color, prof, side })
+    }
+}
+
+/// Sorts pieces into the canonical order of hop1zuo1s: by color (Kok1 first), and then by profession in the order of `prof_index`
+pub(crate) fn sort_hop1zuo1(pieces: &mut [ColorAndProf]) {
+    pieces.sort_by_key(|p| (color_index(p.color), prof_index(p.prof)));
+}
+
+/// Numbers Kok1 and Huok2 as 0 and 1
+pub(crate) const fn color_index(color: Color) -> u8 {
+    match color {
+        Color::Kok1 => 0,
+        Color::Huok2 => 1,
+    }
+}
+
+/// Numbers Nuak1, Kauk2, Gua2, Kaun1, Dau2, Maun1, Kua2, Tuk2, Uai1, Io from 0 to 9
+pub(crate) const fn prof_index(prof: Profession) -> u8 {
+    match prof {
+        Profession::Nuak1 => 0,
+        Profession::Kauk2 => 1,
+        Profession::Gua2 => 2,
+        Profession::Kaun1 => 3,
+        Profession::Dau2 => 4,
+        Profession::Maun1 => 5,
+        Profession::Kua2 => 6,
+        Profession::Tuk2 => 7,
+        Profession::Uai1 => 8,
+        Profession::Io => 9,
+    }
+}
+
+/// Numbers a kind of piece in a hop1zuo1 as `10 * color_index + prof_index`
+pub(crate) const fn kind_code(piece: ColorAndProf) -> u8 {
+    10 * color_index(piece.color) + prof_index(piece.prof)
+}
+
+/// The inverse of `kind_code`
+pub(crate) const fn kind_from_code(code: u8) -> Option<ColorAndProf> {
+    match (color_from_index(code / 10), prof_from_index(code % 10)) {
+        (Some(color), Some(prof)) => Some(ColorAndProf { color, prof }),
+        _ => None,
+    }
+}
+
+/// The inverse of `color_index`
+pub(crate) const fn color_from_index(color: u8) -> Option<Color> {
+    match color {
+        0 => Some(Color::Kok1),
+        1 => Some(Color::Huok2),
+        _ => None,
+    }
+}
+
+/// The inverse of `prof_index`
+pub(crate) const fn prof_from_index(prof: u8) -> Option<Profession> {
+    match prof {
+        0 => Some(Profession::Nuak1),
+        1 => Some(Profession::Kauk2),
+        2 => Some(Profession::Gua2),
+        3 => Some(Profession::Kaun1),
+        4 => Some(Profession::Dau2),
+        5 => Some(Profession::Maun1),
+        6 => Some(Profession::Kua2),
+        7 => Some(Profession::Tuk2),
+        8 => Some(Profession::Uai1),
+        9 => Some(Profession::Io),
+        _ => None,
+    }
+}
+
+/// An object-safe version of [`IsBoard`]
+/// ／[`IsBoard`] のオブジェクト安全版
+///
+/// Coordinates are written as their canonical indices (see [`CetkaikRepresentation::coord_to_index`]) and pieces as [`CanonicalPiece`].
+/// ／座標は正準な番号（[`CetkaikRepresentation::coord_to_index`] を参照）で、駒は [`CanonicalPiece`] で表す。
+///
+/// # Panics
+/// The methods that take an index panic if it is 81 or more.
+/// ／番号を受け取るメソッドは、番号が 81 以上ならパニックする。
+pub trait DynBoard: core::fmt::Debug {
+    /// Looks at a square
+    /// ／マスを見る
+    fn peek(&self, index: u8) -> Option<CanonicalPiece>;
+    /// Removes the piece at a square and returns it
+    /// ／マスの駒を取り除いて返す
+    fn pop(&mut self, index: u8) -> Option<CanonicalPiece>;
+    /// Puts either a piece or a `None` at a square
+    /// ／マスに駒または `None` を置く
+    fn put(&mut self, index: u8, piece: Option<CanonicalPiece>);
+    /// Lists the empty squares
+    /// ／空いているマスを列挙する
+    fn empty_squares(&self) -> Vec<u8>;
+    /// Lists the occupied squares
+    /// ／駒があるマスを列挙する
+    fn occupied_squares(&self) -> Vec<u8>;
+}
+
+/// An object-safe version of [`IsAbsoluteField`]
+/// ／[`IsAbsoluteField`] のオブジェクト安全版
+///
+/// As in [`DynBoard`], coordinates are written as their canonical indices and pieces as [`CanonicalPiece`].
+/// ／[`DynBoard`] と同様に、座標は正準な番号で、駒は [`CanonicalPiece`] で表す。
+pub trait DynField: DynBoard {
+    /// Lists the hop1zuo1 of `side`
+    /// ／`side` の手駒を列挙する
+    fn hop1zuo1_of(&self, side: AbsoluteSide) -> Vec<ColorAndProf>;
+    /// Adds a piece to the hop1zuo1 of `side`
+    /// ／`side` の手駒に駒を加える
+    fn add_to_hop1zuo1(&mut self, side: AbsoluteSide, piece: ColorAndProf);
+    /// Removes one copy of a piece from the hop1zuo1 of `side`, returning whether it was found
+    /// ／`side` の手駒から駒をひとつ取り除き、見つかったかどうかを返す
+    fn remove_from_hop1zuo1(&mut self, side: AbsoluteSide, piece: ColorAndProf) -> bool;
+    /// Applies a move in the manner of [`apply_move`]
+    /// ／[`apply_move`] と同様に指し手を適用する
+    /// # Errors
+    /// Fails if the move contains an index that is 81 or more, or if [`apply_move`] fails.
+    /// ／指し手が 81 以上の番号を含むか、[`apply_move`] が失敗するならエラー。
+    fn apply_move(
+        &self,
+        mv: PureMove_<u8>,
+        whose_turn: AbsoluteSide,
+    ) -> Result<Box<dyn DynField>, &'static str>;
+    /// Clones the field into a new box
+    /// ／`Field` を新しい `Box` に複製する
+    fn clone_box(&self) -> Box<dyn DynField>;
+}
+
+impl Clone for Box<dyn DynField> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+fn coord<R: CetkaikRepresentation>(index: u8) -> R::AbsoluteCoord {
+    R::index_to_coord(index).unwrap_or_else(|| panic!("Invalid square index {index}"))
+}
+
+pub(crate) fn move_to_coord<R: CetkaikRepresentation>(
+    mv: PureMove_<u8>,
+) -> Result<PureMove_<R::AbsoluteCoord>, &'static str> {
+    let c = |index| R::index_to_coord(index).ok_or("Invalid square index");
+    Ok(match mv {
+        PureMove_::NonTamMoveSrcDst {
+            src,
+            dest,
+            is_water_entry_ciurl,
+        } => PureMove_::NonTamMoveSrcDst {
+            src: c(src)?,
+            dest: c(dest)?,
+            is_water_entry_ciurl,
+        },
+        PureMove_::NonTamMoveSrcStepDstFinite {
+            src,
+            step,
+            dest,
+            is_water_entry_ciurl,
+        } => PureMove_::NonTamMoveSrcStepDstFinite {
+            src: c(src)?,
+            step: c(step)?,
+            dest: c(dest)?,
+            is_water_entry_ciurl,
+        },
+        PureMove_::InfAfterStep {
+            src,
+            step,
+            planned_direction,
+        } => PureMove_::InfAfterStep {
+            src: c(src)?,
+            step: c(step)?,
+            planned_direction: c(planned_direction)?,
+        },
+        PureMove_::NonTamMoveFromHopZuo { color, prof, dest } => PureMove_::NonTamMoveFromHopZuo {
+            color,
+            prof,
+            dest: c(dest)?,
+        },
+        PureMove_::TamMoveNoStep {
+            src,
+            first_dest,
+            second_dest,
+        } => PureMove_::TamMoveNoStep {
+            src: c(src)?,
+            first_dest: c(first_dest)?,
+            second_dest: c(second_dest)?,
+        },
+        PureMove_::TamMoveStepsDuringFormer {
+            src,
+            step,
+            first_dest,
+            second_dest,
+        } => PureMove_::TamMoveStepsDuringFormer {
+            src: c(src)?,
+            step: c(step)?,
+            first_dest: c(first_dest)?,
+            second_dest: c(second_dest)?,
+        },
+        PureMove_::TamMoveStepsDuringLatter {
+            src,
+            step,
+            first_dest,
+            second_dest,
+        } => PureMove_::TamMoveStepsDuringLatter {
+            src: c(src)?,
+            step: c(step)?,
+            first_dest: c(first_dest)?,
+            second_dest: c(second_dest)?,
+        },
+    })
+}
+
+pub(crate) fn move_to_index<R: CetkaikRepresentation>(
+    mv: PureMove_<R::AbsoluteCoord>,
+) -> PureMove_<u8> {
+    let i = R::coord_to_index;
+    match mv {
+        PureMove_::NonTamMoveSrcDst {
+            src,
+            dest,
+            is_water_entry_ciurl,
+        } => PureMove_::NonTamMoveSrcDst {
+            src: i(src),
+            dest: i(dest),
+            is_water_entry_ciurl,
+        },
+        PureMove_::NonTamMoveSrcStepDstFinite {
+            src,
+            step,
+            dest,
+            is_water_entry_ciurl,
+        } => PureMove_::NonTamMoveSrcStepDstFinite {
+            src: i(src),
+            step: i(step),
+            dest: i(dest),
+            is_water_entry_ciurl,
+        },
+        PureMove_::InfAfterStep {
+            src,
+            step,
+            planned_direction,
+        } => PureMove_::InfAfterStep {
+            src: i(src),
+            step: i(step),
+            planned_direction: i(planned_direction),
+        },
+        PureMove_::NonTamMoveFromHopZuo { color, prof, dest } => PureMove_::NonTamMoveFromHopZuo {
+            color,
+            prof,
+            dest: i(dest),
+        },
+        PureMove_::TamMoveNoStep {
+            src,
+            first_dest,
+            second_dest,
+        } => PureMove_::TamMoveNoStep {
+            src: i(src),
+            first_dest: i(first_dest),
+            second_dest: i(second_dest),
+        },
+        PureMove_::TamMoveStepsDuringFormer {
+            src,
+            step,
+            first_dest,
+            second_dest,
+        } => PureMove_::TamMoveStepsDuringFormer {
+            src: i(src),
+            step: i(step),
+            first_dest: i(first_dest),
+            second_dest: i(second_dest),
+        },
+        PureMove_::TamMoveStepsDuringLatter {
+            src,
+            first_dest,
+            step,
+            second_dest,
+        } => PureMove_::TamMoveStepsDuringLatter {
+            src: i(src),
+            first_dest: i(first_dest),
+            step: i(step),
+            second_dest: i(second_dest),
+        },
+    }
+}
+
+fn peek<R: CetkaikRepresentation>(board: &R::AbsoluteBoard, index: u8) -> Option<CanonicalPiece> {
+    board
+        .peek(coord::<R>(index))
+        .map(CanonicalPiece::from_piece)
+}
+
+fn pop<R: CetkaikRepresentation>(
+    board: &mut R::AbsoluteBoard,
+    index: u8,
+) -> Option<CanonicalPiece> {
+    board.pop(coord::<R>(index)).map(CanonicalPiece::from_piece)
+}
+
+fn put<R: CetkaikRepresentation>(
+    board: &mut R::AbsoluteBoard,
+    index: u8,
+    piece: Option<CanonicalPiece>,
+) {
+    board.put(coord::<R>(index), piece.map(CanonicalPiece::to_piece::<R>));
+}
+
+/// A board of the representation `R`, wrapped so that it can be used as a [`DynBoard`]
+/// ／表現 `R` の盤面を、[`DynBoard`] として使えるように包んだもの
+pub struct ErasedBoard<R: CetkaikRepresentation>(pub R::AbsoluteBoard);
+
+impl<R: CetkaikRepresentation> ErasedBoard<R> {
+    /// The initial arrangement of the official (yhuap) rule
+    /// ／官定の初期配置
+    #[must_use]
+    pub fn yhuap_initial() -> Self {
+        Self(R::AbsoluteBoard::yhuap_initial())
+    }
+
+    /// A board with no piece on it
+    /// ／駒がひとつもない盤面
+    #[must_use]
+    pub fn empty() -> Self {
+        Self(R::AbsoluteBoard::empty())
+    }
+}
+
+impl<R: CetkaikRepresentation> Clone for ErasedBoard<R> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<R: CetkaikRepresentation> core::fmt::Debug for ErasedBoard<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("ErasedBoard").field(&self.0).finish()
+    }
+}
+
+impl<R: CetkaikRepresentation> DynBoard for ErasedBoard<R> {
+    fn peek(&self, index: u8) -> Option<CanonicalPiece> {
+        peek::<R>(&self.0, index)
+    }
+    fn pop(&mut self, index: u8) -> Option<CanonicalPiece> {
+        pop::<R>(&mut self.0, index)
+    }
+    fn put(&mut self, index: u8, piece: Option<CanonicalPiece>) {
+        put::<R>(&mut self.0, index, piece);
+    }
+    fn empty_squares(&self) -> Vec<u8> {
+        self.0.empty_squares().map(R::coord_to_index).collect()
+    }
+    fn occupied_squares(&self) -> Vec<u8> {
+        self.0.occupied_squares().map(R::coord_to_index).collect()
+    }
+}
+
+impl<R: CetkaikRepresentation + 'static> From<ErasedBoard<R>> for Box<dyn DynBoard> {
+    fn from(board: ErasedBoard<R>) -> Self {
+        Box::new(board)
+    }
+}
+
+/// A field of the representation `R`, wrapped so that it can be used as a [`DynField`]
+/// ／表現 `R` の `Field` を、[`DynField`] として使えるように包んだもの
+pub struct ErasedField<R: CetkaikRepresentation>(pub R::AbsoluteField);
+
+impl<R: CetkaikRepresentation> ErasedField<R> {
+    /// The initial arrangement of the official (yhuap) rule
+    /// ／官定の初期配置
+    #[must_use]
+    pub fn yhuap_initial() -> Self {
+        Self(R::AbsoluteField::yhuap_initial())
+    }
+
+    /// A field with no piece on the board nor in the hop1zuo1s
+    /// ／盤上にも手駒にも駒がひとつもない `Field`
+    #[must_use]
+    pub fn empty() -> Self {
+        Self(R::AbsoluteField::empty())
+    }
+}
+
+impl<R: CetkaikRepresentation> Clone for ErasedField<R> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<R: CetkaikRepresentation> core::fmt::Debug for ErasedField<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("ErasedField").field(&self.0).finish()
+    }
+}
+
+impl<R: CetkaikRepresentation> DynBoard for ErasedField<R> {
+    fn peek(&self, index: u8) -> Option<CanonicalPiece> {
+        peek::<R>(self.0.as_board(), index)
+    }
+    fn pop(&mut self, index: u8) -> Option<CanonicalPiece> {
+        pop::<R>(self.0.as_board_mut(), index)
+    }
+    fn put(&mut self, index: u8, piece: Option<CanonicalPiece>) {
+        put::<R>(self.0.as_board_mut(), index, piece);
+    }
+    fn empty_squares(&self) -> Vec<u8> {
+        self.0
+            .as_board()
+            .empty_squares()
+            .map(R::coord_to_index)
+            .collect()
+    }
+    fn occupied_squares(&self) -> Vec<u8> {
+        self.0
+            .as_board()
+            .occupied_squares()
+            .map(R::coord_to_index)
+            .collect()
+    }
+}
+
+impl<R: CetkaikRepresentation + 'static> DynField for ErasedField<R> {
+    fn hop1zuo1_of(&self, side: AbsoluteSide) -> Vec<ColorAndProf> {
+        self.0.hop1zuo1_of(side).collect()
+    }
+    fn add_to_hop1zuo1(&mut self, side: AbsoluteSide, piece: ColorAndProf) {
+        self.0.add_to_hop1zuo1(side, piece);
+    }
+    fn remove_from_hop1zuo1(&mut self, side: AbsoluteSide, piece: ColorAndProf) -> bool {
+        self.0.remove_from_hop1zuo1(side, piece)
+    }
+    fn apply_move(
+        &self,
+        mv: PureMove_<u8>,
+        whose_turn: AbsoluteSide,
+    ) -> Result<Box<dyn DynField>, &'static str> {
+        let field = apply_move::<R>(&self.0, move_to_coord::<R>(mv)?, whose_turn)?;
+        Ok(Box::new(Self(field)))
+    }
+    fn clone_box(&self) -> Box<dyn DynField> {
+        Box::new(self.clone())
+    }
+}
+
+impl<R: CetkaikRepresentation + 'static> From<ErasedField<R>> for Box<dyn DynField> {
+    fn from(field: ErasedField<R>) -> Self {
+        Box::new(field)
+    }
+}
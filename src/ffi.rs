@@ -0,0 +1,301 @@
+use crate::dynamic::{color_from_index, color_index, prof_from_index, prof_index, sort_hop1zuo1};
+use crate::{CanonicalPiece, CetkaikRepresentation, DynField, ErasedField, SQUARE_COUNT};
+use cetkaik_fundamental::{AbsoluteSide, ColorAndProf, PureMove_};
+
+/// An opaque handle to a field, owned by the C side until passed to [`cetkaik_field_free`]
+/// ／`Field` を指す不透明なハンドル。[`cetkaik_field_free`] に渡すまでは C 側が所有する。
+pub struct FfiField(Box<dyn DynField>);
+
+/// A piece, or the absence of one, as seen from C
+/// ／C から見た駒、またはその不在
+///
+/// `kind` is 0 for an empty square, 1 for Tam2 and 2 for any other piece.
+/// `color` is 0 for Kok1 and 1 for Huok2; `prof` numbers Nuak1, Kauk2, Gua2, Kaun1, Dau2, Maun1, Kua2, Tuk2, Uai1, Io from 0 to 9;
+/// `side` is 0 for `ASide` and 1 for `IASide`. These three are 0 unless `kind` is 2.
+/// ／`kind` は空きマスなら 0、皇なら 1、それ以外の駒なら 2。
+/// `color` は紅が 0、黒が 1。`prof` は船・兵・弓・車・虎・馬・筆・巫・将・王の順に 0 から 9。
+/// `side` は `ASide` が 0、`IASide` が 1。これら三つは `kind` が 2 でなければ 0。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FfiPiece {
+    /// 0: empty, 1: Tam2, 2: other
+    pub kind: u8,
+    /// color of the piece／駒の色
+    pub color: u8,
+    /// profession of the piece／駒の職種
+    pub prof: u8,
+    /// which side the piece belongs to／駒の所属側
+    pub side: u8,
+}
+
+/// A move as seen from C, with squares written as canonical indices
+/// ／C から見た指し手。マスは正準な番号で書く。
+///
+/// `kind` numbers the variants of [`PureMove_`] in the order of declaration, from `NonTamMoveSrcDst` (0) to `TamMoveStepsDuringLatter` (6).
+/// `dest` holds `dest`, `planned_direction` or `second_dest` depending on the variant; the fields unused by the variant are ignored.
+/// `is_water_entry_ciurl` is 0 for `false` and 1 for `true`; any other value makes the move malformed.
+/// `color` and `prof` are encoded as in [`FfiPiece`].
+/// ／`kind` は [`PureMove_`] の列挙子を宣言順に `NonTamMoveSrcDst` (0) から `TamMoveStepsDuringLatter` (6) まで番号付けたもの。
+/// `dest` には列挙子に応じて `dest`・`planned_direction`・`second_dest` のいずれかを入れる。使われないフィールドは無視される。
+/// `is_water_entry_ciurl` は `false` なら 0、`true` なら 1 で、それ以外の値の指し手は不正である。
+/// `color` と `prof` は [`FfiPiece`] と同様に符号化する。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FfiMove {
+    /// which variant of [`PureMove_`]
+    pub kind: u8,
+    /// origin／開始点
+    pub src: u8,
+    /// via point／経由点
+    pub step: u8,
+    /// first destination of Tam2／皇の一回目の終了点
+    pub first_dest: u8,
+    /// destination／終了点
+    pub dest: u8,
+    /// whether a water-entry ciurl is required: 0 for no, 1 for yes／入水判定が必要かどうか。不要なら 0、必要なら 1
+    pub is_water_entry_ciurl: u8,
+    /// color of the parachuted piece／打つ駒の色
+    pub color: u8,
+    /// profession of the parachuted piece／打つ駒の職種
+    pub prof: u8,
+}
+
+const fn side_from_u8(side: u8) -> Option<AbsoluteSide> {
+    match side {
+        0 => Some(AbsoluteSide::ASide),
+        1 => Some(AbsoluteSide::IASide),
+        _ => None,
+    }
+}
+
+const fn bool_from_u8(flag: u8) -> Option<bool> {
+    match flag {
+        0 => Some(false),
+        1 => Some(true),
+        _ => None,
+    }
+}
+
+impl From<Option<CanonicalPiece>> for FfiPiece {
+    fn from(piece: Option<CanonicalPiece>) -> Self {
+        match piece {
+            None => Self {
+                kind: 0,
+                color: 0,
+                prof: 0,
+                side: 0,
+            },
+            Some(CanonicalPiece::Tam2) => Self {
+                kind: 1,
+                color: 0,
+                prof: 0,
+                side: 0,
+            },
+            Some(CanonicalPiece::NonTam2Piece { color, prof, side }) => Self {
+                kind: 2,
+                color: color_index(color),
+                prof: prof_index(prof),
+                side: match side {
+                    AbsoluteSide::ASide => 0,
+                    AbsoluteSide::IASide => 1,
+                },
+            },
+        }
+    }
+}
+
+impl TryFrom<FfiMove> for PureMove_<u8> {
+    type Error = ();
+
+    fn try_from(mv: FfiMove) -> Result<Self, Self::Error> {
+        let FfiMove {
+            kind,
+            src,
+            step,
+            first_dest,
+            dest,
+            is_water_entry_ciurl,
+            color,
+            prof,
+        } = mv;
+        Ok(match kind {
+            0 => Self::NonTamMoveSrcDst {
+                src,
+                dest,
+                is_water_entry_ciurl: bool_from_u8(is_water_entry_ciurl).ok_or(())?,
+            },
+            1 => Self::NonTamMoveSrcStepDstFinite {
+                src,
+                step,
+                dest,
+                is_water_entry_ciurl: bool_from_u8(is_water_entry_ciurl).ok_or(())?,
+            },
+            2 => Self::InfAfterStep {
+                src,
+                step,
+                planned_direction: dest,
+            },
+            3 => Self::NonTamMoveFromHopZuo {
+                color: color_from_index(color).ok_or(())?,
+                prof: prof_from_index(prof).ok_or(())?,
+                dest,
+            },
+            4 => Self::TamMoveNoStep {
+                src,
+                first_dest,
+                second_dest: dest,
+            },
+            5 => Self::TamMoveStepsDuringFormer {
+                src,
+                step,
+                first_dest,
+                second_dest: dest,
+            },
+            6 => Self::TamMoveStepsDuringLatter {
+                src,
+                step,
+                first_dest,
+                second_dest: dest,
+            },
+            _ => return Err(()),
+        })
+    }
+}
+
+/// Hands a field over to C
+/// ／`Field` を C に渡す
+#[must_use]
+pub fn into_raw(field: Box<dyn DynField>) -> *mut FfiField {
+    Box::into_raw(Box::new(FfiField(field)))
+}
+
+/// Creates the yhuap initial arrangement in the representation `R` and hands it over to C
+/// ／表現 `R` で官定の初期配置を作り、C に渡す
+#[must_use]
+pub fn yhuap_initial<R: CetkaikRepresentation + 'static>() -> *mut FfiField {
+    into_raw(Box::new(ErasedField::<R>::yhuap_initial()))
+}
+
+/// Exports an `extern "C"` function named `$name` that creates the yhuap initial arrangement in the representation `$repr`
+/// ／表現 `$repr` で官定の初期配置を作る `extern "C"` 関数を `$name` という名前で公開する
+#[macro_export]
+macro_rules! export_yhuap_initial {
+    ($name:ident, $repr:ty) => {
+        #[no_mangle]
+        pub extern "C" fn $name() -> *mut $crate::ffi::FfiField {
+            $crate::ffi::yhuap_initial::<$repr>()
+        }
+    };
+}
+
+/// Frees a field
+/// ／`Field` を解放する
+/// # Safety
+/// `field` must be null or a handle obtained from this module that has not been freed yet.
+/// ／`field` はヌルであるか、このモジュールから得られたまだ解放されていないハンドルでなければならない。
+#[no_mangle]
+pub unsafe extern "C" fn cetkaik_field_free(field: *mut FfiField) {
+    if !field.is_null() {
+        drop(Box::from_raw(field));
+    }
+}
+
+/// Duplicates a field
+/// ／`Field` を複製する
+/// # Safety
+/// `field` must be a live handle obtained from this module.
+/// ／`field` はこのモジュールから得られた、解放されていないハンドルでなければならない。
+#[no_mangle]
+pub unsafe extern "C" fn cetkaik_field_clone(field: *const FfiField) -> *mut FfiField {
+    into_raw((*field).0.clone_box())
+}
+
+/// Looks at the square with the canonical index `index`; a square index of 81 or more reads as empty
+/// ／正準な番号 `index` のマスを見る。81 以上の番号は空きマスとして読まれる。
+/// # Safety
+/// `field` must be a live handle obtained from this module.
+/// ／`field` はこのモジュールから得られた、解放されていないハンドルでなければならない。
+#[no_mangle]
+pub unsafe extern "C" fn cetkaik_field_peek(field: *const FfiField, index: u8) -> FfiPiece {
+    if index >= SQUARE_COUNT {
+        return FfiPiece::from(None);
+    }
+    FfiPiece::from((*field).0.peek(index))
+}
+
+/// Applies a move in the manner of [`crate::apply_move`], returning a new field
+/// ／指し手を [`crate::apply_move`] と同様に適用し、新たな `Field` を返す
+///
+/// `whose_turn` is 0 for `ASide` and 1 for `IASide`. Null is returned if the move is malformed or cannot be applied.
+/// ／`whose_turn` は `ASide` なら 0、`IASide` なら 1。指し手が不正であるか適用できなければヌルを返す。
+/// # Safety
+/// `field` must be a live handle obtained from this module, and `mv` must point to a valid [`FfiMove`].
+/// ／`field` はこのモジュールから得られた、解放されていないハンドルでなければならず、`mv` は有効な [`FfiMove`] を指していなければならない。
+#[no_mangle]
+pub unsafe extern "C" fn cetkaik_field_apply_move(
+    field: *const FfiField,
+    mv: *const FfiMove,
+    whose_turn: u8,
+) -> *mut FfiField {
+    let (Ok(mv), Some(whose_turn)) = (PureMove_::<u8>::try_from(*mv), side_from_u8(whose_turn))
+    else {
+        return core::ptr::null_mut();
+    };
+    (*field)
+        .0
+        .apply_move(mv, whose_turn)
+        .map_or(core::ptr::null_mut(), into_raw)
+}
+
+/// Serializes a field into `out`, returning the number of bytes the serialization takes
+/// ／`Field` を `out` に書き出し、書き出しに必要なバイト数を返す
+///
+/// Nothing is written if that exceeds `len`, so call this with `len` 0 first to learn the size.
+/// ／それが `len` を超える場合は何も書き込まないので、まず `len` を 0 にして呼べば大きさがわかる。
+///
+/// The format is the 81 squares in the order of canonical indices, each as the four bytes of [`FfiPiece`],
+/// followed by the hop1zuo1 of `ASide` and then that of `IASide`, each as one byte of count and then two bytes (color, prof) per piece.
+/// The pieces of a hop1zuo1 are written in the canonical order (see [`crate::IsAbsoluteField::hop1zuo1_sorted`]),
+/// so the same position serializes to the same bytes whatever the representation.
+/// ／形式は、81 マスを正準な番号順に [`FfiPiece`] の 4 バイトずつ並べ、続けて `ASide`、`IASide` の順に手駒を、
+/// 個数 1 バイトと駒ごとの 2 バイト（色、職種）で並べたものである。
+/// 手駒は正準な順序（[`crate::IsAbsoluteField::hop1zuo1_sorted`] を参照）で書くので、同じ局面は表現によらず同じバイト列になる。
+/// # Safety
+/// `field` must be a live handle obtained from this module, and `out` must be valid for writes of `len` bytes.
+/// ／`field` はこのモジュールから得られた、解放されていないハンドルでなければならず、`out` は `len` バイトの書き込みに対して有効でなければならない。
+#[no_mangle]
+pub unsafe extern "C" fn cetkaik_field_serialize(
+    field: *const FfiField,
+    out: *mut u8,
+    len: usize,
+) -> usize {
+    let bytes = serialize(&*(*field).0);
+    if bytes.len() <= len {
+        core::ptr::copy_nonoverlapping(bytes.as_ptr(), out, bytes.len());
+    }
+    bytes.len()
+}
+
+fn serialize(field: &dyn DynField) -> Vec<u8> {
+    let mut bytes = vec![];
+    for index in 0..SQUARE_COUNT {
+        let FfiPiece {
+            kind,
+            color,
+            prof,
+            side,
+        } = FfiPiece::from(field.peek(index));
+        bytes.extend([kind, color, prof, side]);
+    }
+    for side in [AbsoluteSide::ASide, AbsoluteSide::IASide] {
+        let mut hop1zuo1 = field.hop1zuo1_of(side);
+        sort_hop1zuo1(&mut hop1zuo1);
+        bytes.push(
+            u8::try_from(hop1zuo1.len()).expect("a hop1zuo1 never holds more than 255 pieces"),
+        );
+        for ColorAndProf { color, prof } in hop1zuo1 {
+            bytes.extend([color_index(color), prof_index(prof)]);
+        }
+    }
+    bytes
+}
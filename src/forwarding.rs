@@ -0,0 +1,89 @@
+use crate::{DiffIter, IsBoard, IsPieceWithSide, ProfIter, SquareDiff};
+use cetkaik_fundamental::Profession;
+
+/// Implements `IsBoard` for a pointer type by forwarding every method, including the overridable ones, to the pointee
+macro_rules! forward_is_board {
+    ($($ptr:ty),*) => {
+        $(
+            impl<B: IsBoard + ?Sized> IsBoard for $ptr {
+                type PieceWithSide = B::PieceWithSide;
+                type Coord = B::Coord;
+
+                fn peek(&self, c: Self::Coord) -> Option<Self::PieceWithSide> {
+                    (**self).peek(c)
+                }
+                fn pop(&mut self, c: Self::Coord) -> Option<Self::PieceWithSide> {
+                    (**self).pop(c)
+                }
+                fn put(&mut self, c: Self::Coord, p: Option<Self::PieceWithSide>) {
+                    (**self).put(c, p);
+                }
+                fn is_empty(&self, c: Self::Coord) -> bool {
+                    (**self).is_empty(c)
+                }
+                fn is_occupied(&self, c: Self::Coord) -> bool {
+                    (**self).is_occupied(c)
+                }
+                fn assert_empty(&self, c: Self::Coord) {
+                    (**self).assert_empty(c);
+                }
+                fn assert_occupied(&self, c: Self::Coord) {
+                    (**self).assert_occupied(c);
+                }
+                fn mov(&mut self, from: Self::Coord, to: Self::Coord) {
+                    (**self).mov(from, to);
+                }
+                fn mov_unchecked(&mut self, from: Self::Coord, to: Self::Coord) {
+                    (**self).mov_unchecked(from, to);
+                }
+                fn swap(&mut self, a: Self::Coord, b: Self::Coord) {
+                    (**self).swap(a, b);
+                }
+
+                type EmptySquaresIter = B::EmptySquaresIter;
+                fn empty_squares(&self) -> Self::EmptySquaresIter {
+                    (**self).empty_squares()
+                }
+
+                type OccupiedSquaresIter = B::OccupiedSquaresIter;
+                fn occupied_squares(&self) -> Self::OccupiedSquaresIter {
+                    (**self).occupied_squares()
+                }
+
+                fn empty_square_count(&self) -> usize {
+                    (**self).empty_square_count()
+                }
+                fn occupied_count(&self) -> usize {
+                    (**self).occupied_count()
+                }
+                fn coords_of(
+                    &self,
+                    prof: Profession,
+                    side: <Self::PieceWithSide as IsPieceWithSide>::Side,
+                ) -> ProfIter<Self::Coord>
+                where
+                    Self::PieceWithSide: IsPieceWithSide,
+                {
+                    (**self).coords_of(prof, side)
+                }
+                fn diff(&self, other: &Self) -> DiffIter<Self::Coord, Self::PieceWithSide>
+                where
+                    Self::PieceWithSide: PartialEq,
+                {
+                    (**self).diff(&**other)
+                }
+                fn apply_diff(
+                    &mut self,
+                    diff: &[SquareDiff<Self::Coord, Self::PieceWithSide>],
+                ) -> Result<(), &'static str>
+                where
+                    Self::PieceWithSide: PartialEq,
+                {
+                    (**self).apply_diff(diff)
+                }
+            }
+        )*
+    };
+}
+
+forward_is_board!(&mut B, Box<B>);
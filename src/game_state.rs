@@ -0,0 +1,196 @@
+use crate::IsAbsoluteField;
+use cetkaik_fundamental::{AbsoluteSide, ColorAndProf, Profession};
+
+/// A trait that signifies that you can use it as the whole state of a game, not just the field
+/// ／`Field` だけでなく、ゲーム全体の状態として使える型を表すトレイト
+///
+/// On top of the field, a game keeps track of whose turn it is, the current season, the scores and the doubling rate.
+/// ／`Field` に加えて、ゲームは手番・現在の季節・得点・レートを保持する。
+pub trait IsGameState {
+    /// The field on which the game is played
+    /// ／ゲームが行われている `Field`
+    type Field: IsAbsoluteField;
+
+    /// The rule configuration the game follows
+    /// ／ゲームが従うルール設定
+    type Config;
+
+    /// The field on which the game is played
+    /// ／ゲームが行われている `Field`
+    fn field(&self) -> &Self::Field;
+
+    /// The field on which the game is played, mutably
+    /// ／ゲームが行われている `Field` への可変参照
+    fn field_mut(&mut self) -> &mut Self::Field;
+
+    /// The rule configuration the game follows
+    /// ／ゲームが従うルール設定
+    fn config(&self) -> &Self::Config;
+
+    /// Whose turn it is
+    /// ／手番
+    fn whose_turn(&self) -> AbsoluteSide;
+
+    /// The current season, counted from 0 (spring, 春) to 3 (winter, 冬)
+    /// ／現在の季節。0（春）から 3（冬）まで数える。
+    fn season_index(&self) -> usize;
+
+    /// The score held by `side`; each player starts with 20 points
+    /// ／`side` の持ち点。双方 20 点から始まる。
+    fn score_of(&self, side: AbsoluteSide) -> i32;
+
+    /// The doubling rate, one of 1, 2, 4, 8, 16, 32 and 64
+    /// ／レート。1, 2, 4, 8, 16, 32, 64 のいずれか
+    fn rate(&self) -> i32;
+
+    /// A key for detecting repeated positions, usually [`CetkaikRepresentation::repetition_key`](crate::CetkaikRepresentation::repetition_key) of the field and whose turn it is
+    /// ／同一局面の検出に用いるキー。通常は `Field` と手番に対する [`CetkaikRepresentation::repetition_key`](crate::CetkaikRepresentation::repetition_key)
+    fn repetition_key(&self) -> u64;
+
+    /// A key for looking up the position in an [`OpeningBook`](crate::OpeningBook), combining [`IsGameState::repetition_key`] with the season
+    /// ／[`OpeningBook`](crate::OpeningBook) で局面を引くためのキー。[`IsGameState::repetition_key`] に季節を組み合わせたもの
+    ///
+    /// This agrees with [`book_key`](crate::book_key) as long as `repetition_key` is the usual one.
+    /// ／`repetition_key` が通常のものである限り、これは [`book_key`](crate::book_key) と一致する。
+    fn book_key(&self) -> u128 {
+        crate::book::combine(self.repetition_key(), self.season_index())
+    }
+
+    /// The pieces that `side` has captured since the season began, in the order they were captured
+    /// ／季節が始まってから `side` が取った駒。取った順に並ぶ。
+    ///
+    /// Pieces that were later parachuted back onto the board are still listed, so this is what the detection of hands (役) starts from.
+    /// ／後に盤上に打ち戻された駒も含まれるので、役の判定はこれを出発点とする。
+    fn captured_this_season(&self, side: AbsoluteSide) -> CapturedIter;
+
+    /// Whether `side` has moved Tam2 since the season began
+    /// ／季節が始まってから `side` が皇を動かしたかどうか
+    fn tam2_moved_this_season(&self, side: AbsoluteSide) -> bool;
+
+    /// The piece that the last move captured, which is the piece newly in the hop1zuo1 of the side that made it
+    /// ／直前の手が取った駒。その手を指した側の手駒に新たに加わった駒である。
+    fn captured_by_last_move(&self) -> Option<ColorAndProf>;
+
+    /// How many pieces of the profession `prof` `side` has captured since the season began
+    /// ／季節が始まってから `side` が取った職種 `prof` の駒の数
+    fn captured_count_this_season(&self, side: AbsoluteSide, prof: Profession) -> usize {
+        self.captured_this_season(side)
+            .filter(|p| p.prof == prof)
+            .count()
+    }
+}
+
+/// The iterator returned by [`IsGameState::captured_this_season`]
+/// ／[`IsGameState::captured_this_season`] が返すイテレータ
+#[derive(Clone, Debug)]
+pub struct CapturedIter(std::vec::IntoIter<ColorAndProf>);
+
+impl Iterator for CapturedIter {
+    type Item = ColorAndProf;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for CapturedIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl ExactSizeIterator for CapturedIter {}
+
+impl core::iter::FusedIterator for CapturedIter {}
+
+impl FromIterator<ColorAndProf> for CapturedIter {
+    fn from_iter<I: IntoIterator<Item = ColorAndProf>>(iter: I) -> Self {
+        Self(iter.into_iter().collect::<Vec<_>>().into_iter())
+    }
+}
+
+/// The score each player holds at the beginning of a game
+/// ／ゲーム開始時の各プレイヤーの持ち点
+pub const INITIAL_SCORE: i32 = 20;
+
+/// The number of seasons in a game, from spring (春) to winter (冬)
+/// ／ゲームの季節の数。春から冬まで。
+pub const SEASON_COUNT: usize = 4;
+
+/// What follows the end of a season, as returned by [`IsSeasonState::end_season`]
+/// ／季節の終わりに続くもの。[`IsSeasonState::end_season`] が返す。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SeasonOutcome<S> {
+    /// The game goes on to the next season, whose state is given
+    /// ／ゲームは次の季節に進む。その状態が与えられる。
+    NextSeason(S),
+    /// The game is over
+    /// ／ゲームが終わった
+    GameOver {
+        /// the final score of `ASide`／`ASide` の最終的な持ち点
+        a_side_score: i32,
+        /// the final score of `IASide`／`IASide` の最終的な持ち点
+        ia_side_score: i32,
+        /// the player with the higher score, or `None` for a draw／持ち点の多い側。引き分けなら `None`
+        winner: Option<AbsoluteSide>,
+    },
+}
+
+/// A game state that can carry the scores over from one season to the next
+/// ／ある季節から次の季節へと得点を引き継げるゲームの状態
+///
+/// Only the construction of the next season is left to the implementor; the scoring transitions of 終季 are provided by [`IsSeasonState::end_season`].
+/// ／実装する側に任されるのは次の季節の状態を作ることだけであり、終季における得点の移動は [`IsSeasonState::end_season`] が提供する。
+pub trait IsSeasonState: IsGameState + Sized {
+    /// The state at the beginning of the next season, with the given scores and with `whose_turn` to move first
+    /// ／次の季節の開始時の状態。持ち点は与えられたものとし、`whose_turn` が先手となる。
+    ///
+    /// The rate of the returned state must be back to 1.
+    /// ／返す状態のレートは 1 に戻っていなければならない。
+    #[must_use]
+    fn next_season(self, a_side_score: i32, ia_side_score: i32, whose_turn: AbsoluteSide) -> Self;
+
+    /// Ends the season by 終季, declared by the side to move with hands (役) worth `declared_dat2_score`, with `first_mover` to move first in the next season
+    /// ／手番の側が `declared_dat2_score` 点の役で終季を宣言し、季節を終える。次の季節は `first_mover` が先手となる。
+    ///
+    /// The declarer takes `declared_dat2_score` times the rate from the opponent, or gives it away if the score is negative (as with the penalties of 同色),
+    /// and each score is clamped to the range from 0 to 40.
+    /// The game is over as soon as either player reaches 0 or 40 points, or when winter ends.
+    /// Who moves first in the next season is not decided by the hands, so it is left to the caller.
+    /// ／宣言した側は、役の点数にレートを掛けたものを相手から受け取る。点数が負であれば（同色の減点など）逆に相手に渡す。
+    /// 持ち点はそれぞれ 0 から 40 の範囲に収める。どちらかの持ち点が 0 か 40 に達するか、冬が終わればゲームは終わる。
+    /// 次の季節の先手は役によっては決まらないので、呼び出す側に任される。
+    fn end_season(
+        self,
+        declared_dat2_score: i32,
+        first_mover: AbsoluteSide,
+    ) -> SeasonOutcome<Self> {
+        const TOTAL: i32 = 2 * INITIAL_SCORE;
+        let declarer = self.whose_turn();
+        let declarer_score =
+            0.max(TOTAL.min(self.score_of(declarer) + declared_dat2_score * self.rate()));
+        let (a_side_score, ia_side_score) = match declarer {
+            AbsoluteSide::ASide => (declarer_score, TOTAL - declarer_score),
+            AbsoluteSide::IASide => (TOTAL - declarer_score, declarer_score),
+        };
+        if declarer_score == 0 || declarer_score == TOTAL || self.season_index() + 1 >= SEASON_COUNT
+        {
+            return SeasonOutcome::GameOver {
+                a_side_score,
+                ia_side_score,
+                winner: match a_side_score.cmp(&ia_side_score) {
+                    core::cmp::Ordering::Greater => Some(AbsoluteSide::ASide),
+                    core::cmp::Ordering::Less => Some(AbsoluteSide::IASide),
+                    core::cmp::Ordering::Equal => None,
+                },
+            };
+        }
+        let next = self.next_season(a_side_score, ia_side_score, first_mover);
+        debug_assert_eq!(next.rate(), 1, "the rate must be reset in a new season");
+        SeasonOutcome::NextSeason(next)
+    }
+}
@@ -0,0 +1,250 @@
+use crate::repetition::{hop1zuo1_key, square_key, IA_SIDE_TO_MOVE};
+use crate::{
+    apply_move_in_place, CetkaikRepresentation, IsAbsoluteField, IsBoard, IsField, IsPieceWithSide,
+    PureMove, RuleConfig,
+};
+use cetkaik_fundamental::{AbsoluteSide, ColorAndProf, PureMove_};
+
+/// A field that keeps its [`CetkaikRepresentation::canonical_hash`] up to date as it is mutated
+/// ／変更に合わせて [`CetkaikRepresentation::canonical_hash`] を最新に保つ `Field`
+///
+/// Each mutation XORs out the keys of what it removes and XORs in the keys of what it adds,
+/// using [`square_key`](crate::repetition::square_key) for the board and [`hop1zuo1_key`](crate::repetition::hop1zuo1_key) for the hop1zuo1s,
+/// so the hash is never recomputed from scratch.
+/// The field is mutated only through the methods here; there is deliberately no mutable access to it.
+/// ／各変更は、取り除くもののキーを XOR で外し、加えるもののキーを XOR で入れる。盤面には [`square_key`](crate::repetition::square_key)、
+/// 手駒には [`hop1zuo1_key`](crate::repetition::hop1zuo1_key) を用いるので、ハッシュを一から計算し直すことはない。
+/// `Field` はここにあるメソッドを通じてのみ変更され、可変参照はあえて提供しない。
+pub struct HashedField<R: CetkaikRepresentation> {
+    field: R::AbsoluteField,
+    hash: u64,
+}
+
+/// What [`HashedField::make_move`] changed, so that [`HashedField::unmake_move`] can change it back
+/// ／[`HashedField::make_move`] が変更したもの。[`HashedField::unmake_move`] がこれを元に戻す。
+#[must_use]
+pub struct MoveUndo<R: CetkaikRepresentation> {
+    /// the squares the move changed, with what was on them before
+    squares: [(R::AbsoluteCoord, Option<R::AbsolutePiece>); 2],
+    whose_turn: AbsoluteSide,
+    /// the piece that entered (`true`) or left (`false`) the hop1zuo1 of `whose_turn`, if any
+    hop1zuo1: Option<(ColorAndProf, bool)>,
+    hash: u64,
+}
+
+impl<R: CetkaikRepresentation> Clone for MoveUndo<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R: CetkaikRepresentation> Copy for MoveUndo<R> {}
+
+impl<R: CetkaikRepresentation> Clone for HashedField<R> {
+    fn clone(&self) -> Self {
+        Self {
+            field: self.field.clone(),
+            hash: self.hash,
+        }
+    }
+}
+
+impl<R: CetkaikRepresentation> core::fmt::Debug for HashedField<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HashedField")
+            .field("field", &self.field)
+            .field("hash", &self.hash)
+            .finish()
+    }
+}
+
+impl<R: CetkaikRepresentation> HashedField<R> {
+    /// Wraps `field`, computing its hash once
+    /// ／`field` を包む。ハッシュはここで一度だけ計算する。
+    pub fn new(field: R::AbsoluteField) -> Self {
+        let hash = R::canonical_hash(&field);
+        Self { field, hash }
+    }
+
+    /// The wrapped field
+    /// ／包まれている `Field`
+    pub const fn field(&self) -> &R::AbsoluteField {
+        &self.field
+    }
+
+    /// Unwraps the field
+    /// ／`Field` を取り出す
+    pub fn into_inner(self) -> R::AbsoluteField {
+        self.field
+    }
+
+    /// The hash of the field, equal to [`CetkaikRepresentation::canonical_hash`]
+    /// ／`Field` のハッシュ。[`CetkaikRepresentation::canonical_hash`] に等しい。
+    pub const fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// The key of the position with `whose_turn` to move, equal to [`CetkaikRepresentation::repetition_key`]
+    /// ／`whose_turn` の手番である局面のキー。[`CetkaikRepresentation::repetition_key`] に等しい。
+    pub const fn repetition_key(&self, whose_turn: AbsoluteSide) -> u64 {
+        match whose_turn {
+            AbsoluteSide::ASide => self.hash,
+            AbsoluteSide::IASide => self.hash ^ IA_SIDE_TO_MOVE,
+        }
+    }
+
+    fn count_in_hop1zuo1(&self, side: AbsoluteSide, piece: ColorAndProf) -> usize {
+        self.field.hop1zuo1_of(side).filter(|&p| p == piece).count()
+    }
+
+    fn square_hash(&self, c: R::AbsoluteCoord) -> u64 {
+        self.field
+            .as_board()
+            .peek(c)
+            .map_or(0, |piece| square_key::<R>(c, piece))
+    }
+
+    /// Puts `p` on `c`, replacing whatever was there
+    /// ／`c` に `p` を置く。元あったものは置き換えられる。
+    pub fn put(&mut self, c: R::AbsoluteCoord, p: Option<R::AbsolutePiece>) {
+        self.hash ^= self.square_hash(c);
+        self.field.as_board_mut().put(c, p);
+        self.hash ^= self.square_hash(c);
+    }
+
+    /// Removes and returns whatever was on `c`
+    /// ／`c` にあったものを取り除いて返す
+    pub fn pop(&mut self, c: R::AbsoluteCoord) -> Option<R::AbsolutePiece> {
+        self.hash ^= self.square_hash(c);
+        self.field.as_board_mut().pop(c)
+    }
+
+    /// Adds a piece to the hop1zuo1 of `side`
+    /// ／`side` の手駒に駒を加える
+    pub fn add_to_hop1zuo1(&mut self, side: AbsoluteSide, piece: ColorAndProf) {
+        let count = self.count_in_hop1zuo1(side, piece);
+        self.field.add_to_hop1zuo1(side, piece);
+        self.hash ^= hop1zuo1_key(side, piece, count) ^ hop1zuo1_key(side, piece, count + 1);
+    }
+
+    /// Removes one copy of a piece from the hop1zuo1 of `side`, returning whether it was found
+    /// ／`side` の手駒から駒をひとつ取り除き、見つかったかどうかを返す
+    pub fn remove_from_hop1zuo1(&mut self, side: AbsoluteSide, piece: ColorAndProf) -> bool {
+        let count = self.count_in_hop1zuo1(side, piece);
+        let found = self.field.remove_from_hop1zuo1(side, piece);
+        if found {
+            self.hash ^= hop1zuo1_key(side, piece, count) ^ hop1zuo1_key(side, piece, count - 1);
+        }
+        found
+    }
+
+    /// Applies `mv`, made by `whose_turn`, in the manner of [`apply_move_in_place`], updating the hash
+    /// ／[`apply_move_in_place`] と同様に `whose_turn` による指し手 `mv` を適用し、ハッシュを更新する
+    /// # Errors
+    /// Same as [`apply_move_in_place`], in which case neither the field nor the hash changes.
+    /// ／[`apply_move_in_place`] と同じ。その場合 `Field` もハッシュも変化しない。
+    pub fn apply_move(
+        &mut self,
+        mv: PureMove<R>,
+        whose_turn: AbsoluteSide,
+        config: &RuleConfig,
+    ) -> Result<(), &'static str> {
+        self.make_move(mv, whose_turn, config).map(drop)
+    }
+
+    /// Applies `mv` as [`HashedField::apply_move`] does, returning what [`HashedField::unmake_move`] needs to take it back
+    /// ／[`HashedField::apply_move`] と同様に `mv` を適用し、[`HashedField::unmake_move`] がそれを取り消すのに必要なものを返す
+    ///
+    /// A move changes at most two squares and one hop1zuo1, so taking it back never clones the field.
+    /// ／指し手が変えるのは高々二つのマスと一方の手駒だけなので、取り消しの際に `Field` をクローンすることはない。
+    /// # Errors
+    /// Same as [`apply_move_in_place`], in which case neither the field nor the hash changes.
+    /// ／[`apply_move_in_place`] と同じ。その場合 `Field` もハッシュも変化しない。
+    pub fn make_move(
+        &mut self,
+        mv: PureMove<R>,
+        whose_turn: AbsoluteSide,
+        config: &RuleConfig,
+    ) -> Result<MoveUndo<R>, &'static str> {
+        let (from, to) = match mv {
+            PureMove_::NonTamMoveSrcDst { src, dest, .. }
+            | PureMove_::NonTamMoveSrcStepDstFinite { src, dest, .. }
+            | PureMove_::InfAfterStep {
+                src,
+                planned_direction: dest,
+                ..
+            } => (src, dest),
+            PureMove_::NonTamMoveFromHopZuo { dest, .. } => (dest, dest),
+            PureMove_::TamMoveNoStep {
+                src, second_dest, ..
+            }
+            | PureMove_::TamMoveStepsDuringFormer {
+                src, second_dest, ..
+            }
+            | PureMove_::TamMoveStepsDuringLatter {
+                src, second_dest, ..
+            } => (src, second_dest),
+        };
+        // the kind of piece that enters or leaves the hop1zuo1 of `whose_turn`, if any
+        let hop1zuo1_kind = match mv {
+            PureMove_::NonTamMoveFromHopZuo { color, prof, .. } => {
+                Some(ColorAndProf { color, prof })
+            }
+            _ if from == to => None,
+            _ => self.field.as_board().peek(to).and_then(|piece| {
+                piece.match_on_piece_and_apply(&|| None, &|color, prof, _| {
+                    Some(ColorAndProf { color, prof })
+                })
+            }),
+        };
+        let board = self.field.as_board();
+        let squares = [(from, board.peek(from)), (to, board.peek(to))];
+        let hash = self.hash;
+        let squares_before =
+            self.square_hash(from) ^ if from == to { 0 } else { self.square_hash(to) };
+        let count_before = hop1zuo1_kind.map(|piece| self.count_in_hop1zuo1(whose_turn, piece));
+
+        apply_move_in_place::<R>(&mut self.field, mv, whose_turn, config)?;
+
+        let squares_after =
+            self.square_hash(from) ^ if from == to { 0 } else { self.square_hash(to) };
+        self.hash ^= squares_before ^ squares_after;
+        let mut hop1zuo1 = None;
+        if let (Some(piece), Some(before)) = (hop1zuo1_kind, count_before) {
+            let after = self.count_in_hop1zuo1(whose_turn, piece);
+            self.hash ^=
+                hop1zuo1_key(whose_turn, piece, before) ^ hop1zuo1_key(whose_turn, piece, after);
+            if after != before {
+                hop1zuo1 = Some((piece, after > before));
+            }
+        }
+        Ok(MoveUndo {
+            squares,
+            whose_turn,
+            hop1zuo1,
+            hash,
+        })
+    }
+
+    /// Takes back the move made by the [`HashedField::make_move`] that returned `undo`, which must be the last move made
+    /// ／`undo` を返した [`HashedField::make_move`] による指し手を取り消す。それは最後に指された手でなければならない。
+    pub fn unmake_move(&mut self, undo: MoveUndo<R>) {
+        let MoveUndo {
+            squares,
+            whose_turn,
+            hop1zuo1,
+            hash,
+        } = undo;
+        for (c, p) in squares.into_iter().rev() {
+            self.field.as_board_mut().put(c, p);
+        }
+        match hop1zuo1 {
+            Some((piece, true)) => {
+                self.field.remove_from_hop1zuo1(whose_turn, piece);
+            }
+            Some((piece, false)) => self.field.add_to_hop1zuo1(whose_turn, piece),
+            None => {}
+        }
+        self.hash = hash;
+    }
+}
@@ -0,0 +1,226 @@
+use crate::dynamic::{kind_code, kind_from_code, sort_hop1zuo1};
+use cetkaik_fundamental::{AbsoluteSide, Color, ColorAndProf, Profession};
+
+/// A trait that signifies that the type can be used as the hop1zuo1 of one side
+/// ／一方の陣営の手駒として扱える型を表すトレイト
+///
+/// It is what [`crate::IsAbsoluteField::hop1zuo1`] returns, but can also be used apart from any field.
+/// ／[`crate::IsAbsoluteField::hop1zuo1`] が返す型であるが、`Field` とは独立に用いることもできる。
+///
+/// The hop1zuo1 is a multiset, so the order in which [`IsHop1Zuo1::iter`] yields the pieces is up to the implementation;
+/// use [`IsHop1Zuo1::sorted`] for the canonical order, which is the one of [`crate::IsAbsoluteField::hop1zuo1_sorted`].
+/// Only [`IsHop1Zuo1::iter`], [`IsHop1Zuo1::push`] and [`IsHop1Zuo1::remove`] are required;
+/// a bit-packed representation can override the others to answer without iterating.
+/// ／手駒は多重集合なので、[`IsHop1Zuo1::iter`] が駒を返す順序は実装に任される。
+/// 正準な順序（[`crate::IsAbsoluteField::hop1zuo1_sorted`] と同じもの）が必要なら [`IsHop1Zuo1::sorted`] を用いる。
+/// 必須なのは [`IsHop1Zuo1::iter`]・[`IsHop1Zuo1::push`]・[`IsHop1Zuo1::remove`] のみで、
+/// ビットで詰めた表現は、その他のメソッドを上書きして列挙せずに答えることができる。
+pub trait IsHop1Zuo1 {
+    /// An iterator over the pieces, which knows its length and keeps returning `None` once exhausted
+    /// ／駒を列挙するイテレータ。長さが分かり、尽きた後は `None` を返し続ける。
+    type Iter: ExactSizeIterator<Item = ColorAndProf> + core::iter::FusedIterator;
+
+    /// Iterates over the pieces, each copy separately
+    /// ／駒を、同じ駒もひとつずつ列挙する
+    fn iter(&self) -> Self::Iter;
+
+    /// The number of pieces
+    /// ／駒の数
+    fn count(&self) -> usize {
+        self.iter().len()
+    }
+
+    /// Whether there is no piece
+    /// ／駒がひとつもないかどうか
+    fn is_empty(&self) -> bool {
+        self.count() == 0
+    }
+
+    /// Whether there is at least one piece of the color `color` and the profession `prof`
+    /// ／色 `color`、職種 `prof` の駒が少なくともひとつあるかどうか
+    fn contains(&self, color: Color, prof: Profession) -> bool {
+        self.iter().any(|p| p.color == color && p.prof == prof)
+    }
+
+    /// The number of pieces of the color `color` and the profession `prof`
+    /// ／色 `color`、職種 `prof` の駒の数
+    fn count_of(&self, color: Color, prof: Profession) -> usize {
+        self.iter()
+            .filter(|p| p.color == color && p.prof == prof)
+            .count()
+    }
+
+    /// Adds a piece
+    /// ／駒を加える
+    fn push(&mut self, piece: ColorAndProf);
+
+    /// Removes one copy of a piece, returning whether it was found
+    /// ／駒をひとつ取り除き、見つかったかどうかを返す
+    fn remove(&mut self, piece: ColorAndProf) -> bool;
+
+    /// The pieces in the canonical order, which sorts by color (Kok1 first) and then by profession
+    /// in the order Nuak1, Kauk2, Gua2, Kaun1, Dau2, Maun1, Kua2, Tuk2, Uai1, Io
+    /// ／正準な順序に並べた駒。色（赤が先）、次に職種（船・兵・弓・車・虎・馬・筆・巫・将・王の順）で整列する。
+    fn sorted(&self) -> Vec<ColorAndProf> {
+        let mut pieces = self.iter().collect::<Vec<_>>();
+        sort_hop1zuo1(&mut pieces);
+        pieces
+    }
+}
+
+/// A hop1zuo1 kept as the number of pieces of each kind, which answers [`IsHop1Zuo1::count_of`] in constant time
+/// ／駒の種類ごとの数として保持する手駒。[`IsHop1Zuo1::count_of`] に定数時間で答える。
+///
+/// Since the kinds are counted in the canonical order, [`IsHop1Zuo1::iter`] already yields the pieces in that order.
+/// ／種類は正準な順序で数えるので、[`IsHop1Zuo1::iter`] は既にその順序で駒を返す。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Hop1Zuo1Counts {
+    counts: [u8; 20],
+}
+
+impl Hop1Zuo1Counts {
+    /// A hop1zuo1 with no piece
+    /// ／駒をひとつも持たない手駒
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { counts: [0; 20] }
+    }
+}
+
+impl FromIterator<ColorAndProf> for Hop1Zuo1Counts {
+    fn from_iter<I: IntoIterator<Item = ColorAndProf>>(iter: I) -> Self {
+        let mut hop1zuo1 = Self::new();
+        for piece in iter {
+            hop1zuo1.push(piece);
+        }
+        hop1zuo1
+    }
+}
+
+impl IsHop1Zuo1 for Hop1Zuo1Counts {
+    type Iter = Hop1Zuo1CountsIter;
+
+    fn iter(&self) -> Self::Iter {
+        Hop1Zuo1CountsIter {
+            counts: self.counts,
+            code: 0,
+            remaining: self.count(),
+        }
+    }
+
+    fn count(&self) -> usize {
+        self.counts.iter().map(|&n| usize::from(n)).sum()
+    }
+
+    fn contains(&self, color: Color, prof: Profession) -> bool {
+        self.count_of(color, prof) > 0
+    }
+
+    fn count_of(&self, color: Color, prof: Profession) -> usize {
+        usize::from(self.counts[usize::from(kind_code(ColorAndProf { color, prof }))])
+    }
+
+    fn push(&mut self, piece: ColorAndProf) {
+        let n = &mut self.counts[usize::from(kind_code(piece))];
+        *n = n.saturating_add(1);
+    }
+
+    fn remove(&mut self, piece: ColorAndProf) -> bool {
+        let n = &mut self.counts[usize::from(kind_code(piece))];
+        if *n == 0 {
+            return false;
+        }
+        *n -= 1;
+        true
+    }
+
+    fn sorted(&self) -> Vec<ColorAndProf> {
+        self.iter().collect()
+    }
+}
+
+/// The iterator returned by [`IsHop1Zuo1::iter`] for a [`Hop1Zuo1Counts`]
+/// ／[`Hop1Zuo1Counts`] の [`IsHop1Zuo1::iter`] が返すイテレータ
+#[derive(Clone, Debug)]
+pub struct Hop1Zuo1CountsIter {
+    counts: [u8; 20],
+    code: u8,
+    remaining: usize,
+}
+
+impl Iterator for Hop1Zuo1CountsIter {
+    type Item = ColorAndProf;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(n) = self.counts.get_mut(usize::from(self.code)) {
+            if *n > 0 {
+                *n -= 1;
+                self.remaining -= 1;
+                return kind_from_code(self.code);
+            }
+            self.code += 1;
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for Hop1Zuo1CountsIter {}
+
+impl core::iter::FusedIterator for Hop1Zuo1CountsIter {}
+
+/// The iterator returned by [`crate::IsAbsoluteField::hop1zuo1_both`]
+/// ／[`crate::IsAbsoluteField::hop1zuo1_both`] が返すイテレータ
+///
+/// It goes through the hop1zuo1 of `ASide` and then that of `IASide`.
+/// ／`ASide` の手駒、次に `IASide` の手駒を順にたどる。
+#[derive(Clone, Debug)]
+pub struct Hop1Zuo1Both<I> {
+    a_side: I,
+    ia_side: I,
+}
+
+impl<I> Hop1Zuo1Both<I> {
+    /// Chains the hop1zuo1 of `ASide` and that of `IASide`
+    /// ／`ASide` の手駒と `IASide` の手駒をつなげる
+    pub const fn new(a_side: I, ia_side: I) -> Self {
+        Self { a_side, ia_side }
+    }
+}
+
+impl<I: core::iter::FusedIterator<Item = ColorAndProf>> Iterator for Hop1Zuo1Both<I> {
+    type Item = (AbsoluteSide, ColorAndProf);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.a_side
+            .next()
+            .map(|piece| (AbsoluteSide::ASide, piece))
+            .or_else(|| {
+                self.ia_side
+                    .next()
+                    .map(|piece| (AbsoluteSide::IASide, piece))
+            })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lo, a_hi) = self.a_side.size_hint();
+        let (ia_lo, ia_hi) = self.ia_side.size_hint();
+        (
+            a_lo.saturating_add(ia_lo),
+            a_hi.zip(ia_hi).and_then(|(a, ia)| a.checked_add(ia)),
+        )
+    }
+}
+
+impl<I: ExactSizeIterator<Item = ColorAndProf> + core::iter::FusedIterator> ExactSizeIterator
+    for Hop1Zuo1Both<I>
+{
+}
+
+impl<I: core::iter::FusedIterator<Item = ColorAndProf>> core::iter::FusedIterator
+    for Hop1Zuo1Both<I>
+{
+}
@@ -0,0 +1,251 @@
+use crate::notation::{color_glyph, prof_glyph};
+use crate::{
+    parse_move, CetkaikRepresentation, CoordNotation, GameRecord, MoveNotation, NotationStyle,
+    PlayedMove, PureMove,
+};
+use cetkaik_fundamental::{Color, Profession, PureMove_};
+
+/// The names [`KifuNotation`] gives to the color and the profession of a parachuted piece
+/// ／[`KifuNotation`] が打つ駒の色と職種に付ける名前
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PieceNames {
+    /// The glyphs, written together with the square, e.g. `黒弓ZI`
+    /// ／漢字。マスと続けて書く。例：`黒弓ZI`
+    #[default]
+    Kanji,
+    /// The romanization with tone numbers, joined to the square by hyphens, e.g. `huok2-gua2-ZI`
+    /// ／声調番号付きのローマ字。マスとハイフンでつなぐ。例：`huok2-gua2-ZI`
+    Pekzep,
+    /// The English names, joined to the square by hyphens, e.g. `black-rook-ZI`
+    /// ／英語名。マスとハイフンでつなぐ。例：`black-rook-ZI`
+    English,
+}
+
+impl PieceNames {
+    const fn color(self, color: Color) -> &'static str {
+        match (self, color) {
+            (Self::Kanji, Color::Kok1) => "赤",
+            (Self::Kanji, Color::Huok2) => "黒",
+            (Self::Pekzep, Color::Kok1) => "kok1",
+            (Self::Pekzep, Color::Huok2) => "huok2",
+            (Self::English, Color::Kok1) => "red",
+            (Self::English, Color::Huok2) => "black",
+        }
+    }
+
+    const fn prof(self, prof: Profession) -> &'static str {
+        match self {
+            Self::Kanji => match prof {
+                Profession::Nuak1 => "船",
+                Profession::Kauk2 => "兵",
+                Profession::Gua2 => "弓",
+                Profession::Kaun1 => "車",
+                Profession::Dau2 => "虎",
+                Profession::Maun1 => "馬",
+                Profession::Kua2 => "筆",
+                Profession::Tuk2 => "巫",
+                Profession::Uai1 => "将",
+                Profession::Io => "王",
+            },
+            Self::Pekzep => match prof {
+                Profession::Nuak1 => "nuak1",
+                Profession::Kauk2 => "kauk2",
+                Profession::Gua2 => "gua2",
+                Profession::Kaun1 => "kaun1",
+                Profession::Dau2 => "dau2",
+                Profession::Maun1 => "maun1",
+                Profession::Kua2 => "kua2",
+                Profession::Tuk2 => "tuk2",
+                Profession::Uai1 => "uai1",
+                Profession::Io => "io",
+            },
+            Self::English => match prof {
+                Profession::Nuak1 => "vessel",
+                Profession::Kauk2 => "pawn",
+                Profession::Gua2 => "rook",
+                Profession::Kaun1 => "bishop",
+                Profession::Dau2 => "tiger",
+                Profession::Maun1 => "horse",
+                Profession::Kua2 => "clerk",
+                Profession::Tuk2 => "shaman",
+                Profession::Uai1 => "general",
+                Profession::Io => "king",
+            },
+        }
+    }
+}
+
+/// What [`KifuNotation`] puts between the moves
+/// ／[`KifuNotation`] が指し手の間に置くもの
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MoveDelimiter {
+    /// A single space, all moves on one line／空白一つ。すべての手を一行に書く。
+    #[default]
+    Space,
+    /// A newline after each move／各手の後に改行
+    Newline,
+    /// Each move on its own line, preceded by its number counted from 1, e.g. `1. LAU片LAI`
+    /// ／各手を一行ずつ、1 から数えた番号を前に付けて書く。例：`1. LAU片LAI`
+    Numbered,
+}
+
+/// How [`KifuNotation`] writes a record
+/// ／[`KifuNotation`] が記録を書く方法
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RecordStyle {
+    /// the names of the parachuted pieces／打つ駒の名前
+    pub names: PieceNames,
+    /// how the squares are named／マスの表し方
+    pub squares: NotationStyle,
+    /// what is put between the moves／指し手の間に置くもの
+    pub delimiter: MoveDelimiter,
+    /// whether the number of sticks that fell face up is written after a move, e.g. `LAU片LIA水(3)`
+    /// ／表になった投げ棒の本数を指し手の後に書くかどうか。例：`LAU片LIA水(3)`
+    pub ciurl: bool,
+}
+
+impl Default for RecordStyle {
+    /// Kanji glyphs, Latin square names, spaces, and the sticks recorded
+    fn default() -> Self {
+        Self {
+            names: PieceNames::default(),
+            squares: NotationStyle::default(),
+            delimiter: MoveDelimiter::default(),
+            ciurl: true,
+        }
+    }
+}
+
+/// The moves of a [`GameRecord`] written as a kifu in a [`RecordStyle`]
+/// ／[`RecordStyle`] に従って棋譜として書かれた [`GameRecord`] の指し手
+///
+/// Each move is written in [`MoveNotation`], except that a parachuted piece is named as in [`RecordStyle::names`].
+/// The initial field, the first mover and the rule configuration are not written; the kifu is read back by [`parse_kifu`].
+/// ／各手は [`MoveNotation`] で書く。ただし打つ駒は [`RecordStyle::names`] に従って名付ける。
+/// 初期局面・先手・ルール設定は書かない。棋譜は [`parse_kifu`] で読み戻せる。
+pub struct KifuNotation<'a, R: CetkaikRepresentation> {
+    moves: &'a [PlayedMove<R::AbsoluteCoord>],
+    style: RecordStyle,
+}
+
+impl<R: CetkaikRepresentation> Clone for KifuNotation<'_, R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R: CetkaikRepresentation> Copy for KifuNotation<'_, R> {}
+
+impl<R: CetkaikRepresentation> core::fmt::Debug for KifuNotation<'_, R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("KifuNotation")
+            .field("moves", &self.moves)
+            .field("style", &self.style)
+            .finish()
+    }
+}
+
+impl<'a, R: CetkaikRepresentation> KifuNotation<'a, R> {
+    /// Writes the moves of `record` in the default [`RecordStyle`]
+    /// ／`record` の指し手を既定の [`RecordStyle`] で書き表す
+    pub fn new(record: &'a GameRecord<R>) -> Self {
+        Self {
+            moves: &record.moves,
+            style: RecordStyle::default(),
+        }
+    }
+
+    /// Writes in `style` instead
+    /// ／代わりに `style` で書く
+    #[must_use]
+    pub const fn style(mut self, style: RecordStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl<R: CetkaikRepresentation> core::fmt::Display for KifuNotation<'_, R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let RecordStyle {
+            names,
+            squares,
+            delimiter,
+            ciurl: record_ciurl,
+        } = self.style;
+        for (i, &PlayedMove { mv, ciurl }) in self.moves.iter().enumerate() {
+            match delimiter {
+                MoveDelimiter::Space if i > 0 => write!(f, " ")?,
+                MoveDelimiter::Numbered => write!(f, "{}. ", i + 1)?,
+                MoveDelimiter::Space | MoveDelimiter::Newline => {}
+            }
+            match mv {
+                PureMove_::NonTamMoveFromHopZuo { color, prof, dest } => {
+                    let hyphen = if names == PieceNames::Kanji { "" } else { "-" };
+                    write!(
+                        f,
+                        "{}{hyphen}{}{hyphen}{}",
+                        names.color(color),
+                        names.prof(prof),
+                        CoordNotation::<R>::new(dest).style(squares)
+                    )?;
+                }
+                _ => write!(f, "{}", MoveNotation::<R>::new(mv).style(squares))?,
+            }
+            if let (true, Some(ciurl)) = (record_ciurl, ciurl) {
+                write!(f, "({ciurl})")?;
+            }
+            if delimiter != MoveDelimiter::Space {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads one move of a kifu, with the sticks if they were written
+fn parse_played_move<R: CetkaikRepresentation>(
+    token: &str,
+) -> Option<PlayedMove<R::AbsoluteCoord>> {
+    let (mv, ciurl) = match token.strip_suffix(')').and_then(|t| t.rsplit_once('(')) {
+        Some((mv, ciurl)) => (mv, Some(ciurl.parse().ok()?)),
+        None => (token, None),
+    };
+    Some(PlayedMove {
+        mv: parse_named_move::<R>(mv)?,
+        ciurl,
+    })
+}
+
+/// Reads a move in [`MoveNotation`], or a parachute whose piece is named with hyphens
+fn parse_named_move<R: CetkaikRepresentation>(s: &str) -> Option<PureMove<R>> {
+    let Some((color, rest)) = s.split_once('-') else {
+        return parse_move::<R>(s);
+    };
+    let (prof, dest) = rest.split_once('-')?;
+    let color: Color = color.parse().ok()?;
+    let prof: Profession = prof.parse().ok()?;
+    parse_move::<R>(&format!("{}{}{dest}", color_glyph(color), prof_glyph(prof)))
+}
+
+/// Reads the moves of a kifu written by [`KifuNotation`] in any [`RecordStyle`], or returns `None` if a move cannot be read
+/// ／任意の [`RecordStyle`] の [`KifuNotation`] で書かれた棋譜の指し手を読む。読めない手があれば `None` を返す。
+///
+/// The moves may be separated by any whitespace, and move numbers such as `12.` are skipped.
+/// A move without the sticks written is read with [`PlayedMove::ciurl`] set to `None`.
+/// The names of a parachuted piece are read by the `FromStr` of `Color` and `Profession`, so other conventions they accept are read as well.
+/// To obtain a [`GameRecord`], extend [`GameRecord::moves`] of a record created with the initial field, the first mover and the rules.
+/// ／指し手は任意の空白で区切られていてよく、`12.` のような手数は読み飛ばす。
+/// 投げ棒の書かれていない手は [`PlayedMove::ciurl`] を `None` として読む。
+/// 打つ駒の名前は `Color` と `Profession` の `FromStr` で読むので、それらが受け付ける他の表記も読める。
+/// [`GameRecord`] を得るには、初期局面・先手・ルールから作った記録の [`GameRecord::moves`] に付け加えること。
+#[must_use]
+pub fn parse_kifu<R: CetkaikRepresentation>(s: &str) -> Option<Vec<PlayedMove<R::AbsoluteCoord>>> {
+    s.split_whitespace()
+        .filter(|token| {
+            token
+                .strip_suffix('.')
+                .is_none_or(|number| number.parse::<usize>().is_err())
+        })
+        .map(parse_played_move::<R>)
+        .collect()
+}
@@ -0,0 +1,382 @@
+use crate::movement::{allowed_obstacles, neighbor_indices, obstacles, steps_to};
+use crate::{
+    movement_patterns, CetkaikRepresentation, IsAbsoluteField, IsBoard, IsCoord, IsField,
+    IsPieceWithSide, MovementPattern, PureMove, RuleConfig,
+};
+use cetkaik_fundamental::{AbsoluteSide, ColorAndProf, Profession, PureMove_};
+
+/// A reason why a move cannot be made, as reported by [`is_legal`]
+/// ／[`is_legal`] が報告する、指し手が指せない理由
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum IllegalMove {
+    /// There is no piece at the origin
+    /// ／移動元に駒がない
+    NoPieceAtSrc,
+    /// The piece at the origin does not belong to the side to move; Tam2 belongs to neither side
+    /// ／移動元の駒が手番の側のものではない。皇はどちらの側のものでもない。
+    NotOwnPiece,
+    /// A Tam2 move does not start from Tam2
+    /// ／皇の移動が皇から始まっていない
+    NoTam2AtSrc,
+    /// The piece cannot move that way
+    /// ／駒がそのようには動けない
+    Unreachable,
+    /// The piece could move that way only if it stood on a tam2 hue square
+    /// ／駒が皇処にいればそのように動けるが、そうではない
+    NotTamHue,
+    /// A piece in between blocks the way
+    /// ／間にある駒が行く手を阻んでいる
+    BlockedPath,
+    /// The square to step on is empty
+    /// ／踏むはずのマスが空である
+    NothingToStepOn,
+    /// The move steps over Tam2 while the rule configuration does not allow it
+    /// ／ルール設定が許さないのに皇を踏み越えている
+    SteppingOverTam2,
+    /// The move tries to take Tam2
+    /// ／皇を取ろうとしている
+    CapturingTam2,
+    /// The move tries to take a piece of the side to move
+    /// ／手番の側の駒を取ろうとしている
+    CapturingAlly,
+    /// The piece to be taken is next to an opposing 将 on a tam2 hue square
+    /// ／取ろうとしている駒が、皇処にいる相手の将に隣接している
+    ProtectedByTamHueUai1,
+    /// The destination is occupied
+    /// ／移動先に駒がある
+    DestinationOccupied,
+    /// The piece to be parachuted is not in the hop1zuo1
+    /// ／打とうとしている駒が手駒にない
+    NotInHop1Zuo1,
+    /// The move enters water but is not marked as requiring the water-entry stick cast
+    /// ／入水する手なのに、入水判定を要する印が付いていない
+    WaterEntryWithoutCiurl,
+    /// The move is marked as requiring the water-entry stick cast but does not enter water
+    /// ／入水判定を要する印が付いているのに、入水する手ではない
+    SpuriousWaterEntryCiurl,
+}
+
+impl core::fmt::Display for IllegalMove {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let message = match self {
+            Self::NoPieceAtSrc => "there is no piece at the origin",
+            Self::NotOwnPiece => "the piece at the origin does not belong to the side to move",
+            Self::NoTam2AtSrc => "Tam2 is not found at the origin",
+            Self::Unreachable => "the piece cannot move that way",
+            Self::NotTamHue => "the piece can move that way only on a tam2 hue square",
+            Self::BlockedPath => "a piece in between blocks the way",
+            Self::NothingToStepOn => "the square to step on is empty",
+            Self::SteppingOverTam2 => "stepping over Tam2 is not allowed",
+            Self::CapturingTam2 => "Tam2 cannot be taken",
+            Self::CapturingAlly => "a piece of the side to move cannot be taken",
+            Self::ProtectedByTamHueUai1 => "the piece is protected by a Uai1 on a tam2 hue square",
+            Self::DestinationOccupied => "the destination is occupied",
+            Self::NotInHop1Zuo1 => "the piece is not found in the hop1zuo1",
+            Self::WaterEntryWithoutCiurl => "the move enters water without the stick cast",
+            Self::SpuriousWaterEntryCiurl => {
+                "the move asks for a water-entry stick cast but does not enter water"
+            }
+        };
+        write!(f, "{message}")
+    }
+}
+
+impl std::error::Error for IllegalMove {}
+
+/// The board seen through canonical indices, possibly with the moving piece lifted off
+struct Lookup<'a, R: CetkaikRepresentation> {
+    board: &'a R::AbsoluteBoard,
+    config: &'a RuleConfig,
+    removed: Option<u8>,
+}
+
+impl<R: CetkaikRepresentation> Lookup<'_, R> {
+    fn at(&self, index: u8) -> Option<R::AbsolutePiece> {
+        if Some(index) == self.removed {
+            return None;
+        }
+        R::index_to_coord(index).and_then(|c| self.board.peek(c))
+    }
+
+    fn is_tam_hue(&self, index: u8) -> bool {
+        self.config
+            .is_tam_hue_on::<R>(self.board, index, self.removed)
+    }
+
+    fn is_clear(&self, pattern: MovementPattern, src: u8, steps: u8) -> bool {
+        obstacles(pattern, src, steps, |i| self.at(i).is_some()).count()
+            <= allowed_obstacles(pattern.blocking)
+    }
+
+    /// Whether a piece of `prof` and `side` at `src` can move to `dest`, restricted to infinite or finite movements if `unbounded` says so
+    fn reach(
+        &self,
+        src: u8,
+        dest: u8,
+        prof: Profession,
+        side: AbsoluteSide,
+        unbounded: Option<bool>,
+    ) -> Result<(), IllegalMove> {
+        let matching = |tam_hue| {
+            movement_patterns(prof, tam_hue)
+                .iter()
+                .map(move |pattern| pattern.oriented(side))
+                .filter(move |pattern| unbounded.is_none_or(|u| pattern.is_unbounded() == u))
+                .filter_map(move |pattern| {
+                    steps_to(pattern, src, dest).map(|steps| (pattern, steps))
+                })
+        };
+        let tam_hue = self.is_tam_hue(src);
+        let mut found = false;
+        for (pattern, steps) in matching(tam_hue) {
+            if self.is_clear(pattern, src, steps) {
+                return Ok(());
+            }
+            found = true;
+        }
+        if found {
+            Err(IllegalMove::BlockedPath)
+        } else if !tam_hue && matching(true).next().is_some() {
+            Err(IllegalMove::NotTamHue)
+        } else {
+            Err(IllegalMove::Unreachable)
+        }
+    }
+
+    /// Whether a piece of `side` can end its move on `dest`, taking whatever is there
+    fn can_occupy(&self, dest: u8, side: AbsoluteSide) -> Result<(), IllegalMove> {
+        let Some(piece) = self.at(dest) else {
+            return Ok(());
+        };
+        piece.match_on_piece_and_apply(&|| Err(IllegalMove::CapturingTam2), &|_, _, s| {
+            if s == side {
+                Err(IllegalMove::CapturingAlly)
+            } else {
+                Ok(())
+            }
+        })?;
+        let protected = neighbor_indices(dest).any(|i| {
+            self.at(i).is_some_and(|p| {
+                p.match_on_piece_and_apply(&|| false, &|_, prof, s| {
+                    prof == Profession::Uai1 && s != side && self.is_tam_hue(i)
+                })
+            })
+        });
+        if protected {
+            Err(IllegalMove::ProtectedByTamHueUai1)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Whether `step` can be stepped on
+    fn can_step_on(&self, step: u8) -> Result<(), IllegalMove> {
+        match self.at(step) {
+            None => Err(IllegalMove::NothingToStepOn),
+            Some(p) if p == R::absolute_tam2() && !self.config.allow_kut2tam2 => {
+                Err(IllegalMove::SteppingOverTam2)
+            }
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// The profession of the piece at `src`, which must belong to `side`
+    fn own_prof(&self, src: u8, side: AbsoluteSide) -> Result<Profession, IllegalMove> {
+        self.at(src)
+            .ok_or(IllegalMove::NoPieceAtSrc)?
+            .match_on_piece_and_apply(&|| None, &|_, prof, s| (s == side).then_some(prof))
+            .ok_or(IllegalMove::NotOwnPiece)
+    }
+
+    /// Whether the piece of `side` at `src` can step on `step` and then make an infinite or finite movement to `dest`, returning its profession
+    fn stepping_move(
+        &self,
+        src: u8,
+        step: u8,
+        dest: u8,
+        side: AbsoluteSide,
+        unbounded: bool,
+    ) -> Result<Profession, IllegalMove> {
+        let prof = self.own_prof(src, side)?;
+        self.reach(src, step, prof, side, None)?;
+        self.can_step_on(step)?;
+        let lifted = Lookup::<R> {
+            removed: Some(src),
+            ..*self
+        };
+        lifted.reach(step, dest, prof, side, Some(unbounded))?;
+        lifted.can_occupy(dest, side)?;
+        Ok(prof)
+    }
+
+    /// Whether Tam2 at `src` can move to `first_dest` and then to `second_dest`, stepping on a square before or after `first_dest` if given
+    fn tam2_move(
+        &self,
+        src: u8,
+        step_before: Option<u8>,
+        first_dest: u8,
+        step_after: Option<u8>,
+        second_dest: u8,
+    ) -> Result<(), IllegalMove> {
+        if self.at(src) != Some(R::absolute_tam2()) {
+            return Err(IllegalMove::NoTam2AtSrc);
+        }
+        let lifted = Lookup::<R> {
+            removed: Some(src),
+            ..*self
+        };
+        let mut from = src;
+        for (step, dest) in [(step_before, first_dest), (step_after, second_dest)] {
+            if let Some(step) = step {
+                if !neighbor_indices(from).any(|i| i == step) {
+                    return Err(IllegalMove::Unreachable);
+                }
+                lifted.can_step_on(step)?;
+                from = step;
+            }
+            lifted.empty_neighbor(from, dest)?;
+            from = dest;
+        }
+        Ok(())
+    }
+
+    fn empty_neighbor(&self, from: u8, to: u8) -> Result<(), IllegalMove> {
+        if !neighbor_indices(from).any(|i| i == to) {
+            return Err(IllegalMove::Unreachable);
+        }
+        if self.at(to).is_some() {
+            return Err(IllegalMove::DestinationOccupied);
+        }
+        Ok(())
+    }
+}
+
+/// Whether the water-entry flag is set exactly when a piece other than 船 enters water from land
+fn check_water_entry<R: CetkaikRepresentation>(
+    src: R::AbsoluteCoord,
+    dest: R::AbsoluteCoord,
+    prof: Profession,
+    is_water_entry_ciurl: bool,
+) -> Result<(), IllegalMove> {
+    let enters_water = dest.is_water() && !src.is_water() && prof != Profession::Nuak1;
+    match (enters_water, is_water_entry_ciurl) {
+        (true, false) => Err(IllegalMove::WaterEntryWithoutCiurl),
+        (false, true) => Err(IllegalMove::SpuriousWaterEntryCiurl),
+        _ => Ok(()),
+    }
+}
+
+/// Checks whether `side` can make the move `mv` on `field` under the rule configuration `rules`
+/// ／ルール設定 `rules` のもとで、`side` が `field` 上で指し手 `mv` を指せるかどうかを確かめる
+///
+/// The check follows the official (yhuap) rule as far as one move goes:
+/// the movement patterns of [`movement_patterns`], tam2 hue squares, stepping, protection by a 将 on a tam2 hue square,
+/// and the water-entry flag, which must be set exactly when a piece other than 船 enters water from land.
+/// Rules that span several moves, such as the return of Tam2 to its square, are left to the caller.
+/// ／一手で完結する範囲で官定のルールに従って確かめる。すなわち、[`movement_patterns`] の動き方、皇処、踏越え、
+/// 皇処にいる将による保護、そして入水の印である。入水の印は、船以外の駒が陸から水に入るときにちょうど付いていなければならない。
+/// 皇の再来など、複数の手にわたるルールは呼び出し側に任せる。
+/// # Errors
+/// Returns the first reason found why the move is illegal.
+/// ／指し手が指せない理由のうち、最初に見つかったものを返す。
+pub fn is_legal<R: CetkaikRepresentation>(
+    field: &R::AbsoluteField,
+    mv: PureMove<R>,
+    side: AbsoluteSide,
+    rules: &RuleConfig,
+) -> Result<(), IllegalMove> {
+    let full = Lookup::<R> {
+        board: field.as_board(),
+        config: rules,
+        removed: None,
+    };
+    match mv {
+        PureMove_::NonTamMoveFromHopZuo { color, prof, dest } => {
+            if !field
+                .hop1zuo1_of(side)
+                .any(|p| p == ColorAndProf { color, prof })
+            {
+                return Err(IllegalMove::NotInHop1Zuo1);
+            }
+            if full.at(R::coord_to_index(dest)).is_some() {
+                return Err(IllegalMove::DestinationOccupied);
+            }
+            Ok(())
+        }
+        PureMove_::NonTamMoveSrcDst {
+            src,
+            dest,
+            is_water_entry_ciurl,
+        } => {
+            let (src_index, dest_index) = (R::coord_to_index(src), R::coord_to_index(dest));
+            let prof = full.own_prof(src_index, side)?;
+            full.reach(src_index, dest_index, prof, side, None)?;
+            full.can_occupy(dest_index, side)?;
+            check_water_entry::<R>(src, dest, prof, is_water_entry_ciurl)
+        }
+        PureMove_::NonTamMoveSrcStepDstFinite {
+            src,
+            step,
+            dest,
+            is_water_entry_ciurl,
+        } => {
+            let prof = full.stepping_move(
+                R::coord_to_index(src),
+                R::coord_to_index(step),
+                R::coord_to_index(dest),
+                side,
+                false,
+            )?;
+            check_water_entry::<R>(src, dest, prof, is_water_entry_ciurl)
+        }
+        PureMove_::InfAfterStep {
+            src,
+            step,
+            planned_direction,
+        } => full
+            .stepping_move(
+                R::coord_to_index(src),
+                R::coord_to_index(step),
+                R::coord_to_index(planned_direction),
+                side,
+                true,
+            )
+            .map(|_| ()),
+        PureMove_::TamMoveNoStep {
+            src,
+            first_dest,
+            second_dest,
+        } => full.tam2_move(
+            R::coord_to_index(src),
+            None,
+            R::coord_to_index(first_dest),
+            None,
+            R::coord_to_index(second_dest),
+        ),
+        PureMove_::TamMoveStepsDuringFormer {
+            src,
+            step,
+            first_dest,
+            second_dest,
+        } => full.tam2_move(
+            R::coord_to_index(src),
+            Some(R::coord_to_index(step)),
+            R::coord_to_index(first_dest),
+            None,
+            R::coord_to_index(second_dest),
+        ),
+        PureMove_::TamMoveStepsDuringLatter {
+            src,
+            first_dest,
+            step,
+            second_dest,
+        } => full.tam2_move(
+            R::coord_to_index(src),
+            None,
+            R::coord_to_index(first_dest),
+            Some(R::coord_to_index(step)),
+            R::coord_to_index(second_dest),
+        ),
+    }
+}
@@ -2,11 +2,409 @@
 
 use cetkaik_fundamental::{Color, Profession};
 
+/// Relative views of absolute fields and boards that do not copy them, and adapters that convert coordinates in bulk
+/// ／絶対座標の `Field` や盤面を、コピーせずに相対座標で扱うためのビューと、座標をまとめて変換するアダプタ
+pub mod relative_view;
+
+pub use relative_view::{
+    AbsolutizingIter, AsRelative, RelativeView, RelativeViewMut, RelativizingIter,
+};
+
+/// Interfaces for enumerating moves, generic over the representation
+/// ／表現によらずに指し手を列挙するためのインターフェース
+pub mod movegen;
+
+pub use movegen::{
+    apply_move, apply_move_in_place, apply_move_with_ciurl, apply_move_with_ciurl_and_config,
+    apply_move_with_config, generate_parachutes, play_out, IsMoveGenerator, PureMove,
+};
+
+/// Configuration of the rule variants
+/// ／ルールの差異の設定
+pub mod rules;
+
+pub use rules::{is_ciurl_required, RuleConfig};
+
+/// The movement patterns of the professions, as data shared by every representation
+/// ／どの表現でも共有できるデータとしての、各職種の動き方
+pub mod movement;
+
+pub use movement::{
+    can_reach, movement_patterns, raw_destinations, Blocking, MovementPattern, Reachability,
+    NORMAL_MOVEMENT, TAM2_MOVEMENT, TAM_HUE_MOVEMENT,
+};
+
+/// Checking whether a single move is legal
+/// ／一手の合法性の判定
+pub mod legality;
+
+pub use legality::{is_legal, IllegalMove};
+
+/// The two-phase protocol of stepping moves that end in an infinite movement
+/// ／踏越えて無限移動をする手の二段階の手順
+pub mod stepping;
+
+pub use stepping::StepDeclared;
+
+/// Buffers into which moves are generated
+/// ／指し手の生成先となるバッファ
+pub mod move_list;
+
+pub use move_list::{
+    shuffle_moves, ArrayMoveList, MoveList, SplitMix64, DEFAULT_MOVE_LIST_CAPACITY,
+};
+
+/// Heuristics that order moves so that a search tries promising ones first
+/// ／探索が有望な手から試せるよう指し手を並べ替えるヒューリスティクス
+pub mod ordering;
+
+pub use ordering::{order_moves, HistoryTable, KillerTable};
+
+/// A hash table of search results shared between threads
+/// ／スレッド間で共有される、探索結果のハッシュ表
+pub mod transposition;
+
+pub use transposition::{AlwaysReplace, ReplacementPolicy, TableKey, TranspositionTable};
+
+/// Sources of stick casts
+/// ／投げ棒の結果の供給源
+pub mod ciurl;
+
+#[cfg(feature = "rand")]
+pub use ciurl::RngCiurl;
+pub use ciurl::{FixedCiurl, FnCiurl, IsCiurlSource};
+
+/// Setting up arbitrary positions
+/// ／任意の局面の設定
+pub mod setup;
+
+pub use setup::{
+    initial_with, yhuap_initial_board, yhuap_initial_field, ArrangementSpec, FieldBuilder,
+    SetupError, ValidationError, YHUAP_INITIAL_PIECES, YHUAP_INITIAL_TAM2,
+};
+
+/// Human-readable notation of pieces, moves and fields
+/// ／駒・指し手・`Field` の人間可読な表記
+pub mod notation;
+
+pub use notation::{
+    parse_move, CoordNotation, FieldDump, MoveNotation, NotationStyle, ParsePieceError,
+    PieceGlyphs, PieceNotation,
+};
+
+/// The eight directions on the board, and stepping coordinates along them
+/// ／盤上の八方向と、それに沿った座標の移動
+pub mod direction;
+
+pub use direction::Direction;
+
+/// Enumerating the pieces, and counting the pieces each side has
+/// ／駒の列挙と、各陣営が持つ駒の計数
+pub mod material;
+
+pub use material::{all_piece_kinds, base_value, full_piece_set, MaterialCount};
+
+/// The state of a whole game, beyond the field
+/// ／`Field` を超えた、ゲーム全体の状態
+pub mod game_state;
+
+pub use game_state::{
+    CapturedIter, IsGameState, IsSeasonState, SeasonOutcome, INITIAL_SCORE, SEASON_COUNT,
+};
+
+/// Detection of repeated positions
+/// ／同一局面の検出
+pub mod repetition;
+
+pub use repetition::{hop1zuo1_key, square_key, RepetitionTable};
+
+/// A field whose hash is maintained incrementally
+/// ／ハッシュを差分で更新し続ける `Field`
+pub mod hashed;
+
+pub use hashed::{HashedField, MoveUndo};
+
+/// Indexing of endgame tablebases
+/// ／終盤テーブルベースの索引
+pub mod tablebase;
+
+pub use tablebase::{tb_index, tb_position, TbMaterial};
+
+/// Keys and lookup for opening books
+/// ／定跡のキーと検索
+pub mod book;
+
+pub use book::{book_key, BookMove, BookMoves, OpeningBook};
+
+/// A line-based text protocol between GUIs and engines, in the style of UCI/USI
+/// ／UCI/USI 風の、GUI とエンジンの間の行ベースのテキストプロトコル
+pub mod protocol;
+
+pub use protocol::{run_engine, Engine, GoParams};
+
+/// A generic alpha-beta search, with its limits and the means of stopping it
+/// ／汎用のアルファベータ探索と、その制限および探索を止める手段
+pub mod search;
+
+pub use search::{
+    search, search_with_pool, Evaluator, MaterialEvaluator, Score, SearchController, SearchLimits,
+    SearchState,
+};
+
+/// An arena-backed tree for Monte-Carlo tree search
+/// ／アリーナに置かれたモンテカルロ木探索の木
+pub mod mcts;
+
+pub use mcts::{MctsNode, MctsTree, NodeId};
+
+/// Records of whole games and their replay
+/// ／ゲーム全体の記録とその再生
+pub mod record;
+
+pub use record::{GameRecord, PlayedMove, ReplayError, ReplayIter};
+
+/// Kifu, the moves of a game record written as text, in the conventions of various communities
+/// ／様々なコミュニティの慣習に従ってテキストとして書かれたゲームの記録の指し手（棋譜）
+pub mod kifu;
+
+pub use kifu::{parse_kifu, KifuNotation, MoveDelimiter, PieceNames, RecordStyle};
+
+/// A versioned binary format for saving in-progress games
+/// ／進行中のゲームを保存するための、版付きのバイナリ形式
+pub mod save;
+
+pub use save::{load, save, LoadError, SavedGame, SAVE_MAGIC, SAVE_VERSION};
+
+mod symmetry;
+
+mod forwarding;
+
+/// Tensor encoding of fields for neural networks
+/// ／ニューラルネットワーク向けの `Field` のテンソル符号化
+pub mod planes;
+
+pub use planes::{encode_planes, PLANE_COUNT, PLANE_SIZE};
+
+/// A board wrapper that reports every change
+/// ／すべての変更を報告する盤面のラッパー
+pub mod observed;
+
+pub use observed::{BoardEvent, ObservedBoard};
+
+/// A board wrapper that journals every operation, and replaying of journals
+/// ／すべての操作を記録する盤面のラッパーと、記録の再生
+pub mod recorded;
+
+pub use recorded::{decode_journal, encode_journal, replay, BoardOp, Journal, Recorded};
+
+/// Mutations on a field that can be rolled back
+/// ／取り消すことのできる `Field` への変更
+pub mod transaction;
+
+pub use transaction::Transaction;
+
+/// A field wrapper that clones lazily on the first mutation
+/// ／最初の変更時に遅延してクローンする `Field` のラッパー
+pub mod cow;
+
+pub use cow::CowField;
+
+/// Recycling of field allocations
+/// ／`Field` の確保領域の再利用
+pub mod pool;
+
+pub use pool::FieldPool;
+
+/// A wrapper that validates every operation on a board or a field
+/// ／盤面や `Field` への操作をすべて検証するラッパー
+pub mod checked;
+
+pub use checked::Checked;
+
+/// Occupancy of boards as bit masks
+/// ／ビットマスクとしての盤面の占有状況
+pub mod bitboard;
+
+pub use bitboard::IsBitBoard;
+
+/// A hop1zuo1 of one side as an abstraction of its own
+/// ／一方の陣営の手駒を、それ自体として抽象化したもの
+pub mod hop1zuo1;
+
+pub use hop1zuo1::{Hop1Zuo1Both, Hop1Zuo1Counts, Hop1Zuo1CountsIter, IsHop1Zuo1};
+
+/// Mapping between moves and the outputs of a policy network
+/// ／指し手と方策ネットワークの出力との対応
+pub mod policy;
+
+pub use policy::{move_to_policy_index, policy_index_to_move, POLICY_SIZE};
+
+/// Self-play between engines, and training samples drawn from its records
+/// ／エンジンどうしの自己対局と、その記録から取り出す学習用サンプル
+pub mod self_play;
+
+pub use self_play::{
+    self_play, training_samples, IllegalEngineMove, TrainingSample, TrainingSamples,
+};
+
+/// Differences between boards and between fields
+/// ／盤面どうし、`Field` どうしの差分
+pub mod diff;
+
+pub use diff::{captures_between, DiffIter, FieldDiff, Hop1Zuo1Diff, SquareDiff};
+
+/// Iterators over the squares of a board
+/// ／盤面のマスを列挙するイテレータ
+pub mod squares;
+
+pub use squares::{DisplayIter, FinalsIter, GuardIter, ProfIter, RawDestIter, RegionIter};
+
+/// An object-safe facade over any representation, with coordinates and pieces erased to canonical forms
+/// ／座標と駒を正準な形に消去した、任意の表現に対するオブジェクト安全なファサード
+pub mod dynamic;
+
+pub use dynamic::{CanonicalPiece, DynBoard, DynField, ErasedBoard, ErasedField};
+
+/// A C ABI over [`DynField`]
+/// ／[`DynField`] に対する C の ABI
+///
+/// The functions here work on any representation through [`DynField`].
+/// Since a C function cannot be generic, the constructor is provided as the generic [`ffi::yhuap_initial`],
+/// which a `cdylib` exports under a name of its choice with [`export_yhuap_initial!`](crate::export_yhuap_initial).
+/// ／ここにある関数は [`DynField`] を通じて任意の表現に対して動作する。
+/// C の関数はジェネリックにできないので、コンストラクタはジェネリックな [`ffi::yhuap_initial`] として提供され、
+/// `cdylib` 側で [`export_yhuap_initial!`](crate::export_yhuap_initial) を用いて好きな名前で公開する。
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+/// `#[wasm_bindgen]` wrappers over [`DynField`], with JSON conversions
+/// ／[`DynField`] に対する `#[wasm_bindgen]` なラッパー（JSON 変換付き）
+///
+/// With the `schemars` feature, the JSON forms of coordinates, pieces, moves and fields are described by `schemars::JsonSchema`.
+/// ／`schemars` フィーチャを有効にすると、座標・駒・指し手・`Field` の JSON 形式が `schemars::JsonSchema` によって記述される。
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+/// Node counting for verifying move generators
+/// ／指し手生成を検証するためのノード数計算
+pub mod perft;
+
+pub use perft::{perft, perft_with_pool};
+
+/// Property-testing strategies that generate legal positions and moves
+/// ／合法な局面や指し手を生成する、プロパティテスト用の戦略
+#[cfg(feature = "proptest")]
+pub mod strategy;
+
+/// Random positions and random playouts
+/// ／ランダムな局面とランダムなプレイアウト
+#[cfg(feature = "rand")]
+pub mod random;
+
+/// Parallel iteration over squares and moves
+/// ／マスや指し手の並列イテレーション
+#[cfg(feature = "rayon")]
+pub mod parallel;
+
+/// Canonical forms of fields, moves and records that rkyv can archive and access without copying
+/// ／rkyv で保存し、コピーせずに参照できる `Field`・指し手・記録の正準な形
+#[cfg(feature = "rkyv")]
+pub mod archive;
+
+#[cfg(feature = "rkyv")]
+pub use archive::{FieldData, MoveData, PlayedMoveData, RecordData};
+
+/// An absolute coordinate written as its canonical index; see [`CetkaikRepresentation::coord_to_index`]
+/// ／正準な番号で書かれた絶対座標。[`CetkaikRepresentation::coord_to_index`] を参照。
+pub type CanonicalCoord = u8;
+
+/// The number of rows of the board, from A to IA
+/// ／盤の行数。A から IA まで
+///
+/// The square in the row `row` and the column `column`, each counted from 0, has the canonical index `COLS * row + column`,
+/// so the canonical indices run below `ROWS * COLS`.
+/// ／0 から数えて `row` 行 `column` 列目のマスの正準な番号は `COLS * row + column` なので、正準な番号は `ROWS * COLS` 未満である。
+pub const ROWS: usize = 9;
+
+/// The number of columns of the board, from K to P
+/// ／盤の列数。K から P まで
+pub const COLS: usize = 9;
+
+/// [`ROWS`] as a canonical index, for arithmetic on canonical indices
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) const ROWS_U8: CanonicalCoord = ROWS as CanonicalCoord;
+
+/// [`COLS`] as a canonical index, for arithmetic on canonical indices
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) const COLS_U8: CanonicalCoord = COLS as CanonicalCoord;
+
+/// The number of squares, i.e. one more than the largest canonical index
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) const SQUARE_COUNT: CanonicalCoord = (ROWS * COLS) as CanonicalCoord;
+
 /// A trait that signifies that you can use it as a `Board` with an absolute coordinate
 /// ／絶対座標付きの `Board` として扱える型を表すトレイト
 pub trait IsAbsoluteBoard: IsBoard {
     /// The initial arrangement of the official (yhuap) rule
-    fn yhuap_initial() -> Self;
+    /// ／官定の初期配置
+    ///
+    /// The default puts [`YHUAP_INITIAL_PIECES`] and Tam2 on [`IsAbsoluteBoard::empty`], so that an implementation need not spell out the arrangement again.
+    /// ／既定の実装は [`IsAbsoluteBoard::empty`] に [`YHUAP_INITIAL_PIECES`] と皇を置くので、実装で配置を改めて書き下す必要はない。
+    /// # Panics
+    /// Panics if [`IsAbsoluteCoord::from_canonical_index`] fails on an index below 81.
+    /// ／[`IsAbsoluteCoord::from_canonical_index`] が 81 未満の番号に対して失敗するとパニックする。
+    #[must_use]
+    fn yhuap_initial() -> Self
+    where
+        Self: std::marker::Sized,
+        Self::Coord: IsAbsoluteCoord,
+        Self::PieceWithSide: IsPieceWithSide<Side = cetkaik_fundamental::AbsoluteSide>,
+    {
+        let mut board = Self::empty();
+        setup::put_yhuap_initial(&mut board);
+        board
+    }
+
+    /// A board with no piece on it
+    /// ／駒がひとつもない盤面
+    fn empty() -> Self;
+
+    /// A board with the given pieces on it, and nothing else
+    /// ／与えられた駒だけが置かれた盤面
+    /// # Errors
+    /// Fails if more than one piece is placed on the same square.
+    /// ／同じマスに二つ以上の駒が置かれていれば失敗する。
+    fn from_piece_iter<I: IntoIterator<Item = (Self::Coord, Self::PieceWithSide)>>(
+        iter: I,
+    ) -> Result<Self, SetupError>
+    where
+        Self: std::marker::Sized,
+    {
+        let mut board = Self::empty();
+        for (coord, piece) in iter {
+            if board.is_occupied(coord) {
+                return Err(SetupError::SquareOccupiedTwice);
+            }
+            board.put(coord, Some(piece));
+        }
+        Ok(board)
+    }
+
+    /// Whether `self` and `other` have the same piece on every square
+    /// ／`self` と `other` のすべてのマスに同じ駒があるかどうか
+    ///
+    /// Unlike `==`, which may also compare the internal layout, this looks only at the pieces.
+    /// Override this when the representation can compare the pieces bitwise.
+    /// ／内部の配置まで比較しうる `==` と異なり、これは駒だけを見る。表現が駒をビット単位で比較できる場合は、これを上書きするとよい。
+    fn semantically_equal(&self, other: &Self) -> bool
+    where
+        Self::PieceWithSide: PartialEq,
+    {
+        self.occupied_count() == other.occupied_count()
+            && self
+                .occupied_squares()
+                .all(|c| self.peek(c) == other.peek(c))
+    }
 }
 
 /// A trait that signifies that you can use it as a `Board`
@@ -19,20 +417,63 @@ pub trait IsBoard {
 
     /// peek
     fn peek(&self, c: Self::Coord) -> Option<Self::PieceWithSide>;
+    /// Looks at several squares at once
+    /// ／複数のマスを一度に見る
+    ///
+    /// Override this when the representation can answer with a few mask operations instead of repeated `peek`s.
+    /// ／`peek` を繰り返すよりも少数のマスク演算で答えられる表現は、これを上書きするとよい。
+    fn peek_many<const N: usize>(
+        &self,
+        coords: [Self::Coord; N],
+    ) -> [Option<Self::PieceWithSide>; N]
+    where
+        Self: std::marker::Sized,
+    {
+        coords.map(|c| self.peek(c))
+    }
     /// pop
     fn pop(&mut self, c: Self::Coord) -> Option<Self::PieceWithSide>;
     /// put either a piece or a `None`
     fn put(&mut self, c: Self::Coord, p: Option<Self::PieceWithSide>);
+    /// Whether the square is empty
+    /// ／マスが空いているかどうか
+    fn is_empty(&self, c: Self::Coord) -> bool {
+        self.peek(c).is_none()
+    }
+    /// Whether the square is occupied
+    /// ／マスに駒があるかどうか
+    fn is_occupied(&self, c: Self::Coord) -> bool {
+        !self.is_empty(c)
+    }
     /// assert that the square is empty
-    fn assert_empty(&self, c: Self::Coord);
+    /// # Panics
+    /// Panics if the square is occupied
+    fn assert_empty(&self, c: Self::Coord) {
+        assert!(
+            self.is_empty(c),
+            "Expected the square {c:?} to be empty, but it was occupied"
+        );
+    }
     /// assert that the square is occupied
-    fn assert_occupied(&self, c: Self::Coord);
+    /// # Panics
+    /// Panics if the square is empty
+    fn assert_occupied(&self, c: Self::Coord) {
+        assert!(
+            self.is_occupied(c),
+            "Expected the square {c:?} to be occupied, but it was empty"
+        );
+    }
     /// Moves the piece located at `from` to an empty square `to`.
     /// # Panics
     /// Should panics if either:
     /// - `from` is unoccupied
     /// - `to` is already occupied
+    ///
+    /// To skip these checks where the move has already been validated, call [`IsBoard::mov_unchecked`] instead.
+    /// ／指し手が既に検証済みでこれらの検査を省きたい箇所では、代わりに [`IsBoard::mov_unchecked`] を呼ぶ。
     fn mov(&mut self, from: Self::Coord, to: Self::Coord) {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(?from, ?to, "mov");
         self.pop(from).map_or_else(
             || panic!("Empty square encountered at {from:?}"),
             |src_piece| {
@@ -41,19 +482,398 @@ pub trait IsBoard {
             },
         );
     }
+    /// Moves the piece located at `from` to `to` without checking that `from` is occupied and `to` is empty
+    /// ／`from` に駒があり `to` が空いていることを確かめずに、`from` にある駒を `to` に動かす
+    ///
+    /// Meant for hot paths where the move has already been validated. Breaking the assumptions causes no undefined behavior:
+    /// whatever was at `to` is overwritten with whatever was at `from`, and `from` becomes empty.
+    /// ／指し手が既に検証済みである、速度が重要な箇所のためのもの。前提が破られても未定義動作にはならず、
+    /// `to` にあったものは `from` にあったもので上書きされ、`from` は空になる。
+    fn mov_unchecked(&mut self, from: Self::Coord, to: Self::Coord) {
+        let piece = self.pop(from);
+        self.put(to, piece);
+    }
+    /// Exchanges the contents of `a` and `b`, either of which may be empty
+    /// ／`a` と `b` の中身を入れ替える。どちらが空でもよい。
+    ///
+    /// Swapping a square with itself leaves the board unchanged.
+    /// Override this when the representation can exchange the squares with a few mask operations.
+    /// ／同じマスどうしを入れ替えても盤面は変わらない。表現が少数のマスク演算でマスを入れ替えられる場合は、これを上書きするとよい。
+    fn swap(&mut self, a: Self::Coord, b: Self::Coord) {
+        let piece_a = self.pop(a);
+        let piece_b = self.pop(b);
+        self.put(a, piece_b);
+        self.put(b, piece_a);
+    }
 
-    type EmptySquaresIter: Iterator<Item = Self::Coord>;
+    /// An iterator over the empty squares, which knows its length, keeps returning `None` once exhausted,
+    /// and can also be consumed from the back
+    /// ／空きマスを列挙するイテレータ。長さが分かり、尽きた後は `None` を返し続け、後ろからも読み進められる。
+    type EmptySquaresIter: ExactSizeIterator<Item = Self::Coord>
+        + DoubleEndedIterator
+        + core::iter::FusedIterator;
+    /// Iterates over the squares that have nothing on them
+    /// ／何も置かれていないマスを列挙する
+    ///
+    /// The order is up to the implementation; use [`CetkaikRepresentation::empty_squares_sorted`] for the canonical order.
+    /// ／順序は実装に任される。正準な順序が必要なら [`CetkaikRepresentation::empty_squares_sorted`] を用いる。
     fn empty_squares(&self) -> Self::EmptySquaresIter;
+
+    /// An iterator over the occupied squares, which can also be consumed from the back
+    /// ／駒のあるマスを列挙するイテレータ。後ろからも読み進められる。
+    type OccupiedSquaresIter: DoubleEndedIterator<Item = Self::Coord>;
+    /// Iterates over the squares that have a piece on them (including Tam2)
+    /// ／駒（皇を含む）が置かれているマスを列挙する
+    fn occupied_squares(&self) -> Self::OccupiedSquaresIter;
+
+    /// The number of empty squares
+    /// ／空いているマスの数
+    ///
+    /// The default takes the length of [`IsBoard::empty_squares`]; a bitboard-based representation can answer with a popcount.
+    /// ／既定の実装は [`IsBoard::empty_squares`] の長さを取る。ビットボードに基づく表現なら popcount で答えられる。
+    fn empty_square_count(&self) -> usize {
+        self.empty_squares().len()
+    }
+
+    /// The number of occupied squares (including the one with Tam2)
+    /// ／駒（皇を含む）が置かれているマスの数
+    ///
+    /// The default drains [`IsBoard::occupied_squares`]; a bitboard-based representation can answer with a popcount.
+    /// ／既定の実装は [`IsBoard::occupied_squares`] を最後まで回す。ビットボードに基づく表現なら popcount で答えられる。
+    fn occupied_count(&self) -> usize {
+        self.occupied_squares().count()
+    }
+
+    /// The empty squares as a bit mask, in which bit `i` stands for the square whose canonical index is `i`
+    /// ／空きマスを表すビットマスク。ビット `i` は正準な番号が `i` のマスを表す。
+    ///
+    /// This answers for all 81 squares at once, e.g. when generating parachutes with [`generate_parachutes`].
+    /// The default complements [`IsBitBoard::occupancy`] of `R`, so a representation that answers that with its native masks answers this too.
+    /// ／81 マスすべてについて一度に答える。[`generate_parachutes`] で打つ手を生成する場合などに用いる。
+    /// 既定の実装は `R` の [`IsBitBoard::occupancy`] の補集合を取るので、それを固有のマスクで答える表現はこれにも同様に答える。
+    fn empty_mask<R>(&self) -> u128
+    where
+        Self: Sized,
+        R: IsBitBoard<AbsoluteBoard = Self>,
+    {
+        bitboard::ALL_SQUARES & !R::occupancy(self)
+    }
+
+    /// Lists the squares that hold a piece of the profession `prof` belonging to `side`, in the order of `occupied_squares`
+    /// ／`side` に属する職種 `prof` の駒があるマスを、`occupied_squares` の順で列挙する
+    ///
+    /// The default scans every occupied square; a bitboard-based representation can answer with a bit scan.
+    /// ／既定の実装は駒のあるマスをすべて走査する。ビットボードに基づく表現はビット走査で答えることができる。
+    fn coords_of(
+        &self,
+        prof: Profession,
+        side: <Self::PieceWithSide as IsPieceWithSide>::Side,
+    ) -> ProfIter<Self::Coord>
+    where
+        Self::PieceWithSide: IsPieceWithSide,
+    {
+        self.occupied_squares()
+            .filter(|&c| {
+                self.peek(c).is_some_and(|piece| {
+                    piece.match_on_piece_and_apply(&|| false, &|_, p, s| p == prof && s == side)
+                })
+            })
+            .collect()
+    }
+
+    /// Lists the squares whose contents differ between `self` and `other`.
+    /// Squares that are occupied in `self` come first, in the order of `occupied_squares`.
+    /// ／`self` と `other` で中身が異なるマスを列挙する。`self` で駒があるマスが `occupied_squares` の順で先に来る。
+    fn diff(&self, other: &Self) -> DiffIter<Self::Coord, Self::PieceWithSide>
+    where
+        Self::PieceWithSide: PartialEq,
+    {
+        let changed_or_vacated = self.occupied_squares().filter_map(|coord| {
+            let (before, after) = (self.peek(coord), other.peek(coord));
+            (before != after).then_some(SquareDiff {
+                coord,
+                before,
+                after,
+            })
+        });
+        let newly_occupied = other
+            .occupied_squares()
+            .filter(|coord| self.peek(*coord).is_none())
+            .map(|coord| SquareDiff {
+                coord,
+                before: None,
+                after: other.peek(coord),
+            });
+        changed_or_vacated.chain(newly_occupied).collect()
+    }
+
+    /// Applies a patch such as the one produced by [`IsBoard::diff`]
+    /// ／[`IsBoard::diff`] が生成するようなパッチを適用する
+    /// # Errors
+    /// Fails without modifying `self` if some square does not hold what the patch expects to find there.
+    /// ／パッチが想定する中身と異なるマスがあれば、`self` を変更せずに失敗する。
+    fn apply_diff(
+        &mut self,
+        diff: &[SquareDiff<Self::Coord, Self::PieceWithSide>],
+    ) -> Result<(), &'static str>
+    where
+        Self::PieceWithSide: PartialEq,
+    {
+        if diff.iter().any(|d| self.peek(d.coord) != d.before) {
+            return Err("The board does not match what the patch expects");
+        }
+        for d in diff {
+            self.put(d.coord, d.after);
+        }
+        Ok(())
+    }
 }
 
 /// A trait that signifies that you can use it as a `Field` in absolute coordinates
 /// ／絶対座標で書かれた `Field` として扱える型を表すトレイト
 pub trait IsAbsoluteField: IsField {
-    /// The initial arrangement of the official (yhuap) rule
-    fn yhuap_initial() -> Self;
+    /// The initial arrangement of the official (yhuap) rule, with both hop1zuo1s empty
+    /// ／官定の初期配置。手駒はどちらも空である。
+    ///
+    /// The default puts [`YHUAP_INITIAL_PIECES`] and Tam2 on the board of [`IsAbsoluteField::empty`], as [`IsAbsoluteBoard::yhuap_initial`] does.
+    /// ／既定の実装は、[`IsAbsoluteBoard::yhuap_initial`] と同様に [`IsAbsoluteField::empty`] の盤面に [`YHUAP_INITIAL_PIECES`] と皇を置く。
+    /// # Panics
+    /// Same as [`IsAbsoluteBoard::yhuap_initial`].
+    /// ／[`IsAbsoluteBoard::yhuap_initial`] と同じ。
+    #[must_use]
+    fn yhuap_initial() -> Self
+    where
+        Self: std::marker::Sized,
+        Self::Coord: IsAbsoluteCoord,
+        Self::PieceWithSide: IsPieceWithSide<Side = cetkaik_fundamental::AbsoluteSide>,
+    {
+        let mut field = Self::empty();
+        setup::put_yhuap_initial(field.as_board_mut());
+        field
+    }
+
+    /// A field with no piece on the board nor in the hop1zuo1s
+    /// ／盤上にも手駒にも駒がひとつもない `Field`
+    fn empty() -> Self;
+
+    /// The hop1zuo1 of one side
+    /// ／一方の陣営の手駒
+    type Hop1Zuo1: IsHop1Zuo1;
+
+    /// The hop1zuo1 of `side`
+    /// ／`side` の手駒
+    fn hop1zuo1(&self, side: cetkaik_fundamental::AbsoluteSide) -> &Self::Hop1Zuo1;
+
+    /// Iterates over the hop1zuo1 of `side`
+    /// ／`side` の手駒を列挙する
+    ///
+    /// The order is up to the implementation; use [`IsAbsoluteField::hop1zuo1_sorted`] for the canonical order.
+    /// ／順序は実装に任される。正準な順序が必要なら [`IsAbsoluteField::hop1zuo1_sorted`] を用いる。
+    fn hop1zuo1_of(
+        &self,
+        side: cetkaik_fundamental::AbsoluteSide,
+    ) -> <Self::Hop1Zuo1 as IsHop1Zuo1>::Iter {
+        self.hop1zuo1(side).iter()
+    }
+
+    /// The hop1zuo1 of `side` in the canonical order, which sorts by color (Kok1 first) and then by profession
+    /// in the order Nuak1, Kauk2, Gua2, Kaun1, Dau2, Maun1, Kua2, Tuk2, Uai1, Io
+    /// ／正準な順序に並べた `side` の手駒。色（赤が先）、次に職種（船・兵・弓・車・虎・馬・筆・巫・将・王の順）で整列する。
+    fn hop1zuo1_sorted(
+        &self,
+        side: cetkaik_fundamental::AbsoluteSide,
+    ) -> Vec<cetkaik_fundamental::ColorAndProf> {
+        self.hop1zuo1(side).sorted()
+    }
 
-    type Hop1Zuo1Iter: Iterator<Item = cetkaik_fundamental::ColorAndProf>;
-    fn hop1zuo1_of(&self, side: cetkaik_fundamental::AbsoluteSide) -> Self::Hop1Zuo1Iter;
+    /// Iterates over both hop1zuo1s, that of `ASide` first, pairing each piece with the side holding it
+    /// ／両陣営の手駒を、`ASide` のものから順に、持っている陣営と組にして列挙する
+    fn hop1zuo1_both(&self) -> Hop1Zuo1Both<<Self::Hop1Zuo1 as IsHop1Zuo1>::Iter> {
+        use cetkaik_fundamental::AbsoluteSide;
+        Hop1Zuo1Both::new(
+            self.hop1zuo1_of(AbsoluteSide::ASide),
+            self.hop1zuo1_of(AbsoluteSide::IASide),
+        )
+    }
+
+    /// The numbers of Kok1 and Huok2 pieces, in this order, in the hop1zuo1 of `side`
+    /// ／`side` の手駒にある赤の駒と黒の駒の数（この順）
+    ///
+    /// Scoring a hand of colors (色の役) and showing badges in a UI need only these two numbers;
+    /// a representation that keeps them can override this to answer in constant time.
+    /// ／色の役の採点や UI のバッジの表示にはこの二つの数だけで足りる。これを保持している表現は、上書きして定数時間で答えることができる。
+    fn hand_count_by_color(&self, side: cetkaik_fundamental::AbsoluteSide) -> (usize, usize) {
+        self.hop1zuo1_of(side)
+            .fold((0, 0), |(kok1, huok2), piece| match piece.color {
+                Color::Kok1 => (kok1 + 1, huok2),
+                Color::Huok2 => (kok1, huok2 + 1),
+            })
+    }
+
+    /// Adds a piece to the hop1zuo1 of `side`
+    /// ／`side` の手駒に駒を加える
+    fn add_to_hop1zuo1(
+        &mut self,
+        side: cetkaik_fundamental::AbsoluteSide,
+        piece: cetkaik_fundamental::ColorAndProf,
+    );
+
+    /// Removes one copy of a piece from the hop1zuo1 of `side`, returning whether it was found
+    /// ／`side` の手駒から駒をひとつ取り除き、見つかったかどうかを返す
+    fn remove_from_hop1zuo1(
+        &mut self,
+        side: cetkaik_fundamental::AbsoluteSide,
+        piece: cetkaik_fundamental::ColorAndProf,
+    ) -> bool;
+
+    /// Whether `self` and `other` have the same pieces on the board and the same hop1zuo1s, the latter compared as multisets
+    /// ／`self` と `other` が盤上に同じ駒を持ち、手駒も多重集合として等しいかどうか
+    ///
+    /// Unlike `==`, which may also compare the internal layout such as the order of the hop1zuo1s, this looks only at the pieces.
+    /// Override this when the representation can compare the pieces bitwise.
+    /// ／手駒の順序のような内部の配置まで比較しうる `==` と異なり、これは駒だけを見る。
+    /// 表現が駒をビット単位で比較できる場合は、これを上書きするとよい。
+    fn semantically_equal(&self, other: &Self) -> bool
+    where
+        Self::Board: IsAbsoluteBoard,
+        Self::PieceWithSide: PartialEq,
+    {
+        use cetkaik_fundamental::AbsoluteSide;
+        self.as_board().semantically_equal(other.as_board())
+            && [AbsoluteSide::ASide, AbsoluteSide::IASide]
+                .into_iter()
+                .all(|side| {
+                    Hop1Zuo1Diff::between(self.hop1zuo1_of(side), other.hop1zuo1_of(side))
+                        .is_empty()
+                })
+    }
+
+    /// Checks that the pieces, counting both the board and the hop1zuo1s, can occur in a game
+    /// ／盤上と手駒を合わせた駒の内訳が、ゲーム中に現れうるものであるかを検査する
+    ///
+    /// There must be exactly one Tam2, and no more pieces of each color and profession than the game has.
+    /// ／皇はちょうど一つでなければならず、各色・各職種の駒はゲームに存在する数を超えてはならない。
+    /// # Errors
+    /// Returns the first violation found.
+    /// ／最初に見つかった違反を返す。
+    fn validate(&self) -> Result<(), ValidationError>
+    where
+        Self: std::marker::Sized,
+        Self::PieceWithSide: IsPieceWithSide,
+    {
+        setup::check_census(&setup::census(self))
+    }
+
+    /// How many pieces of each profession `side` has, counting both the board and its hop1zuo1
+    /// ／`side` が持つ各職種の駒の数。盤上と手駒の両方を数える。
+    fn material_count(&self, side: cetkaik_fundamental::AbsoluteSide) -> MaterialCount
+    where
+        Self: std::marker::Sized,
+        Self::PieceWithSide: IsPieceWithSide<Side = cetkaik_fundamental::AbsoluteSide>,
+    {
+        material::material_count(self, side)
+    }
+
+    /// The material of `side` minus that of its opponent, counting both the board and the hop1zuo1s
+    /// ／`side` の駒の価値から相手の駒の価値を引いたもの。盤上と手駒の両方を数える。
+    ///
+    /// By default the pieces are valued by [`base_value`]; an engine with its own table of values can override this.
+    /// ／既定では駒を [`base_value`] で評価する。独自の価値の表を持つエンジンはこれを上書きできる。
+    fn material_eval(&self, side: cetkaik_fundamental::AbsoluteSide) -> i32
+    where
+        Self: std::marker::Sized,
+        Self::PieceWithSide: IsPieceWithSide<Side = cetkaik_fundamental::AbsoluteSide>,
+    {
+        material::material_eval(self, side)
+    }
+
+    /// The [`MaterialCount::signature`]s of both sides, `ASide` in the low 32 bits and `IASide` in the high 32 bits
+    /// ／両陣営の [`MaterialCount::signature`]。下位 32 ビットが `ASide`、上位 32 ビットが `IASide` である。
+    ///
+    /// Two fields with the same signature have the same material, which is what evaluation,
+    /// endgame classification and tablebase indexing key on.
+    /// ／署名が同じ二つの `Field` は駒の内訳が同じであり、評価・終盤の分類・テーブルベースの索引はこれをキーにする。
+    fn material_signature(&self) -> u64
+    where
+        Self: std::marker::Sized,
+        Self::PieceWithSide: IsPieceWithSide<Side = cetkaik_fundamental::AbsoluteSide>,
+    {
+        use cetkaik_fundamental::AbsoluteSide;
+        let a = self.material_count(AbsoluteSide::ASide).signature();
+        let ia = self.material_count(AbsoluteSide::IASide).signature();
+        u64::from(ia) << 32 | u64::from(a)
+    }
+
+    /// Starts a transaction, through which the field can be mutated and then restored without cloning
+    /// ／トランザクションを開始する。これを通じて `Field` を変更し、クローンせずに元に戻すことができる。
+    fn begin_transaction(&mut self) -> Transaction<'_, Self>
+    where
+        Self: std::marker::Sized,
+        Self::Coord: Copy,
+        Self::PieceWithSide: Copy,
+    {
+        Transaction::new(self)
+    }
+
+    /// Computes what changed from `self` to `other`, both on the board and in the hop1zuo1s
+    /// ／`self` から `other` への変化を、盤面と手駒の両方について求める
+    fn diff(&self, other: &Self) -> FieldDiff<Self::Coord, Self::PieceWithSide>
+    where
+        Self::PieceWithSide: PartialEq,
+    {
+        use cetkaik_fundamental::AbsoluteSide;
+        FieldDiff {
+            squares: self.as_board().diff(other.as_board()).collect(),
+            a_side_hop1zuo1: Hop1Zuo1Diff::between(
+                self.hop1zuo1_of(AbsoluteSide::ASide),
+                other.hop1zuo1_of(AbsoluteSide::ASide),
+            ),
+            ia_side_hop1zuo1: Hop1Zuo1Diff::between(
+                self.hop1zuo1_of(AbsoluteSide::IASide),
+                other.hop1zuo1_of(AbsoluteSide::IASide),
+            ),
+        }
+    }
+
+    /// Applies a patch such as the one produced by [`IsAbsoluteField::diff`]
+    /// ／[`IsAbsoluteField::diff`] が生成するようなパッチを適用する
+    /// # Errors
+    /// Fails without modifying `self` if some square does not hold what the patch expects to find there,
+    /// or if a piece to be removed from a hop1zuo1 is not there.
+    /// ／パッチが想定する中身と異なるマスがあるか、手駒から取り除くべき駒が見つからなければ、`self` を変更せずに失敗する。
+    fn apply_diff(
+        &mut self,
+        diff: &FieldDiff<Self::Coord, Self::PieceWithSide>,
+    ) -> Result<(), &'static str>
+    where
+        Self: std::marker::Sized,
+        Self::PieceWithSide: PartialEq,
+    {
+        use cetkaik_fundamental::AbsoluteSide;
+        let sides = [
+            (AbsoluteSide::ASide, &diff.a_side_hop1zuo1),
+            (AbsoluteSide::IASide, &diff.ia_side_hop1zuo1),
+        ];
+        for (side, hop1zuo1_diff) in sides {
+            let mut remaining = self.hop1zuo1_of(side).collect::<Vec<_>>();
+            for piece in &hop1zuo1_diff.removed {
+                let Some(i) = remaining.iter().position(|p| p == piece) else {
+                    return Err("The hop1zuo1 does not have a piece that the patch removes");
+                };
+                remaining.remove(i);
+            }
+        }
+        self.as_board_mut().apply_diff(&diff.squares)?;
+        for (side, hop1zuo1_diff) in sides {
+            for piece in &hop1zuo1_diff.removed {
+                self.remove_from_hop1zuo1(side, *piece);
+            }
+            for piece in &hop1zuo1_diff.added {
+                self.add_to_hop1zuo1(side, *piece);
+            }
+        }
+        Ok(())
+    }
 }
 
 /// A trait that signifies that you can use it as a `Field`
@@ -104,6 +924,34 @@ pub trait IsField {
     fn as_board_mut(&mut self) -> &mut Self::Board;
 }
 
+/// A trait that signifies that you can use it as a coordinate, either absolute or relative
+/// ／絶対座標または相対座標として扱える型を表すトレイト
+pub trait IsCoord: Copy + Eq {
+    /// Whether the square is a tam2 nua2 (tam2's water), entry to which is restricted
+    /// ／マスが皇水（たむぬあ）であるかどうか
+    fn is_water(self) -> bool;
+}
+
+/// A relative coordinate given as `[row, column]`, each counted from 0 at a corner of the board.
+/// Since the water is symmetric, the answer does not depend on which corner is chosen.
+/// Representations cannot implement [`IsCoord`] for an array themselves, so it is provided here.
+/// ／盤の隅を 0 として数えた `[行, 列]` で表された相対座標。
+/// 皇水は対称なので、どの隅を選んでも結果は変わらない。
+/// 表現の側では配列に [`IsCoord`] を実装できないため、ここで提供する。
+impl IsCoord for [usize; 2] {
+    fn is_water(self) -> bool {
+        matches!(self, [4, 2..=6] | [2..=6, 4])
+    }
+}
+
+/// A trait that signifies that you can use it as an absolute coordinate, which can be built from its canonical index
+/// ／絶対座標として扱える型を表すトレイト。正準な番号から組み立てることができる。
+pub trait IsAbsoluteCoord: IsCoord {
+    /// The square whose canonical index (see [`CetkaikRepresentation::coord_to_index`]) is `index`; `None` if `index` is 81 or more
+    /// ／正準な番号（[`CetkaikRepresentation::coord_to_index`] を参照）が `index` であるマス。`index` が 81 以上なら `None`
+    fn from_canonical_index(index: CanonicalCoord) -> Option<Self>;
+}
+
 pub trait IsPieceWithSide {
     type Side: Copy + Eq;
     fn has_prof(self, prof: Profession) -> bool
@@ -117,25 +965,106 @@ pub trait IsPieceWithSide {
         f_tam: &dyn Fn() -> U,
         f_piece: &dyn Fn(Color, Profession, Self::Side) -> U,
     ) -> U;
+    /// The color and the profession of the piece, or `None` for Tam2
+    /// ／駒の色と職種。皇なら `None`
+    fn kind(self) -> Option<(Color, Profession)>
+    where
+        Self: std::marker::Sized,
+    {
+        self.match_on_piece_and_apply(&|| None, &|color, prof, _| Some((color, prof)))
+    }
+    /// Whether the two pieces have the same color and profession, ignoring which side they belong to
+    /// ／所属側を無視して、二つの駒の色と職種が等しいかどうか
+    ///
+    /// Tam2 is only of the same kind as Tam2.
+    /// ／皇は皇とのみ同じ種類である。
+    fn same_kind(self, other: Self) -> bool
+    where
+        Self: std::marker::Sized,
+    {
+        self.kind() == other.kind()
+    }
+    /// Constructs a non-Tam2 piece
+    /// ／皇以外の駒を作る
+    fn non_tam2_piece(color: Color, prof: Profession, side: Self::Side) -> Self
+    where
+        Self: std::marker::Sized;
+    /// Constructs Tam2
+    /// ／皇を作る
+    fn tam2() -> Self
+    where
+        Self: std::marker::Sized;
+    /// The canonical `u8` encoding of a piece, shared by every representation
+    /// ／駒の正準な `u8` 符号。すべての表現で共通である。
+    ///
+    /// Tam2 is 0, and any other piece is `1 + 20 * side + 10 * color + prof`,
+    /// where `ASide` and `IASide` are 0 and 1, `Kok1` and `Huok2` are 0 and 1,
+    /// and the professions Nuak1, Kauk2, Gua2, Kaun1, Dau2, Maun1, Kua2, Tuk2, Uai1 and Io are numbered from 0 to 9.
+    /// The codes therefore range from 0 to 40.
+    /// ／皇は 0、それ以外の駒は `1 + 20 * 側 + 10 * 色 + 職種` である。
+    /// ここで `ASide` と `IASide` は 0 と 1、`Kok1` と `Huok2` は 0 と 1、
+    /// 職種 Nuak1, Kauk2, Gua2, Kaun1, Dau2, Maun1, Kua2, Tuk2, Uai1, Io は 0 から 9 の番号とする。
+    /// したがって符号は 0 から 40 の範囲に収まる。
+    #[must_use]
+    fn to_canonical_u8(self) -> u8
+    where
+        Self: std::marker::Sized + IsPieceWithSide<Side = cetkaik_fundamental::AbsoluteSide>,
+    {
+        CanonicalPiece::from_piece(self).code()
+    }
+    /// The inverse of [`IsPieceWithSide::to_canonical_u8`]; `None` if `code` is 41 or more
+    /// ／[`IsPieceWithSide::to_canonical_u8`] の逆。`code` が 41 以上なら `None`
+    #[must_use]
+    fn from_canonical_u8(code: u8) -> Option<Self>
+    where
+        Self: std::marker::Sized + IsPieceWithSide<Side = cetkaik_fundamental::AbsoluteSide>,
+    {
+        CanonicalPiece::from_code(code).map(|piece| match piece {
+            CanonicalPiece::Tam2 => Self::tam2(),
+            CanonicalPiece::NonTam2Piece { color, prof, side } => {
+                Self::non_tam2_piece(color, prof, side)
+            }
+        })
+    }
 }
 
 pub trait CetkaikRepresentation {
+    /// The square on which Tam2 starts in the official (yhuap) rule, i.e. ZO, whose canonical index is [`YHUAP_INITIAL_TAM2`]
+    /// ／官定で皇が最初に置かれるマス、すなわち ZO。正準な番号は [`YHUAP_INITIAL_TAM2`] である。
+    const TAM2_INITIAL: Self::AbsoluteCoord;
+
     type Perspective: Copy + Eq;
 
-    type AbsoluteCoord: Copy + Eq + core::fmt::Debug;
-    type RelativeCoord: Copy + Eq;
+    type AbsoluteCoord: IsAbsoluteCoord + core::fmt::Debug;
+    type RelativeCoord: IsCoord;
 
     type AbsoluteBoard: Clone
         + core::fmt::Debug
+        + PartialEq
+        + Eq
         + IsAbsoluteBoard<PieceWithSide = Self::AbsolutePiece, Coord = Self::AbsoluteCoord>;
     type RelativeBoard: Copy
         + IsBoard<PieceWithSide = Self::RelativePiece, Coord = Self::RelativeCoord>;
 
-    type AbsolutePiece: Copy + Eq + IsPieceWithSide<Side = cetkaik_fundamental::AbsoluteSide>;
-    type RelativePiece: Copy + Eq + IsPieceWithSide<Side = Self::RelativeSide>;
+    /// A piece in absolute terms
+    /// ／絶対的な陣営を持つ駒
+    ///
+    /// `Ord` and `Hash` are required so that generic code can sort and hash pieces, e.g. for move ordering and canonical hands.
+    /// Since `cetkaik_fundamental` does not order colors and professions, comparing [`IsPieceWithSide::to_canonical_u8`] is a convenient way to implement `Ord`.
+    /// ／汎用のコードが駒を整列・ハッシュできるよう（例えば指し手の並べ替えや手駒の正規化のため）、`Ord` と `Hash` を要求する。
+    /// `cetkaik_fundamental` は色と職種に順序を定めないので、`Ord` は [`IsPieceWithSide::to_canonical_u8`] を比較して実装するのが手軽である。
+    type AbsolutePiece: Copy
+        + Ord
+        + core::hash::Hash
+        + IsPieceWithSide<Side = cetkaik_fundamental::AbsoluteSide>;
+    /// A piece in relative terms, which is required to be `Ord` and `Hash` as well
+    /// ／相対的な陣営を持つ駒。こちらも `Ord` と `Hash` を要求する。
+    type RelativePiece: Copy + Ord + core::hash::Hash + IsPieceWithSide<Side = Self::RelativeSide>;
 
     type AbsoluteField: Clone
         + core::fmt::Debug
+        + PartialEq
+        + Eq
         + IsField<
             PieceWithSide = Self::AbsolutePiece,
             Coord = Self::AbsoluteCoord,
@@ -179,6 +1108,14 @@ pub trait CetkaikRepresentation {
         board.peek(coord)
     }
     fn is_tam_hue_by_default(coord: Self::RelativeCoord) -> bool;
+    /// Whether the square at the absolute coordinate `coord` is tam2 hue by default
+    /// ／絶対座標 `coord` のマスが既定で皇処であるかどうか
+    ///
+    /// The squares are symmetric, so the perspective used to look them up does not matter.
+    /// ／これらのマスは対称なので、判定に用いる視点は結果に影響しない。
+    fn is_tam_hue_by_default_absolute(coord: Self::AbsoluteCoord) -> bool {
+        Self::is_tam_hue_by_default(Self::to_relative_coord(coord, Self::get_one_perspective()))
+    }
     fn relative_tam2() -> Self::RelativePiece;
     fn absolute_tam2() -> Self::AbsolutePiece;
     fn is_upward(s: Self::RelativeSide) -> bool;
@@ -224,27 +1161,458 @@ pub trait CetkaikRepresentation {
     fn as_board_absolute(field: &Self::AbsoluteField) -> &Self::AbsoluteBoard;
     fn as_board_mut_absolute(field: &mut Self::AbsoluteField) -> &mut Self::AbsoluteBoard;
     fn as_board_relative(field: &Self::RelativeField) -> &Self::RelativeBoard;
-    fn is_water_relative(c: Self::RelativeCoord) -> bool;
-    fn is_water_absolute(c: Self::AbsoluteCoord) -> bool;
+    fn as_board_mut_relative(field: &mut Self::RelativeField) -> &mut Self::RelativeBoard;
+    #[deprecated = "Use `c.is_water()`"]
+    fn is_water_relative(c: Self::RelativeCoord) -> bool {
+        c.is_water()
+    }
+    #[deprecated = "Use `c.is_water()`"]
+    fn is_water_absolute(c: Self::AbsoluteCoord) -> bool {
+        c.is_water()
+    }
     fn loop_over_one_side_and_tam(
         board: &Self::RelativeBoard,
         side: Self::RelativeSide,
         f_tam_or_piece: &mut dyn FnMut(Self::RelativeCoord, Option<Profession>),
     );
     fn to_relative_field(field: Self::AbsoluteField, p: Self::Perspective) -> Self::RelativeField;
+    fn to_absolute_field(field: Self::RelativeField, p: Self::Perspective) -> Self::AbsoluteField;
+    /// Borrows `field` as seen from `p` without copying it; see [`RelativeView`]
+    /// ／`field` をコピーせずに視点 `p` から見る。[`RelativeView`] を参照。
+    fn to_relative_view(field: &Self::AbsoluteField, p: Self::Perspective) -> RelativeView<'_, Self>
+    where
+        Self: std::marker::Sized,
+    {
+        RelativeView::new(field, p)
+    }
+    /// Mutably borrows `field` as seen from `p` without copying it; see [`RelativeViewMut`]
+    /// ／`field` をコピーせずに視点 `p` から見て、可変で借用する。[`RelativeViewMut`] を参照。
+    fn to_relative_view_mut(
+        field: &mut Self::AbsoluteField,
+        p: Self::Perspective,
+    ) -> RelativeViewMut<'_, Self>
+    where
+        Self: std::marker::Sized,
+    {
+        RelativeViewMut::new(field, p)
+    }
     fn to_relative_side(
         side: cetkaik_fundamental::AbsoluteSide,
         p: Self::Perspective,
     ) -> Self::RelativeSide;
+    fn to_absolute_side(
+        side: Self::RelativeSide,
+        p: Self::Perspective,
+    ) -> cetkaik_fundamental::AbsoluteSide;
+    /// The relative piece that `piece` is when seen from the perspective `p`
+    /// ／視点 `p` から見たとき `piece` がどの相対的な駒であるか
+    fn to_relative_piece(piece: Self::AbsolutePiece, p: Self::Perspective) -> Self::RelativePiece {
+        piece.match_on_piece_and_apply(&Self::relative_tam2, &|color, prof, side| {
+            Self::RelativePiece::non_tam2_piece(color, prof, Self::to_relative_side(side, p))
+        })
+    }
+    /// The inverse of [`CetkaikRepresentation::to_relative_piece`]
+    /// ／[`CetkaikRepresentation::to_relative_piece`] の逆
+    fn to_absolute_piece(piece: Self::RelativePiece, p: Self::Perspective) -> Self::AbsolutePiece {
+        piece.match_on_piece_and_apply(&Self::absolute_tam2, &|color, prof, side| {
+            Self::AbsolutePiece::non_tam2_piece(color, prof, Self::to_absolute_side(side, p))
+        })
+    }
     fn get_one_perspective() -> Self::Perspective;
+    /// The other perspective, i.e. the one held by the opponent
+    /// ／もう一方の視点、すなわち相手から見た視点
+    fn flip_perspective(p: Self::Perspective) -> Self::Perspective;
+    /// Both perspectives, useful for asserting that something is invariant under the change of perspective
+    /// ／両方の視点。視点を変えても結果が変わらないことを確かめるのに便利
+    #[must_use]
+    fn all_perspectives() -> [Self::Perspective; 2] {
+        let p = Self::get_one_perspective();
+        [p, Self::flip_perspective(p)]
+    }
+    /// A hash of a field that agrees across every representation
+    /// ／どの表現でも一致する `Field` のハッシュ値
+    fn canonical_hash(field: &Self::AbsoluteField) -> u64
+    where
+        Self: std::marker::Sized,
+    {
+        repetition::canonical_hash::<Self>(field)
+    }
+    /// A key for detecting repeated positions, which combines [`CetkaikRepresentation::canonical_hash`] with whose turn it is
+    /// ／同一局面の検出に用いるキー。[`CetkaikRepresentation::canonical_hash`] に手番を組み合わせたもの
+    fn repetition_key(
+        field: &Self::AbsoluteField,
+        whose_turn: cetkaik_fundamental::AbsoluteSide,
+    ) -> u64
+    where
+        Self: std::marker::Sized,
+    {
+        repetition::repetition_key::<Self>(field, whose_turn)
+    }
+    /// Reflects an absolute coordinate left to right, exchanging the columns K and P, L and M, and so on
+    /// ／絶対座標を左右反転する。列 K と P、L と M などが入れ替わる。
+    fn mirror_coord(coord: Self::AbsoluteCoord) -> Self::AbsoluteCoord
+    where
+        Self: std::marker::Sized,
+    {
+        symmetry::mirror_coord::<Self>(coord)
+    }
+    /// Reflects a board left to right
+    /// ／盤面を左右反転する
+    fn mirror_board(board: &Self::AbsoluteBoard) -> Self::AbsoluteBoard
+    where
+        Self: std::marker::Sized,
+    {
+        symmetry::mirror_board::<Self>(board)
+    }
+    /// Reflects the board of a field left to right; the hop1zuo1s are left as they are
+    /// ／`Field` の盤面を左右反転する。手駒はそのまま
+    fn mirror_field(field: &Self::AbsoluteField) -> Self::AbsoluteField
+    where
+        Self: std::marker::Sized,
+    {
+        symmetry::mirror_field::<Self>(field)
+    }
+    /// Reflects a move left to right, so that it can be played on the mirrored field
+    /// ／指し手を左右反転し、左右反転した `Field` で指せるようにする
+    fn mirror_move(mv: PureMove<Self>) -> PureMove<Self>
+    where
+        Self: std::marker::Sized,
+    {
+        symmetry::mirror_move::<Self>(mv)
+    }
+    /// Returns whichever of `field` and its mirror image is smaller, and whether it is the mirror image
+    /// ／`field` とその左右反転のうち小さい方と、それが左右反転の方であるかどうかを返す
+    ///
+    /// Boards are compared lexicographically over the squares in the order of canonical indices,
+    /// so the result is the same across representations. Moves found on the result are to be passed
+    /// through [`CetkaikRepresentation::mirror_move`] before being played on `field` if the flag is `true`.
+    /// ／盤面は正準な番号順にマスを並べて辞書式に比較するので、結果は表現によらない。
+    /// フラグが `true` の場合、結果の上で得られた指し手は [`CetkaikRepresentation::mirror_move`] を通してから `field` で指すこと。
+    fn canonicalize_field(field: &Self::AbsoluteField) -> (Self::AbsoluteField, bool)
+    where
+        Self: std::marker::Sized,
+    {
+        symmetry::canonicalize_field::<Self>(field)
+    }
+    /// The initial board of the official (yhuap) rule, built from [`YHUAP_INITIAL_PIECES`] through [`IsAbsoluteBoard::from_piece_iter`]
+    /// ／[`YHUAP_INITIAL_PIECES`] から [`IsAbsoluteBoard::from_piece_iter`] で組み立てた、官定の初期盤面
+    ///
+    /// This agrees with the default of [`IsAbsoluteBoard::yhuap_initial`], which builds the board without going through here.
+    /// ／これは [`IsAbsoluteBoard::yhuap_initial`] の既定の実装と一致する。そちらはここを経由せずに盤面を組み立てる。
+    /// # Panics
+    /// Panics if [`CetkaikRepresentation::index_to_coord`] fails on an index below 81.
+    /// ／[`CetkaikRepresentation::index_to_coord`] が 81 未満の番号に対して失敗するとパニックする。
+    #[must_use]
+    fn yhuap_initial_board() -> Self::AbsoluteBoard
+    where
+        Self: std::marker::Sized,
+    {
+        setup::yhuap_initial_board::<Self>()
+    }
+    /// The initial field of the official (yhuap) rule, whose board is [`CetkaikRepresentation::yhuap_initial_board`]
+    /// ／盤面が [`CetkaikRepresentation::yhuap_initial_board`] である、官定の初期 `Field`
+    ///
+    /// This agrees with the default of [`IsAbsoluteField::yhuap_initial`], which builds the field without going through here.
+    /// ／これは [`IsAbsoluteField::yhuap_initial`] の既定の実装と一致する。そちらはここを経由せずに `Field` を組み立てる。
+    /// # Panics
+    /// Same as [`CetkaikRepresentation::yhuap_initial_board`].
+    /// ／[`CetkaikRepresentation::yhuap_initial_board`] と同じ。
+    #[must_use]
+    fn yhuap_initial_field() -> Self::AbsoluteField
+    where
+        Self: std::marker::Sized,
+    {
+        setup::yhuap_initial_field::<Self>()
+    }
+    /// A field in the initial arrangement `arrangement`, with both hop1zuo1s empty
+    /// ／初期配置 `arrangement` の `Field`。手駒はどちらも空である。
+    /// # Errors
+    /// Fails if the arrangement is not a legal one; see [`initial_with`].
+    /// ／配置が合法でなければ失敗する。[`initial_with`] を参照。
+    fn initial_with(arrangement: &ArrangementSpec) -> Result<Self::AbsoluteField, SetupError>
+    where
+        Self: std::marker::Sized,
+    {
+        setup::initial_with::<Self>(arrangement)
+    }
+    /// The empty squares of `board` in the canonical order, which is the ascending order of [`CetkaikRepresentation::coord_to_index`]
+    /// ／`board` の空きマスを正準な順序、すなわち [`CetkaikRepresentation::coord_to_index`] の昇順に並べたもの
+    fn empty_squares_sorted(board: &Self::AbsoluteBoard) -> Vec<Self::AbsoluteCoord> {
+        let mut squares = board.empty_squares().collect::<Vec<_>>();
+        squares.sort_by_key(|&c| Self::coord_to_index(c));
+        squares
+    }
+    /// Every square, in the order a GUI draws them for the player holding the perspective `p`:
+    /// row by row from the far side of the board, each row from left to right
+    /// ／視点 `p` を持つプレイヤーのために GUI が描く順に並べた全マス。盤の奥の行から一行ずつ、各行は左から右へ
+    ///
+    /// The player whose pieces point upward sits on the side of `IASide`, and so sees the squares in the canonical order,
+    /// from AK to IAP; the other player sees them in the reverse order.
+    /// If `mirror` is `true`, the board is drawn reflected left to right, i.e. each row is drawn from right to left;
+    /// the coordinates themselves stay as they are, and [`CetkaikRepresentation::mirror_coord`] tells which square is drawn where.
+    /// ／駒が上を向くプレイヤーは `IASide` の側に座るので、マスは AK から IAP へ正準な順に見える。もう一方のプレイヤーには逆順に見える。
+    /// `mirror` が `true` なら盤を左右反転して描く、すなわち各行を右から左へ描く。
+    /// 座標そのものは変わらず、どのマスがどこに描かれるかは [`CetkaikRepresentation::mirror_coord`] でわかる。
+    fn coords_in_display_order(
+        p: Self::Perspective,
+        mirror: bool,
+    ) -> DisplayIter<Self::AbsoluteCoord>
+    where
+        Self: std::marker::Sized,
+    {
+        let ia_side_is_down = Self::is_upward(Self::to_relative_side(
+            cetkaik_fundamental::AbsoluteSide::IASide,
+            p,
+        ));
+        (0..SQUARE_COUNT)
+            .map(|i| {
+                if ia_side_is_down {
+                    i
+                } else {
+                    SQUARE_COUNT - 1 - i
+                }
+            })
+            .filter_map(Self::index_to_coord)
+            .map(|c| if mirror { Self::mirror_coord(c) } else { c })
+            .collect()
+    }
+    /// The squares of the row numbered `row` as in [`CetkaikRepresentation::coord_to_index`], from the column K to P;
+    /// nothing if `row` is 9 or more
+    /// ／[`CetkaikRepresentation::coord_to_index`] と同じく番号が `row` である行のマス。列 K から P の順。`row` が 9 以上なら何もない
+    #[must_use]
+    fn coords_in_row(row: u8) -> RegionIter<Self::AbsoluteCoord> {
+        (0..COLS_U8)
+            .filter(|_| row < ROWS_U8)
+            .filter_map(|column| Self::index_to_coord(COLS_U8 * row + column))
+            .collect()
+    }
+    /// The squares of the column numbered `column` as in [`CetkaikRepresentation::coord_to_index`], from the row A to IA;
+    /// nothing if `column` is 9 or more
+    /// ／[`CetkaikRepresentation::coord_to_index`] と同じく番号が `column` である列のマス。行 A から IA の順。`column` が 9 以上なら何もない
+    #[must_use]
+    fn coords_in_column(column: u8) -> RegionIter<Self::AbsoluteCoord> {
+        (0..ROWS_U8)
+            .filter(|_| column < COLS_U8)
+            .filter_map(|row| Self::index_to_coord(COLS_U8 * row + column))
+            .collect()
+    }
+    /// The squares of the rectangle with the opposite corners `a` and `b`, both included, in the canonical order
+    /// ／`a` と `b` を対角の頂点とする長方形（両端を含む）のマス。正準な順に並ぶ。
+    fn coords_in_rect(
+        a: Self::AbsoluteCoord,
+        b: Self::AbsoluteCoord,
+    ) -> RegionIter<Self::AbsoluteCoord> {
+        let (a, b) = (Self::coord_to_index(a), Self::coord_to_index(b));
+        let rows = (a / COLS_U8).min(b / COLS_U8)..=(a / COLS_U8).max(b / COLS_U8);
+        let columns = (a % COLS_U8).min(b % COLS_U8)..=(a % COLS_U8).max(b % COLS_U8);
+        rows.flat_map(|row| columns.clone().map(move |column| COLS_U8 * row + column))
+            .filter_map(Self::index_to_coord)
+            .collect()
+    }
+    /// A stable, human-readable dump of `field`, in the format described at [`FieldDump`]
+    /// ／`field` の安定した人間可読なダンプ。書式は [`FieldDump`] を参照。
+    ///
+    /// Equal positions dump to the same bytes whichever representation holds them, which makes this suitable for snapshot tests.
+    /// ／等しい局面はどの表現で保持されていても同じバイト列になるので、スナップショットテストに適する。
+    fn dump_field(field: &Self::AbsoluteField) -> FieldDump<'_, Self>
+    where
+        Self: std::marker::Sized,
+    {
+        FieldDump::new(field)
+    }
+    /// The canonical index of an absolute coordinate, which is `9 * row + column`,
+    /// where the rows A, E, I, U, O, Y, AI, AU, IA and the columns K, L, N, T, Z, X, C, M, P are each numbered from 0 to 8
+    /// ／絶対座標の正準な番号。行 A, E, I, U, O, Y, AI, AU, IA と列 K, L, N, T, Z, X, C, M, P にそれぞれ 0 から 8 の番号を振ったとき、`9 * 行 + 列` である
+    fn coord_to_index(coord: Self::AbsoluteCoord) -> u8;
+    /// The inverse of [`CetkaikRepresentation::coord_to_index`]; `None` if `index` is 81 or more
+    /// ／[`CetkaikRepresentation::coord_to_index`] の逆。`index` が 81 以上なら `None`
+    ///
+    /// The default calls [`IsAbsoluteCoord::from_canonical_index`].
+    /// ／既定の実装は [`IsAbsoluteCoord::from_canonical_index`] を呼ぶ。
+    #[must_use]
+    fn index_to_coord(index: u8) -> Option<Self::AbsoluteCoord> {
+        Self::AbsoluteCoord::from_canonical_index(index)
+    }
+    /// The canonical `u8` encoding of an absolute coordinate, shared by every representation
+    /// ／絶対座標の正準な `u8` 符号。すべての表現で共通である。
+    ///
+    /// This is the canonical index of [`CetkaikRepresentation::coord_to_index`] under a name meant for wire formats, tablebases and hashes.
+    /// ／[`CetkaikRepresentation::coord_to_index`] の正準な番号を、通信形式・テーブルベース・ハッシュ向けの名前で提供するもの。
+    #[must_use]
+    fn to_canonical_u8(coord: Self::AbsoluteCoord) -> u8 {
+        Self::coord_to_index(coord)
+    }
+    /// The inverse of [`CetkaikRepresentation::to_canonical_u8`]; `None` if `code` is 81 or more
+    /// ／[`CetkaikRepresentation::to_canonical_u8`] の逆。`code` が 81 以上なら `None`
+    #[must_use]
+    fn from_canonical_u8(code: u8) -> Option<Self::AbsoluteCoord> {
+        Self::index_to_coord(code)
+    }
+    /// The distance between `a` and `b` as the sticks measure it, which is the Chebyshev distance
+    /// ／投げ棒判定で測る `a` と `b` の距離。チェビシェフ距離である。
+    ///
+    /// An implementation must agree with [`CetkaikRepresentation::chebyshev_distance_absolute`].
+    /// ／実装は [`CetkaikRepresentation::chebyshev_distance_absolute`] と一致しなければならない。
     fn absolute_distance(a: Self::AbsoluteCoord, b: Self::AbsoluteCoord) -> i32;
+    /// The Chebyshev distance between `a` and `b` in absolute coordinates, i.e. the larger of the differences of their rows and of their columns
+    /// ／絶対座標における `a` と `b` のチェビシェフ距離。すなわち行の差と列の差の大きい方
+    ///
+    /// This is the number of king steps from one to the other.
+    /// ／一方から他方へ、八方向に一マスずつ進んだときの手数である。
+    #[must_use]
+    fn chebyshev_distance_absolute(a: Self::AbsoluteCoord, b: Self::AbsoluteCoord) -> i32
+    where
+        Self: std::marker::Sized,
+    {
+        let [dr, dc] = direction::delta_absolute::<Self>(a, b);
+        dr.abs().max(dc.abs())
+    }
+    /// The Manhattan distance between `a` and `b` in absolute coordinates, i.e. the sum of the differences of their rows and of their columns
+    /// ／絶対座標における `a` と `b` のマンハッタン距離。すなわち行の差と列の差の和
+    #[must_use]
+    fn manhattan_distance_absolute(a: Self::AbsoluteCoord, b: Self::AbsoluteCoord) -> i32
+    where
+        Self: std::marker::Sized,
+    {
+        let [dr, dc] = direction::delta_absolute::<Self>(a, b);
+        dr.abs() + dc.abs()
+    }
+    /// Whether `a` and `b` are adjacent in absolute coordinates, i.e. one of the eight neighbors of the other; a square is not adjacent to itself
+    /// ／絶対座標において `a` と `b` が隣接している、すなわち一方が他方の八近傍のひとつであるかどうか。マスは自身とは隣接しない。
+    #[must_use]
+    fn is_adjacent_absolute(a: Self::AbsoluteCoord, b: Self::AbsoluteCoord) -> bool
+    where
+        Self: std::marker::Sized,
+    {
+        Self::chebyshev_distance_absolute(a, b) == 1
+    }
+    /// The Chebyshev distance between `a` and `b` in relative coordinates, which equals that between the corresponding absolute coordinates
+    /// ／相対座標における `a` と `b` のチェビシェフ距離。対応する絶対座標どうしのものと等しい。
+    ///
+    /// The default tries every offset with [`CetkaikRepresentation::add_delta`]; a representation that knows the rows and columns of its relative coordinates can override it.
+    /// ／既定の実装はあらゆるずれを [`CetkaikRepresentation::add_delta`] で試す。相対座標の行と列を知っている表現はこれを上書きできる。
+    #[must_use]
+    fn chebyshev_distance_relative(a: Self::RelativeCoord, b: Self::RelativeCoord) -> i32
+    where
+        Self: std::marker::Sized,
+    {
+        let [dr, dc] = direction::delta_relative::<Self>(a, b);
+        dr.abs().max(dc.abs())
+    }
+    /// The Manhattan distance between `a` and `b` in relative coordinates, which equals that between the corresponding absolute coordinates
+    /// ／相対座標における `a` と `b` のマンハッタン距離。対応する絶対座標どうしのものと等しい。
+    #[must_use]
+    fn manhattan_distance_relative(a: Self::RelativeCoord, b: Self::RelativeCoord) -> i32
+    where
+        Self: std::marker::Sized,
+    {
+        let [dr, dc] = direction::delta_relative::<Self>(a, b);
+        dr.abs() + dc.abs()
+    }
+    /// Whether `a` and `b` are adjacent in relative coordinates, i.e. one of the eight neighbors of the other; a square is not adjacent to itself
+    /// ／相対座標において `a` と `b` が隣接している、すなわち一方が他方の八近傍のひとつであるかどうか。マスは自身とは隣接しない。
+    #[must_use]
+    fn is_adjacent_relative(a: Self::RelativeCoord, b: Self::RelativeCoord) -> bool
+    where
+        Self: std::marker::Sized,
+    {
+        Self::chebyshev_distance_relative(a, b) == 1
+    }
     fn absolute_same_direction(
         origin: Self::AbsoluteCoord,
         a: Self::AbsoluteCoord,
         b: Self::AbsoluteCoord,
     ) -> bool;
+    /// The absolute coordinate reached by moving `n` squares from `coord` in the direction `dir`, or `None` if it is off the board
+    /// ／`coord` から方向 `dir` に `n` マス進んだ絶対座標。盤外なら `None`
+    fn step_absolute(
+        coord: Self::AbsoluteCoord,
+        dir: Direction,
+        n: u8,
+    ) -> Option<Self::AbsoluteCoord>
+    where
+        Self: std::marker::Sized,
+    {
+        direction::step_absolute::<Self>(coord, dir, n)
+    }
+    /// The relative coordinate reached by moving `n` squares from `coord` in the direction `dir`, or `None` if it is off the board
+    /// ／`coord` から方向 `dir` に `n` マス進んだ相対座標。盤外なら `None`
+    fn step_relative(
+        coord: Self::RelativeCoord,
+        dir: Direction,
+        n: u8,
+    ) -> Option<Self::RelativeCoord>
+    where
+        Self: std::marker::Sized,
+    {
+        direction::step_relative::<Self>(coord, dir, n)
+    }
+    /// The direction from `a` to `b` in absolute coordinates, or `None` if they are equal or not on a common row, column or diagonal
+    /// ／絶対座標における `a` から `b` への方向。両者が等しいか、同じ行・列・斜めの線上になければ `None`
+    fn direction_between_absolute(
+        a: Self::AbsoluteCoord,
+        b: Self::AbsoluteCoord,
+    ) -> Option<Direction>
+    where
+        Self: std::marker::Sized,
+    {
+        direction::direction_between_absolute::<Self>(a, b)
+    }
+    /// The direction from `a` to `b` in relative coordinates, or `None` if they are equal or not on a common row, column or diagonal
+    /// ／相対座標における `a` から `b` への方向。両者が等しいか、同じ行・列・斜めの線上になければ `None`
+    fn direction_between_relative(
+        a: Self::RelativeCoord,
+        b: Self::RelativeCoord,
+    ) -> Option<Direction>
+    where
+        Self: std::marker::Sized,
+    {
+        direction::direction_between_relative::<Self>(a, b)
+    }
     #[deprecated = "Use `piece.has_prof(prof)`"]
     fn has_prof_absolute(piece: Self::AbsolutePiece, prof: Profession) -> bool {
         piece.has_prof(prof)
     }
 }
+
+/// A marker trait for representations whose boards, fields, pieces and coordinates can all be shared across threads
+/// ／盤面・`Field`・駒・座標のすべてをスレッド間で共有できる表現であることを表すマーカートレイト
+///
+/// This is implemented automatically for every such representation,
+/// so that parallel search can say `R: ThreadSafeRepresentation` instead of repeating the bounds on each associated type.
+/// ／条件を満たす表現には自動で実装されるので、並列探索では各関連型に境界を書き連ねる代わりに `R: ThreadSafeRepresentation` と書けばよい。
+pub trait ThreadSafeRepresentation:
+    CetkaikRepresentation<
+        Perspective: Send + Sync + 'static,
+        AbsoluteCoord: Send + Sync + 'static,
+        RelativeCoord: Send + Sync + 'static,
+        AbsoluteBoard: Send + Sync + 'static,
+        RelativeBoard: Send + Sync + 'static,
+        AbsolutePiece: Send + Sync + 'static,
+        RelativePiece: Send + Sync + 'static,
+        AbsoluteField: Send + Sync + 'static,
+        RelativeField: Send + Sync + 'static,
+        RelativeSide: Send + Sync + 'static,
+    > + Send
+    + Sync
+    + 'static
+{
+}
+
+impl<R> ThreadSafeRepresentation for R where
+    R: CetkaikRepresentation<
+            Perspective: Send + Sync + 'static,
+            AbsoluteCoord: Send + Sync + 'static,
+            RelativeCoord: Send + Sync + 'static,
+            AbsoluteBoard: Send + Sync + 'static,
+            RelativeBoard: Send + Sync + 'static,
+            AbsolutePiece: Send + Sync + 'static,
+            RelativePiece: Send + Sync + 'static,
+            AbsoluteField: Send + Sync + 'static,
+            RelativeField: Send + Sync + 'static,
+            RelativeSide: Send + Sync + 'static,
+        > + Send
+        + Sync
+        + 'static
+{
+}
@@ -0,0 +1,146 @@
+use crate::dynamic::{kind_from_code, prof_from_index, prof_index};
+use crate::{
+    CetkaikRepresentation, IsAbsoluteField, IsBoard, IsPieceWithSide, YHUAP_INITIAL_PIECES,
+};
+use cetkaik_fundamental::{AbsoluteSide, Color, ColorAndProf, Profession};
+
+/// The widths, in the order Nuak1, Kauk2, Gua2, Kaun1, Dau2, Maun1, Kua2, Tuk2, Uai1, Io, of the bit fields in [`MaterialCount::signature`]
+const SIGNATURE_WIDTHS: [u32; 10] = [2, 5, 3, 3, 3, 3, 3, 3, 3, 2];
+
+/// How many pieces of each profession a side has, counting both the board and its hop1zuo1
+/// ／ある陣営が持つ各職種の駒の数。盤上と手駒の両方を数える。
+///
+/// The color of the pieces is not distinguished.
+/// ／駒の色は区別しない。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MaterialCount {
+    counts: [u8; 10],
+}
+
+impl MaterialCount {
+    /// Builds from the counts indexed in the order of [`SIGNATURE_WIDTHS`]
+    pub(crate) const fn from_counts(counts: [u8; 10]) -> Self {
+        Self { counts }
+    }
+
+    /// The number of pieces of the profession `prof`
+    /// ／職種 `prof` の駒の数
+    #[must_use]
+    pub const fn count(&self, prof: Profession) -> u8 {
+        self.counts[prof_index(prof) as usize]
+    }
+
+    /// The number of pieces of all professions
+    /// ／全職種の駒の数
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.counts.iter().map(|&c| usize::from(c)).sum()
+    }
+
+    /// Packs the counts into 27 bits, from Nuak1 in the lowest bits to Io in the highest
+    /// ／各数を 27 ビットに詰める。最下位の船から最上位の王の順に並ぶ。
+    ///
+    /// Kauk2 takes 5 bits, Nuak1 and Io take 2 bits, and the others take 3 bits,
+    /// which is enough for any set of pieces that passes [`IsAbsoluteField::validate`].
+    /// Larger counts are saturated.
+    /// ／兵は 5 ビット、船と王は 2 ビット、その他は 3 ビットを占める。
+    /// これは [`IsAbsoluteField::validate`] を通るどの駒の組にも十分である。これより大きな数は飽和させる。
+    #[must_use]
+    pub fn signature(&self) -> u32 {
+        let mut signature = 0;
+        let mut shift = 0;
+        for (&count, width) in self.counts.iter().zip(SIGNATURE_WIDTHS) {
+            let max = (1 << width) - 1;
+            signature |= u32::from(count).min(max) << shift;
+            shift += width;
+        }
+        signature
+    }
+
+    /// The sum of [`base_value`] over the counted pieces
+    /// ／数えた駒の [`base_value`] の和
+    #[must_use]
+    pub fn value(&self) -> i32 {
+        (0..10)
+            .filter_map(prof_from_index)
+            .map(|prof| i32::from(self.count(prof)) * base_value(prof))
+            .sum()
+    }
+}
+
+/// Counts the pieces of `side` on the board and in its hop1zuo1
+/// ／`side` の駒を盤上と手駒から数える
+pub fn material_count<F>(field: &F, side: AbsoluteSide) -> MaterialCount
+where
+    F: IsAbsoluteField,
+    F::PieceWithSide: IsPieceWithSide<Side = AbsoluteSide>,
+{
+    let mut material = MaterialCount::default();
+    let board = field.as_board();
+    for c in board.occupied_squares() {
+        if let Some(piece) = board.peek(c) {
+            if let Some(prof) =
+                piece.match_on_piece_and_apply(&|| None, &|_, prof, s| (s == side).then_some(prof))
+            {
+                material.counts[usize::from(prof_index(prof))] += 1;
+            }
+        }
+    }
+    for piece in field.hop1zuo1_of(side) {
+        material.counts[usize::from(prof_index(piece.prof))] += 1;
+    }
+    material
+}
+
+/// A baseline value of a piece of the profession `prof`, in hundredths of a Kauk2
+/// ／職種 `prof` の駒の基準となる価値。兵を 100 とする。
+///
+/// This is a rough table meant as a starting point for simple engines;
+/// an engine with its own evaluation should override [`IsAbsoluteField::material_eval`] instead of relying on it.
+/// ／単純なエンジンの出発点とするための大まかな表である。独自の評価を持つエンジンは、これに頼らず [`IsAbsoluteField::material_eval`] を上書きすること。
+#[must_use]
+pub const fn base_value(prof: Profession) -> i32 {
+    match prof {
+        Profession::Kauk2 => 100,
+        Profession::Nuak1 | Profession::Kaun1 => 300,
+        Profession::Dau2 | Profession::Maun1 => 350,
+        Profession::Uai1 => 400,
+        Profession::Gua2 | Profession::Kua2 | Profession::Tuk2 => 500,
+        Profession::Io => 1000,
+    }
+}
+
+/// The material of `side` minus that of its opponent, valued by [`base_value`]
+/// ／[`base_value`] で評価した、`side` の駒の価値から相手の駒の価値を引いたもの
+pub fn material_eval<F>(field: &F, side: AbsoluteSide) -> i32
+where
+    F: IsAbsoluteField,
+    F::PieceWithSide: IsPieceWithSide<Side = AbsoluteSide>,
+{
+    material_count(field, side).value() - material_count(field, !side).value()
+}
+
+/// The 20 kinds of pieces other than Tam2, as pairs of a color and a profession
+/// ／皇以外の 20 種類の駒。色と職種の組で表す。
+///
+/// They come by color (Kok1 first) and then by profession in the order Nuak1, Kauk2, Gua2, Kaun1, Dau2, Maun1, Kua2, Tuk2, Uai1, Io,
+/// which is the order hop1zuo1s are sorted in elsewhere in this crate.
+/// ／色（赤が先）、次に職種を船・兵・弓・車・虎・馬・筆・巫・将・王の順に並ぶ。これはこのクレートの他の箇所で手駒を整列させる順序と同じである。
+pub fn all_piece_kinds() -> impl Iterator<Item = (Color, Profession)> {
+    (0..20)
+        .filter_map(kind_from_code)
+        .map(|ColorAndProf { color, prof }| (color, prof))
+}
+
+/// The full set of 49 pieces, each with the side it starts on
+/// ／49 枚の駒一式。それぞれ最初に属する陣営付き
+///
+/// Tam2 comes first, followed by the 48 pieces of [`YHUAP_INITIAL_PIECES`] in the order of the squares they start on.
+/// ／最初に皇、続いて [`YHUAP_INITIAL_PIECES`] の 48 枚が初期位置のマスの順に並ぶ。
+pub fn full_piece_set<R: CetkaikRepresentation>() -> impl Iterator<Item = R::AbsolutePiece> {
+    core::iter::once(R::absolute_tam2()).chain(
+        YHUAP_INITIAL_PIECES
+            .iter()
+            .map(|&(_, color, prof, side)| R::AbsolutePiece::non_tam2_piece(color, prof, side)),
+    )
+}
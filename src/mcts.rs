@@ -0,0 +1,397 @@
+use crate::movegen::apply_move_in_place;
+use crate::{CetkaikRepresentation, FieldPool, IsMoveGenerator, PureMove};
+use cetkaik_fundamental::AbsoluteSide;
+use core::ops::Range;
+
+/// The index of a node in a [`MctsTree`]
+/// ／[`MctsTree`] の節点の番号
+pub type NodeId = u32;
+
+/// A node of a [`MctsTree`]: the move leading to it, its place in the tree and its statistics
+/// ／[`MctsTree`] の節点。そこに至る指し手、木の中での位置、統計からなる。
+///
+/// The reward is counted from the point of view of the side that made the move leading to the node.
+/// ／報酬は、その節点に至る手を指した陣営から見たものである。
+#[derive(Clone, Debug)]
+pub struct MctsNode<R: CetkaikRepresentation> {
+    mv: Option<PureMove<R>>,
+    parent: Option<NodeId>,
+    first_child: NodeId,
+    child_count: u32,
+    expanded: bool,
+    visits: u32,
+    total_reward: f64,
+}
+
+impl<R: CetkaikRepresentation> MctsNode<R> {
+    const fn new(mv: Option<PureMove<R>>, parent: Option<NodeId>) -> Self {
+        Self {
+            mv,
+            parent,
+            first_child: 0,
+            child_count: 0,
+            expanded: false,
+            visits: 0,
+            total_reward: 0.0,
+        }
+    }
+
+    /// The move leading to the node, or `None` for the root
+    /// ／節点に至る指し手。根では `None`
+    #[must_use]
+    pub const fn mv(&self) -> Option<PureMove<R>> {
+        self.mv
+    }
+
+    /// The parent of the node, or `None` for the root
+    /// ／節点の親。根では `None`
+    #[must_use]
+    pub const fn parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+
+    /// The children of the node, which are stored next to each other
+    /// ／節点の子。隣り合って格納されている。
+    #[must_use]
+    pub const fn children(&self) -> Range<NodeId> {
+        self.first_child..self.first_child + self.child_count
+    }
+
+    /// Whether the node has been expanded; an expanded node without children is one where the side to move has no move
+    /// ／節点が展開済みかどうか。子のない展開済みの節点は、手番の陣営が指せる手のない節点である。
+    #[must_use]
+    pub const fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    /// The number of playouts that went through the node
+    /// ／節点を通ったプレイアウトの数
+    #[must_use]
+    pub const fn visits(&self) -> u32 {
+        self.visits
+    }
+
+    /// The mean reward of the playouts that went through the node, or 0 if there is none
+    /// ／節点を通ったプレイアウトの報酬の平均。ひとつもなければ 0
+    #[must_use]
+    pub fn mean_reward(&self) -> f64 {
+        if self.visits == 0 {
+            0.0
+        } else {
+            self.total_reward / f64::from(self.visits)
+        }
+    }
+}
+
+/// A tree for Monte-Carlo tree search, whose nodes live in one arena and refer to each other by indices
+/// ／モンテカルロ木探索の木。節点はひとつのアリーナに置かれ、互いを番号で参照する。
+///
+/// Only the moves are stored; the field of a node is rebuilt by replaying them from the root, so a node costs a few dozen bytes whatever the representation.
+/// [`MctsTree::reset`] starts a new tree while keeping the arena and the other buffers, so a tree can be reused from move to move without allocating.
+/// Moves are applied with [`apply_move_in_place`] under the configuration of the rules, so every stick cast is assumed to succeed.
+/// The `_with_pool` methods replay on a field taken from a [`FieldPool`], so that an iteration need not allocate a field at all.
+/// ／格納するのは指し手だけであり、節点の `Field` は根から指し手を再生して組み立て直すので、節点ひとつの大きさは表現によらず数十バイトで済む。
+/// [`MctsTree::reset`] はアリーナなどのバッファを保ったまま新しい木を始めるので、手ごとに確保し直すことなく木を使い回せる。
+/// `_with_pool` の付くメソッドは [`FieldPool`] から取り出した `Field` の上で再生するので、反復ごとに `Field` を確保せずに済む。
+/// 指し手は規則の設定のもとで [`apply_move_in_place`] により適用されるので、投げ棒判定はすべて成功するものとする。
+#[derive(Clone, Debug)]
+pub struct MctsTree<R: CetkaikRepresentation> {
+    root_field: R::AbsoluteField,
+    root_side: AbsoluteSide,
+    nodes: Vec<MctsNode<R>>,
+    path: Vec<PureMove<R>>,
+}
+
+impl<R: CetkaikRepresentation> MctsTree<R> {
+    /// The root of every tree
+    /// ／すべての木の根
+    pub const ROOT: NodeId = 0;
+
+    /// A tree consisting only of the root, which is `field` with `whose_turn` to move
+    /// ／根のみからなる木。根は `whose_turn` が手番の `field` である。
+    pub fn new(field: R::AbsoluteField, whose_turn: AbsoluteSide) -> Self {
+        Self {
+            root_field: field,
+            root_side: whose_turn,
+            nodes: vec![MctsNode::new(None, None)],
+            path: vec![],
+        }
+    }
+
+    /// Discards every node and starts again from `field` with `whose_turn` to move, keeping the buffers
+    /// ／すべての節点を捨て、バッファを保ったまま `whose_turn` が手番の `field` から始め直す
+    pub fn reset(&mut self, field: R::AbsoluteField, whose_turn: AbsoluteSide) {
+        self.root_field = field;
+        self.root_side = whose_turn;
+        self.nodes.clear();
+        self.nodes.push(MctsNode::new(None, None));
+    }
+
+    /// The field at the root
+    /// ／根の `Field`
+    pub const fn root_field(&self) -> &R::AbsoluteField {
+        &self.root_field
+    }
+
+    /// The side to move at the root
+    /// ／根の手番の陣営
+    pub const fn root_side(&self) -> AbsoluteSide {
+        self.root_side
+    }
+
+    /// The number of nodes, including the root
+    /// ／根を含む節点の数
+    pub const fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// The node `id`
+    /// ／節点 `id`
+    ///
+    /// # Panics
+    /// Panics if there is no such node.
+    /// ／そのような節点がなければパニックする。
+    pub fn node(&self, id: NodeId) -> &MctsNode<R> {
+        &self.nodes[id as usize]
+    }
+
+    /// The field at the node `id` and the side to move there, obtained by replaying the moves from the root
+    /// ／根から指し手を再生して得られる、節点 `id` の `Field` とその手番の陣営
+    ///
+    /// # Errors
+    /// Returns the error of [`apply_move_in_place`] if a move cannot be applied.
+    /// ／指し手が適用できなければ [`apply_move_in_place`] のエラーを返す。
+    pub fn field_at<G: IsMoveGenerator<R>>(
+        &mut self,
+        rules: &G,
+        id: NodeId,
+    ) -> Result<(R::AbsoluteField, AbsoluteSide), &'static str> {
+        self.field_at_with_pool(rules, id, &mut FieldPool::new())
+    }
+
+    /// The field at the node `id` and the side to move there, as in [`MctsTree::field_at`], replayed on a field taken from `pool`
+    /// ／[`MctsTree::field_at`] と同様に節点 `id` の `Field` とその手番の陣営を返す。指し手は `pool` から取り出した `Field` の上で再生する。
+    ///
+    /// Moves are applied with [`apply_move_in_place`]; the field may be released to `pool` once the caller is done with it.
+    /// ／指し手は [`apply_move_in_place`] により適用される。呼び出し側は使い終わった `Field` を `pool` に返却してよい。
+    ///
+    /// # Errors
+    /// Returns the error of [`apply_move_in_place`] if a move cannot be applied, in which case the field is released to `pool`.
+    /// ／指し手が適用できなければ [`apply_move_in_place`] のエラーを返す。その場合 `Field` は `pool` に返却される。
+    pub fn field_at_with_pool<G: IsMoveGenerator<R>>(
+        &mut self,
+        rules: &G,
+        id: NodeId,
+        pool: &mut FieldPool<R::AbsoluteField>,
+    ) -> Result<(R::AbsoluteField, AbsoluteSide), &'static str> {
+        self.path.clear();
+        let mut current = id;
+        while let Some(mv) = self.node(current).mv {
+            self.path.push(mv);
+            current = self.node(current).parent.unwrap_or(Self::ROOT);
+        }
+        let config = rules.config();
+        let mut field = pool.acquire_copy_of(&self.root_field);
+        let mut whose_turn = self.root_side;
+        for &mv in self.path.iter().rev() {
+            if let Err(e) = apply_move_in_place::<R>(&mut field, mv, whose_turn, &config) {
+                pool.release(field);
+                return Err(e);
+            }
+            whose_turn = !whose_turn;
+        }
+        Ok((field, whose_turn))
+    }
+
+    /// Adds a child to the node `id`, which is `field` with `whose_turn` to move, for each move available there, and returns the children
+    /// ／`whose_turn` が手番の `field` である節点 `id` に、そこで指せる手ごとに子を加え、その子を返す
+    ///
+    /// A node already expanded is left as it is.
+    /// ／既に展開済みの節点はそのままにする。
+    ///
+    /// # Panics
+    /// Panics if there is no such node, or if the tree would have more than `u32::MAX` nodes.
+    /// ／そのような節点がないか、木の節点の数が `u32::MAX` を超える場合にパニックする。
+    pub fn expand<G: IsMoveGenerator<R>>(
+        &mut self,
+        rules: &G,
+        id: NodeId,
+        field: &R::AbsoluteField,
+        whose_turn: AbsoluteSide,
+    ) -> Range<NodeId> {
+        if self.node(id).expanded {
+            return self.node(id).children();
+        }
+        let moves = rules.all_moves(field, whose_turn);
+        let first_child = NodeId::try_from(self.nodes.len()).expect("too many nodes");
+        let child_count = u32::try_from(moves.len()).expect("too many nodes");
+        self.nodes
+            .extend(moves.iter().map(|&mv| MctsNode::new(Some(mv), Some(id))));
+        let node = &mut self.nodes[id as usize];
+        node.first_child = first_child;
+        node.child_count = child_count;
+        node.expanded = true;
+        node.children()
+    }
+
+    /// The child of the node `id` with the greatest UCT value, with `exploration` as the exploration constant; a child never visited comes first
+    /// ／探索定数を `exploration` としたときに UCT 値が最大となる節点 `id` の子。一度も訪れていない子が優先される。
+    ///
+    /// Returns `None` if the node has no children.
+    /// ／節点に子がなければ `None` を返す。
+    pub fn select_child(&self, id: NodeId, exploration: f64) -> Option<NodeId> {
+        let log_visits = f64::from(self.node(id).visits.max(1)).ln();
+        let uct = |child: NodeId| {
+            let child = self.node(child);
+            if child.visits == 0 {
+                f64::INFINITY
+            } else {
+                exploration.mul_add(
+                    (log_visits / f64::from(child.visits)).sqrt(),
+                    child.mean_reward(),
+                )
+            }
+        };
+        self.node(id)
+            .children()
+            .max_by(|&a, &b| uct(a).total_cmp(&uct(b)).then(b.cmp(&a)))
+    }
+
+    /// Descends from the root by [`MctsTree::select_child`] until it reaches a node not expanded or without children
+    /// ／[`MctsTree::select_child`] によって根から、展開されていないか子のない節点に達するまで下る
+    pub fn select(&self, exploration: f64) -> NodeId {
+        let mut id = Self::ROOT;
+        while let Some(child) = self.select_child(id, exploration) {
+            id = child;
+        }
+        id
+    }
+
+    /// Adds a playout with `reward` to the node `id` and its ancestors
+    /// ／節点 `id` とその祖先に、報酬が `reward` であるプレイアウトを加える
+    ///
+    /// `reward`, between 0 and 1, is from the point of view of the side that made the move leading to the node `id`;
+    /// it is turned into `1 - reward` at each step up, since the sides alternate.
+    /// ／`reward` は 0 以上 1 以下で、節点 `id` に至る手を指した陣営から見たものである。陣営は交互に入れ替わるので、一段上るごとに `1 - reward` とする。
+    pub fn backprop(&mut self, id: NodeId, mut reward: f64) {
+        let mut current = Some(id);
+        while let Some(id) = current {
+            let node = &mut self.nodes[id as usize];
+            node.visits += 1;
+            node.total_reward += reward;
+            reward = 1.0 - reward;
+            current = node.parent;
+        }
+    }
+
+    /// Runs one iteration of selection, expansion, simulation and backpropagation
+    /// ／選択・展開・シミュレーション・逆伝播を一回行う
+    ///
+    /// The node selected by [`MctsTree::select`] is expanded, and unless it has no move, one of its children is picked by `choose`, as in [`play_out`](crate::play_out).
+    /// From there, at most `max_plies` moves are played as by [`play_out`](crate::play_out) with `choose`,
+    /// and `reward` scores the resulting field for the side to move there, between 0 and 1.
+    /// Returns the node the playout started from.
+    /// ／[`MctsTree::select`] で選んだ節点を展開し、指せる手があれば、[`play_out`](crate::play_out) と同様に `choose` でその子をひとつ選ぶ。
+    /// そこから `choose` を用いて [`play_out`](crate::play_out) で最大 `max_plies` 手を指し、得られた `Field` をそこでの手番の陣営から見て `reward` で 0 以上 1 以下に評価する。
+    /// プレイアウトを始めた節点を返す。
+    ///
+    /// # Errors
+    /// Returns the error of [`apply_move_in_place`] if a move in the tree cannot be applied.
+    /// ／木の中の指し手が適用できなければ [`apply_move_in_place`] のエラーを返す。
+    ///
+    /// # Panics
+    /// Panics if `choose` returns an index out of range, or if the tree would have more than `u32::MAX` nodes.
+    /// ／`choose` が範囲外の番号を返すか、木の節点の数が `u32::MAX` を超える場合にパニックする。
+    pub fn iterate<G: IsMoveGenerator<R>>(
+        &mut self,
+        rules: &G,
+        exploration: f64,
+        max_plies: usize,
+        choose: impl FnMut(usize) -> usize,
+        reward: impl FnOnce(&R::AbsoluteField, AbsoluteSide) -> f64,
+    ) -> Result<NodeId, &'static str> {
+        self.iterate_with_pool(
+            rules,
+            exploration,
+            max_plies,
+            choose,
+            reward,
+            &mut FieldPool::new(),
+        )
+    }
+
+    /// Runs one iteration in the manner of [`MctsTree::iterate`], replaying and playing out on a single field taken from `pool`
+    /// ／[`MctsTree::iterate`] と同様に一回の反復を行う。再生とプレイアウトは `pool` から取り出したひとつの `Field` の上で行う。
+    ///
+    /// Every move is applied in place with [`apply_move_in_place`], and the field is released to `pool` at the end,
+    /// so that the iterations after the first allocate no field.
+    /// ／すべての指し手は [`apply_move_in_place`] によりその場で適用され、`Field` は最後に `pool` に返却されるので、
+    /// 二回目以降の反復では `Field` を確保しない。
+    ///
+    /// # Errors
+    /// Returns the error of [`apply_move_in_place`] if a move in the tree cannot be applied.
+    /// ／木の中の指し手が適用できなければ [`apply_move_in_place`] のエラーを返す。
+    ///
+    /// # Panics
+    /// Panics if `choose` returns an index out of range, or if the tree would have more than `u32::MAX` nodes.
+    /// ／`choose` が範囲外の番号を返すか、木の節点の数が `u32::MAX` を超える場合にパニックする。
+    pub fn iterate_with_pool<G: IsMoveGenerator<R>>(
+        &mut self,
+        rules: &G,
+        exploration: f64,
+        max_plies: usize,
+        mut choose: impl FnMut(usize) -> usize,
+        reward: impl FnOnce(&R::AbsoluteField, AbsoluteSide) -> f64,
+        pool: &mut FieldPool<R::AbsoluteField>,
+    ) -> Result<NodeId, &'static str> {
+        let config = rules.config();
+        let mut id = self.select(exploration);
+        let (mut field, mut whose_turn) = self.field_at_with_pool(rules, id, pool)?;
+        let children = self.expand(rules, id, &field, whose_turn);
+        if !children.is_empty() {
+            let index = choose(children.len());
+            assert!(index < children.len(), "index out of range");
+            id = children.start + u32::try_from(index).expect("too many nodes");
+            let mv = self.node(id).mv.expect("a child has a move");
+            if let Err(e) = apply_move_in_place::<R>(&mut field, mv, whose_turn, &config) {
+                pool.release(field);
+                return Err(e);
+            }
+            whose_turn = !whose_turn;
+        }
+        // the playout of `play_out`, on `field` itself
+        let mut end_side = whose_turn;
+        for _ in 0..max_plies {
+            let moves = rules.all_moves(&field, end_side);
+            if moves.is_empty() {
+                break;
+            }
+            let mv = moves[choose(moves.len())];
+            if apply_move_in_place::<R>(&mut field, mv, end_side, &config).is_err() {
+                break;
+            }
+            end_side = !end_side;
+        }
+        let end_reward = reward(&field, end_side);
+        pool.release(field);
+        // the side that made the move leading to `id` is the opponent of `whose_turn`
+        self.backprop(
+            id,
+            if end_side == whose_turn {
+                1.0 - end_reward
+            } else {
+                end_reward
+            },
+        );
+        Ok(id)
+    }
+
+    /// The move from the root visited most often, or `None` if the root has no children
+    /// ／根からの手のうち最も多く訪れたもの。根に子がなければ `None`
+    pub fn best_move(&self) -> Option<PureMove<R>> {
+        self.node(Self::ROOT)
+            .children()
+            .max_by_key(|&child| (self.node(child).visits, core::cmp::Reverse(child)))
+            .and_then(|child| self.node(child).mv)
+    }
+}
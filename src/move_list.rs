@@ -0,0 +1,190 @@
+use crate::repetition::mix;
+use core::mem::MaybeUninit;
+
+/// A trait that signifies that you can use it as a buffer into which moves are generated
+/// ／指し手の生成先のバッファとして扱える型を表すトレイト
+pub trait MoveList<M> {
+    /// Appends a move
+    /// ／指し手を末尾に追加する
+    fn push(&mut self, mv: M);
+
+    /// Removes every move
+    /// ／指し手をすべて取り除く
+    fn clear(&mut self);
+
+    /// The moves held
+    /// ／保持している指し手
+    fn as_slice(&self) -> &[M];
+
+    /// The moves held, mutably
+    /// ／保持している指し手への可変参照
+    fn as_mut_slice(&mut self) -> &mut [M];
+
+    /// The number of moves held
+    /// ／保持している指し手の数
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    /// Whether no move is held
+    /// ／指し手をひとつも保持していないかどうか
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over the moves held
+    /// ／保持している指し手を列挙する
+    fn iter(&self) -> core::slice::Iter<'_, M> {
+        self.as_slice().iter()
+    }
+
+    /// Sorts the moves held by a key, e.g. to try promising moves first
+    /// ／キーによって指し手を並べ替える。有望な手から試す場合などに用いる。
+    fn sort_by_key<K: Ord, F: FnMut(&M) -> K>(&mut self, f: F)
+    where
+        Self: Sized,
+    {
+        self.as_mut_slice().sort_by_key(f);
+    }
+}
+
+impl<M> MoveList<M> for Vec<M> {
+    fn push(&mut self, mv: M) {
+        Self::push(self, mv);
+    }
+    fn clear(&mut self) {
+        Self::clear(self);
+    }
+    fn as_slice(&self) -> &[M] {
+        self
+    }
+    fn as_mut_slice(&mut self) -> &mut [M] {
+        self
+    }
+}
+
+/// The capacity of [`ArrayMoveList`] unless otherwise specified, which exceeds the number of moves available in any position arising in practice
+/// ／特に指定しない場合の [`ArrayMoveList`] の容量。実戦で現れるどの局面の指し手の数よりも大きい。
+pub const DEFAULT_MOVE_LIST_CAPACITY: usize = 4096;
+
+/// A [`MoveList`] of fixed capacity that lives on the stack, so that generating moves does not allocate
+/// ／スタック上に置かれる固定容量の [`MoveList`]。これを使えば指し手生成でメモリ確保が起こらない。
+///
+/// # Panics
+/// [`MoveList::push`] panics when the list is full.
+/// ／リストが満杯のとき、[`MoveList::push`] はパニックする。
+pub struct ArrayMoveList<M: Copy, const N: usize = DEFAULT_MOVE_LIST_CAPACITY> {
+    moves: [MaybeUninit<M>; N],
+    len: usize,
+}
+
+impl<M: Copy, const N: usize> ArrayMoveList<M, N> {
+    /// An empty list
+    /// ／空のリスト
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            moves: [MaybeUninit::uninit(); N],
+            len: 0,
+        }
+    }
+}
+
+impl<M: Copy, const N: usize> Default for ArrayMoveList<M, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M: Copy, const N: usize> Clone for ArrayMoveList<M, N> {
+    fn clone(&self) -> Self {
+        Self {
+            moves: self.moves,
+            len: self.len,
+        }
+    }
+}
+
+impl<M: Copy + core::fmt::Debug, const N: usize> core::fmt::Debug for ArrayMoveList<M, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<M: Copy, const N: usize> MoveList<M> for ArrayMoveList<M, N> {
+    fn push(&mut self, mv: M) {
+        assert!(self.len < N, "ArrayMoveList is full (capacity {N})");
+        self.moves[self.len] = MaybeUninit::new(mv);
+        self.len += 1;
+    }
+    fn clear(&mut self) {
+        self.len = 0;
+    }
+    fn as_slice(&self) -> &[M] {
+        // SAFETY: the first `len` elements have been initialized by `push`
+        unsafe { core::slice::from_raw_parts(self.moves.as_ptr().cast::<M>(), self.len) }
+    }
+    fn as_mut_slice(&mut self) -> &mut [M] {
+        // SAFETY: the first `len` elements have been initialized by `push`
+        unsafe { core::slice::from_raw_parts_mut(self.moves.as_mut_ptr().cast::<M>(), self.len) }
+    }
+}
+
+/// A small pseudorandom number generator (splitmix64), which gives the same sequence for the same seed on every platform
+/// ／小さな擬似乱数生成器（splitmix64）。同じシードからはどのプラットフォームでも同じ列を生成する。
+///
+/// It is meant for reproducible choices such as the order of moves in self-play, not for anything that needs unpredictability.
+/// ／自己対局における指し手の順序など、再現可能な選択のためのものであり、予測不能性が必要な用途には向かない。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// A generator starting from `seed`
+    /// ／`seed` から始まる生成器
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// The next number, uniformly distributed over `u64`
+    /// ／次の数。`u64` 全体に一様に分布する。
+    pub const fn next_u64(&mut self) -> u64 {
+        let x = mix(self.state);
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        x
+    }
+
+    /// A number below `n`, by multiplying the next number by `n` and keeping the upper half
+    /// ／`n` 未満の数。次の数に `n` を掛けた積の上位半分を取る。
+    ///
+    /// The bias of this is below `n / 2^64`, which is negligible for the lengths of move lists.
+    /// ／偏りは `n / 2^64` 未満であり、指し手のリストの長さでは無視できる。
+    ///
+    /// # Panics
+    /// Panics if `n` is 0.
+    /// ／`n` が 0 ならパニックする。
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "SplitMix64::below(0)");
+        let product = u128::from(self.next_u64()) * n as u128;
+        usize::try_from(product >> 64).expect("below n, which is a usize")
+    }
+
+    /// Shuffles `items` by the Fisher-Yates algorithm
+    /// ／Fisher-Yates のアルゴリズムで `items` をシャッフルする
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// Shuffles the moves held in `list` in an order determined only by `seed` and the length of the list
+/// ／`list` が保持する指し手を、`seed` とリストの長さのみで決まる順序にシャッフルする
+///
+/// This uses [`SplitMix64`], so that self-play can be reproduced without depending on `rand`.
+/// ／`rand` に依存せずに自己対局を再現できるよう、[`SplitMix64`] を用いる。
+pub fn shuffle_moves<M, L: MoveList<M> + ?Sized>(list: &mut L, seed: u64) {
+    SplitMix64::new(seed).shuffle(list.as_mut_slice());
+}
@@ -0,0 +1,434 @@
+use crate::{
+    is_ciurl_required, CetkaikRepresentation, GuardIter, IsAbsoluteField, IsBitBoard, IsBoard,
+    IsCiurlSource, IsField, IsPieceWithSide, MoveList, RuleConfig,
+};
+use cetkaik_fundamental::{AbsoluteSide, ColorAndProf, PureMove_};
+
+/// A move written in the absolute coordinate of the representation `R`
+/// ／表現 `R` の絶対座標で書かれた指し手
+pub type PureMove<R> = PureMove_<<R as CetkaikRepresentation>::AbsoluteCoord>;
+
+/// A trait that signifies that you can use it to enumerate the moves available on a field
+/// ／`Field` 上で可能な指し手を列挙できる型を表すトレイト
+///
+/// This is meant to be implemented by a rule set, so that the same rule set works for every representation.
+/// ／ルールを表す型が実装することを想定しており、そうすれば同じルールをどの表現に対しても使える。
+pub trait IsMoveGenerator<R: CetkaikRepresentation> {
+    /// Appends to `out` every move that `whose_turn` can make on `field`
+    /// ／`field` において `whose_turn` が指せる手をすべて `out` に追加する
+    ///
+    /// Pass an [`ArrayMoveList`](crate::ArrayMoveList) to generate moves without allocating.
+    /// ／[`ArrayMoveList`](crate::ArrayMoveList) を渡せば、メモリ確保なしに指し手を生成できる。
+    fn generate_moves<L: MoveList<PureMove<R>>>(
+        &self,
+        field: &R::AbsoluteField,
+        whose_turn: AbsoluteSide,
+        out: &mut L,
+    );
+
+    /// The rule configuration under which the moves are generated, and with which helpers such as [`play_out`] apply them
+    /// ／指し手を生成する際のルール設定。[`play_out`] などの補助関数もこの設定で指し手を適用する。
+    fn config(&self) -> RuleConfig {
+        RuleConfig::default()
+    }
+
+    /// Lists every move that `whose_turn` can make on `field`
+    /// ／`field` において `whose_turn` が指せる手をすべて列挙する
+    fn all_moves(&self, field: &R::AbsoluteField, whose_turn: AbsoluteSide) -> Vec<PureMove<R>> {
+        let mut moves = vec![];
+        self.generate_moves(field, whose_turn, &mut moves);
+        moves
+    }
+
+    /// Lists, without duplicates, the squares that a piece of `side` on the board can move to on its next turn
+    /// ／盤上にある `side` の駒が次の手番で移動できるマスを、重複なく列挙する
+    ///
+    /// Parachuting from the hop1zuo1, moving Tam2 and returning to the original square do not guard a square, since none of them can take a piece.
+    /// The default generates every move of `side` with [`IsMoveGenerator::generate_moves`];
+    /// a move generator backed by precomputed masks can override it.
+    /// ／手駒を打つこと、皇を動かすこと、元のマスに戻ることはいずれも駒を取れないので、マスを守ることにはならない。
+    /// 既定の実装は [`IsMoveGenerator::generate_moves`] で `side` の指し手をすべて生成する。事前計算したマスクを持つ指し手生成器はこれを上書きできる。
+    fn guarded_squares(
+        &self,
+        field: &R::AbsoluteField,
+        side: AbsoluteSide,
+    ) -> GuardIter<R::AbsoluteCoord> {
+        let mut seen = 0u128;
+        self.all_moves(field, side)
+            .into_iter()
+            .filter_map(|mv| match mv {
+                PureMove_::NonTamMoveSrcDst { src, dest, .. }
+                | PureMove_::NonTamMoveSrcStepDstFinite { src, dest, .. }
+                | PureMove_::InfAfterStep {
+                    src,
+                    planned_direction: dest,
+                    ..
+                } => (src != dest).then_some(dest),
+                PureMove_::NonTamMoveFromHopZuo { .. }
+                | PureMove_::TamMoveNoStep { .. }
+                | PureMove_::TamMoveStepsDuringFormer { .. }
+                | PureMove_::TamMoveStepsDuringLatter { .. } => None,
+            })
+            .filter(|&dest| {
+                let bit = 1 << R::coord_to_index(dest);
+                let new = seen & bit == 0;
+                seen |= bit;
+                new
+            })
+            .collect()
+    }
+
+    /// Whether a piece of `by_side` on the board can move to `coord` on its next turn
+    /// ／盤上にある `by_side` の駒が次の手番で `coord` に移動できるかどうか
+    ///
+    /// The default looks `coord` up in [`IsMoveGenerator::guarded_squares`];
+    /// a move generator that can stop at the first piece reaching `coord` should override it.
+    /// ／既定の実装は [`IsMoveGenerator::guarded_squares`] から `coord` を探す。
+    /// `coord` に届く最初の駒が見つかった時点で打ち切れる指し手生成器は、これを上書きすべきである。
+    fn is_attacked(
+        &self,
+        field: &R::AbsoluteField,
+        coord: R::AbsoluteCoord,
+        by_side: AbsoluteSide,
+    ) -> bool {
+        self.guarded_squares(field, by_side).any(|c| c == coord)
+    }
+}
+
+/// Appends to `out` every parachute that `whose_turn` can make on `field`, i.e. every piece of its hop1zuo1 onto every empty square
+/// ／`field` において `whose_turn` が打てる手、すなわち手駒の各駒を各空きマスに打つ手をすべて `out` に追加する
+///
+/// The empty squares are taken at once from [`IsBoard::empty_mask`] instead of being checked one by one.
+/// The moves come by piece, in the canonical order of [`IsAbsoluteField::hop1zuo1_sorted`] with duplicates removed,
+/// and then by destination, in the canonical order of squares. A move generator can call this from [`IsMoveGenerator::generate_moves`].
+/// ／空きマスは一マスずつ確かめるのではなく、[`IsBoard::empty_mask`] から一度に得る。
+/// 指し手は駒ごとに（重複を除いた [`IsAbsoluteField::hop1zuo1_sorted`] の正準な順で）、次に行き先ごとに（マスの正準な順で）並ぶ。
+/// 指し手生成器は [`IsMoveGenerator::generate_moves`] からこれを呼ぶことができる。
+#[allow(clippy::cast_possible_truncation)]
+pub fn generate_parachutes<R: IsBitBoard, L: MoveList<PureMove<R>>>(
+    field: &R::AbsoluteField,
+    whose_turn: AbsoluteSide,
+    out: &mut L,
+) {
+    let empty = field.as_board().empty_mask::<R>();
+    let mut pieces = field.hop1zuo1_sorted(whose_turn);
+    pieces.dedup();
+    for ColorAndProf { color, prof } in pieces {
+        let mut rest = empty;
+        while rest != 0 {
+            // below 128, since `rest` is not 0
+            let index = rest.trailing_zeros() as u8;
+            rest &= rest - 1;
+            if let Some(dest) = R::index_to_coord(index) {
+                out.push(PureMove_::NonTamMoveFromHopZuo { color, prof, dest });
+            }
+        }
+    }
+}
+
+/// Applies `mv`, made by `whose_turn`, to `field`
+/// ／`whose_turn` による指し手 `mv` を `field` に適用する
+///
+/// Every stick cast the move requires is assumed to turn out in favor of the mover:
+/// a water entry always succeeds, and an `InfAfterStep` always reaches `planned_direction`.
+/// ／必要な投げ棒判定はすべて成功するものとする。つまり、入水判定は常に成功し、`InfAfterStep` は常に `planned_direction` に到達する。
+///
+/// Use [`apply_move_with_ciurl`] to have the sticks actually cast,
+/// and [`apply_move_with_config`] to follow a rule configuration other than [`RuleConfig::default`].
+/// ／投げ棒を実際に投げるには [`apply_move_with_ciurl`] を、
+/// [`RuleConfig::default`] 以外のルール設定に従うには [`apply_move_with_config`] を用いる。
+/// # Errors
+/// - the move tries to move a piece that is absent or that does not belong to `whose_turn`
+/// - the move tries to take Tam2
+/// - a Tam2 move does not start from Tam2, or ends at an occupied square
+/// - the piece to be parachuted is not found in the hop1zuo1, or the destination is occupied
+pub fn apply_move<R: CetkaikRepresentation>(
+    field: &R::AbsoluteField,
+    mv: PureMove<R>,
+    whose_turn: AbsoluteSide,
+) -> Result<R::AbsoluteField, &'static str> {
+    apply_move_with_config::<R>(field, mv, whose_turn, &RuleConfig::default())
+}
+
+/// Applies `mv`, made by `whose_turn`, to `field`, following the rule configuration `config`
+/// ／`whose_turn` による指し手 `mv` を、ルール設定 `config` に従って `field` に適用する
+///
+/// As in [`apply_move`], every stick cast is assumed to succeed.
+/// With the `tracing` feature, the move is applied in a `debug` span recording the move, whose turn it is and the
+/// [`CetkaikRepresentation::canonical_hash`] of `field`, with events for parachuting, the rejection of the move and the hash after it.
+/// ／[`apply_move`] と同様に、投げ棒判定はすべて成功するものとする。
+/// `tracing` フィーチャーを有効にすると、指し手・手番・`field` の [`CetkaikRepresentation::canonical_hash`] を記録する `debug` スパンの中で適用され、
+/// 駒を打つこと、指し手が拒否されたこと、適用後のハッシュがイベントとして記録される。
+/// # Errors
+/// Same as [`apply_move`], and also when the move steps over Tam2 while `config` does not allow it.
+/// ／[`apply_move`] と同じ。加えて、`config` が許さないのに皇を踏み越える手であれば失敗する。
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        err(level = "debug"),
+        fields(mv = %crate::MoveNotation::<R>::new(mv), ?whose_turn, hash = R::canonical_hash(field))
+    )
+)]
+pub fn apply_move_with_config<R: CetkaikRepresentation>(
+    field: &R::AbsoluteField,
+    mv: PureMove<R>,
+    whose_turn: AbsoluteSide,
+    config: &RuleConfig,
+) -> Result<R::AbsoluteField, &'static str> {
+    if let PureMove_::NonTamMoveSrcStepDstFinite { step, .. }
+    | PureMove_::InfAfterStep { step, .. } = mv
+    {
+        if !config.allow_kut2tam2 && field.as_board().peek(step) == Some(R::absolute_tam2()) {
+            return Err("Stepping over Tam2 is not allowed");
+        }
+    }
+    let result = match mv {
+        PureMove_::NonTamMoveSrcDst { src, dest, .. }
+        | PureMove_::NonTamMoveSrcStepDstFinite { src, dest, .. }
+        | PureMove_::InfAfterStep {
+            src,
+            planned_direction: dest,
+            ..
+        } => field.move_nontam_piece_from_src_to_dest_while_taking_opponent_piece_if_needed(
+            src, dest, whose_turn,
+        ),
+        PureMove_::NonTamMoveFromHopZuo { color, prof, dest } => {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(?color, ?prof, dest = R::coord_to_index(dest), "parachute");
+            field
+                .search_from_hop1zuo1_and_parachute_at(color, prof, whose_turn, dest)
+                .ok_or("Cannot find the piece in the hop1zuo1, or the destination is occupied")
+        }
+        PureMove_::TamMoveNoStep {
+            src, second_dest, ..
+        }
+        | PureMove_::TamMoveStepsDuringFormer {
+            src, second_dest, ..
+        }
+        | PureMove_::TamMoveStepsDuringLatter {
+            src, second_dest, ..
+        } => {
+            let mut new_field = field.clone();
+            let board = new_field.as_board_mut();
+            if board.peek(src) != Some(R::absolute_tam2()) {
+                return Err("Tam2 is not found at the origin");
+            }
+            if src != second_dest && board.peek(second_dest).is_some() {
+                return Err("The destination of Tam2 is occupied");
+            }
+            board.mov(src, second_dest);
+            Ok(new_field)
+        }
+    };
+    #[cfg(feature = "tracing")]
+    if let Ok(new_field) = &result {
+        tracing::debug!(hash = R::canonical_hash(new_field), "move applied");
+    }
+    result
+}
+
+/// Applies `mv`, made by `whose_turn`, to `field` in place, following the rule configuration `config`
+/// ／`whose_turn` による指し手 `mv` を、ルール設定 `config` に従ってその場で `field` に適用する
+///
+/// This works through [`IsField::as_board_mut`] and the hop1zuo1 methods instead of building a new field,
+/// so that a field taken from a [`FieldPool`](crate::FieldPool) can be reused. Otherwise it behaves as [`apply_move_with_config`].
+/// ／新たな `Field` を作る代わりに [`IsField::as_board_mut`] と手駒のメソッドを通じて操作するので、
+/// [`FieldPool`](crate::FieldPool) から取り出した `Field` を再利用できる。それ以外は [`apply_move_with_config`] と同様に振る舞う。
+/// # Errors
+/// Same as [`apply_move_with_config`], in which case `field` is left unchanged.
+/// ／[`apply_move_with_config`] と同じ。その場合 `field` は変更されない。
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        err(level = "debug"),
+        fields(mv = %crate::MoveNotation::<R>::new(mv), ?whose_turn, hash = R::canonical_hash(field))
+    )
+)]
+pub fn apply_move_in_place<R: CetkaikRepresentation>(
+    field: &mut R::AbsoluteField,
+    mv: PureMove<R>,
+    whose_turn: AbsoluteSide,
+    config: &RuleConfig,
+) -> Result<(), &'static str> {
+    let result = match mv {
+        PureMove_::NonTamMoveSrcDst { src, dest, .. }
+        | PureMove_::NonTamMoveSrcStepDstFinite { src, dest, .. }
+        | PureMove_::InfAfterStep {
+            src,
+            planned_direction: dest,
+            ..
+        } => {
+            if let PureMove_::NonTamMoveSrcStepDstFinite { step, .. }
+            | PureMove_::InfAfterStep { step, .. } = mv
+            {
+                if !config.allow_kut2tam2 && field.as_board().peek(step) == Some(R::absolute_tam2())
+                {
+                    return Err("Stepping over Tam2 is not allowed");
+                }
+            }
+            let board = field.as_board();
+            let src_piece = board.peek(src).ok_or("src does not contain a piece")?;
+            if src_piece.match_on_piece_and_apply(&|| None, &|_, _, side| Some(side))
+                != Some(whose_turn)
+            {
+                return Err("Expected a piece of the side to move at the src");
+            }
+            let captured = if src == dest { None } else { board.peek(dest) };
+            let captured = match captured {
+                None => None,
+                Some(piece) => Some(piece.match_on_piece_and_apply(
+                    &|| Err("Tried to capture a Tam2"),
+                    &|color, prof, side| {
+                        if side == whose_turn {
+                            Err("Tried to capture an ally")
+                        } else {
+                            Ok(ColorAndProf { color, prof })
+                        }
+                    },
+                )?),
+            };
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                src = R::coord_to_index(src),
+                dest = R::coord_to_index(dest),
+                piece = ?crate::CanonicalPiece::from_piece(src_piece),
+                ?captured,
+                "piece moved"
+            );
+            let board = field.as_board_mut();
+            board.pop(src);
+            board.put(dest, Some(src_piece));
+            if let Some(piece) = captured {
+                field.add_to_hop1zuo1(whose_turn, piece);
+            }
+            Ok(())
+        }
+        PureMove_::NonTamMoveFromHopZuo { color, prof, dest } => {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(?color, ?prof, dest = R::coord_to_index(dest), "parachute");
+            if field.as_board().is_occupied(dest)
+                || !field.remove_from_hop1zuo1(whose_turn, ColorAndProf { color, prof })
+            {
+                return Err(
+                    "Cannot find the piece in the hop1zuo1, or the destination is occupied",
+                );
+            }
+            field.as_board_mut().put(
+                dest,
+                Some(R::AbsolutePiece::non_tam2_piece(color, prof, whose_turn)),
+            );
+            Ok(())
+        }
+        PureMove_::TamMoveNoStep {
+            src, second_dest, ..
+        }
+        | PureMove_::TamMoveStepsDuringFormer {
+            src, second_dest, ..
+        }
+        | PureMove_::TamMoveStepsDuringLatter {
+            src, second_dest, ..
+        } => {
+            let board = field.as_board_mut();
+            if board.peek(src) != Some(R::absolute_tam2()) {
+                return Err("Tam2 is not found at the origin");
+            }
+            if src != second_dest && board.peek(second_dest).is_some() {
+                return Err("The destination of Tam2 is occupied");
+            }
+            board.mov(src, second_dest);
+            Ok(())
+        }
+    };
+    #[cfg(feature = "tracing")]
+    if result.is_ok() {
+        tracing::debug!(hash = R::canonical_hash(field), "move applied");
+    }
+    result
+}
+
+/// Applies `mv`, made by `whose_turn`, to `field`, casting the sticks from `ciurl` whenever the move requires it
+/// ／`whose_turn` による指し手 `mv` を `field` に適用する。投げ棒判定が必要な場合は `ciurl` から投げる。
+///
+/// A water entry succeeds when 3 or more sticks fall face up, and an `InfAfterStep` reaches `planned_direction`
+/// when as many sticks as the distance from `step` to `planned_direction` fall face up.
+/// On failure the piece stays at `src`, so the field is returned unchanged.
+/// ／入水判定は 3 本以上が表なら成功し、`InfAfterStep` は `step` から `planned_direction` までの距離以上の本数が表なら `planned_direction` に到達する。
+/// 失敗した場合は駒が `src` に留まるので、`Field` は変化しないまま返る。
+/// # Errors
+/// Same as [`apply_move`]. The move is checked before the sticks are cast, so an illegal move casts none.
+/// ／[`apply_move`] と同じ。指し手は投げ棒を投げる前に検査されるので、不正な手では投げ棒を投げない。
+pub fn apply_move_with_ciurl<R: CetkaikRepresentation, C: IsCiurlSource + ?Sized>(
+    field: &R::AbsoluteField,
+    mv: PureMove<R>,
+    whose_turn: AbsoluteSide,
+    ciurl: &mut C,
+) -> Result<R::AbsoluteField, &'static str> {
+    apply_move_with_ciurl_and_config::<R, C>(field, mv, whose_turn, ciurl, &RuleConfig::default())
+}
+
+/// Applies `mv` in the manner of [`apply_move_with_ciurl`], following the rule configuration `config`
+/// ／[`apply_move_with_ciurl`] と同様に、ルール設定 `config` に従って `mv` を適用する
+///
+/// Whether the sticks are cast is decided by [`is_ciurl_required`].
+/// ／投げ棒を投げるかどうかは [`is_ciurl_required`] が決める。
+/// # Errors
+/// Same as [`apply_move_with_config`]. The move is checked before the sticks are cast, so an illegal move casts none.
+/// ／[`apply_move_with_config`] と同じ。指し手は投げ棒を投げる前に検査されるので、不正な手では投げ棒を投げない。
+pub fn apply_move_with_ciurl_and_config<R: CetkaikRepresentation, C: IsCiurlSource + ?Sized>(
+    field: &R::AbsoluteField,
+    mv: PureMove<R>,
+    whose_turn: AbsoluteSide,
+    ciurl: &mut C,
+    config: &RuleConfig,
+) -> Result<R::AbsoluteField, &'static str> {
+    let new_field = apply_move_with_config::<R>(field, mv, whose_turn, config)?;
+    if !is_ciurl_required::<R>(&mv, config) {
+        return Ok(new_field);
+    }
+    let succeeds = match mv {
+        PureMove_::InfAfterStep {
+            step,
+            planned_direction,
+            ..
+        } => i32::from(ciurl.cast()) >= R::absolute_distance(step, planned_direction),
+        _ => ciurl.cast() >= 3,
+    };
+    Ok(if succeeds { new_field } else { field.clone() })
+}
+
+/// Plays at most `max_plies` moves from `field`, letting `choose` pick which of the `n` available moves to play
+/// ／`field` から最大 `max_plies` 手を指す。`n` 個の候補のうちどれを指すかは `choose` が決める。
+///
+/// Moves are applied with [`apply_move_with_config`] under the configuration of `rules`.
+/// Stops early when the side to move has no move, or when a move cannot be applied.
+/// Returns the resulting field, the side to move next, and the moves played.
+/// ／指し手は `rules` の設定のもとで [`apply_move_with_config`] により適用される。
+/// 手番の陣営が指せる手がないか、手が適用できなかった時点で打ち切る。得られた `Field`、次の手番の陣営、指した手を返す。
+pub fn play_out<R: CetkaikRepresentation, G: IsMoveGenerator<R>>(
+    rules: &G,
+    mut field: R::AbsoluteField,
+    mut whose_turn: AbsoluteSide,
+    max_plies: usize,
+    mut choose: impl FnMut(usize) -> usize,
+) -> (R::AbsoluteField, AbsoluteSide, Vec<PureMove<R>>) {
+    let config = rules.config();
+    let mut played = vec![];
+    for _ in 0..max_plies {
+        let moves = rules.all_moves(&field, whose_turn);
+        if moves.is_empty() {
+            break;
+        }
+        let mv = moves[choose(moves.len())];
+        match apply_move_with_config::<R>(&field, mv, whose_turn, &config) {
+            Ok(next) => field = next,
+            Err(_) => break,
+        }
+        played.push(mv);
+        whose_turn = !whose_turn;
+    }
+    (field, whose_turn, played)
+}
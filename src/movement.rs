@@ -0,0 +1,385 @@
+use crate::dynamic::prof_index;
+use crate::{CetkaikRepresentation, Direction, IsBoard, RawDestIter, COLS_U8, ROWS_U8};
+use cetkaik_fundamental::{AbsoluteSide, Profession};
+
+/// How the squares between a piece and its destination affect a [`MovementPattern`]
+/// ／駒と移動先の間にあるマスが [`MovementPattern`] にどう影響するか
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Blocking {
+    /// The squares in between do not matter
+    /// ／間のマスは関係ない
+    Jump,
+    /// Every square in between must be empty
+    /// ／間のマスはすべて空でなければならない
+    Blocked,
+    /// Every square in between, except the one next to the piece, must be empty (the 馬 on a tam2 hue)
+    /// ／間のマスは、駒に隣接するものを除いてすべて空でなければならない（皇処の馬）
+    BlockedExceptFirst,
+    /// At most one square in between may be occupied (the 巫 on a tam2 hue)
+    /// ／間のマスのうち、駒があってよいのは高々ひとつ（皇処の巫）
+    OverOne,
+}
+
+/// A set of destinations along one direction, reached by repeating `dir` between `min` and `max` times
+/// ／一方向に沿った移動先の集まり。`dir` を `min` 回以上 `max` 回以下繰り返して到達する。
+///
+/// Patterns are written for a piece moving upward, i.e. toward the row A, with `dir` given as `[row, column]`;
+/// use [`MovementPattern::oriented`] for the pieces of [`AbsoluteSide::ASide`].
+/// A pattern whose `max` is [`MovementPattern::UNBOUNDED`] is an infinite movement, whose distance is limited by the sticks when stepping.
+/// ／パターンは上向き、つまり A の行に向かって動く駒について、`dir` を `[行, 列]` として書かれている。
+/// [`AbsoluteSide::ASide`] の駒には [`MovementPattern::oriented`] を用いる。
+/// `max` が [`MovementPattern::UNBOUNDED`] であるパターンは無限移動であり、踏越えの際には投げ棒によって距離が制限される。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MovementPattern {
+    /// the unit step, as `[row, column]`／一歩の変位。`[行, 列]` で表す。
+    pub dir: [i8; 2],
+    /// the least number of steps／最小の歩数
+    pub min: u8,
+    /// the greatest number of steps／最大の歩数
+    pub max: u8,
+    /// how the squares in between matter／間のマスの扱い
+    pub blocking: Blocking,
+}
+
+impl MovementPattern {
+    /// The `max` of an infinite movement, which is long enough to cross the board
+    /// ／無限移動の `max`。盤を横断するのに十分な長さである。
+    pub const UNBOUNDED: u8 = 8;
+
+    const fn new(dir: [i8; 2], min: u8, max: u8, blocking: Blocking) -> Self {
+        Self {
+            dir,
+            min,
+            max,
+            blocking,
+        }
+    }
+
+    /// Whether this is an infinite movement
+    /// ／無限移動であるかどうか
+    #[must_use]
+    pub const fn is_unbounded(self) -> bool {
+        self.max >= Self::UNBOUNDED
+    }
+
+    /// The pattern as seen by a piece of `side`; the pieces of [`AbsoluteSide::ASide`] move downward
+    /// ／`side` の駒から見たパターン。[`AbsoluteSide::ASide`] の駒は下向きに動く。
+    #[must_use]
+    pub const fn oriented(self, side: AbsoluteSide) -> Self {
+        match side {
+            AbsoluteSide::IASide => self,
+            AbsoluteSide::ASide => Self {
+                dir: [-self.dir[0], self.dir[1]],
+                ..self
+            },
+        }
+    }
+
+    /// The displacement after `n` steps, as `[row, column]`
+    /// ／`n` 歩後の変位。`[行, 列]` で表す。
+    #[must_use]
+    pub const fn delta(self, n: u8) -> [i8; 2] {
+        #[allow(clippy::cast_possible_wrap)]
+        let n = n as i8;
+        [self.dir[0] * n, self.dir[1] * n]
+    }
+}
+
+const UP: [i8; 2] = Direction::Up.delta();
+const DOWN: [i8; 2] = Direction::Down.delta();
+const LEFT: [i8; 2] = Direction::Left.delta();
+const RIGHT: [i8; 2] = Direction::Right.delta();
+const UP_LEFT: [i8; 2] = Direction::UpLeft.delta();
+const UP_RIGHT: [i8; 2] = Direction::UpRight.delta();
+const DOWN_LEFT: [i8; 2] = Direction::DownLeft.delta();
+const DOWN_RIGHT: [i8; 2] = Direction::DownRight.delta();
+
+const INF: u8 = MovementPattern::UNBOUNDED;
+
+const fn one(dir: [i8; 2]) -> MovementPattern {
+    MovementPattern::new(dir, 1, 1, Blocking::Jump)
+}
+
+const fn jump_two(dir: [i8; 2]) -> MovementPattern {
+    MovementPattern::new(dir, 2, 2, Blocking::Jump)
+}
+
+const fn ray(dir: [i8; 2], max: u8) -> MovementPattern {
+    MovementPattern::new(dir, 1, max, Blocking::Blocked)
+}
+
+const EIGHT_NEIGHBORHOOD: &[MovementPattern] = &[
+    one(UP_LEFT),
+    one(UP),
+    one(UP_RIGHT),
+    one(LEFT),
+    one(RIGHT),
+    one(DOWN_LEFT),
+    one(DOWN),
+    one(DOWN_RIGHT),
+];
+
+/// How Tam2 moves in each of its two steps
+/// ／皇の二回の移動それぞれにおける動き方
+pub const TAM2_MOVEMENT: &[MovementPattern] = EIGHT_NEIGHBORHOOD;
+
+/// How each profession moves on a square that is not tam2 hue, indexed in the order 船, 兵, 弓, 車, 虎, 馬, 筆, 巫, 将, 王
+/// ／皇処でないマスにおける各職種の動き方。船、兵、弓、車、虎、馬、筆、巫、将、王の順に並ぶ。
+pub const NORMAL_MOVEMENT: [&[MovementPattern]; 10] = [
+    // 船
+    &[ray(UP, INF)],
+    // 兵
+    &[one(UP)],
+    // 弓
+    &[
+        ray(UP, INF),
+        ray(DOWN, INF),
+        ray(LEFT, INF),
+        ray(RIGHT, INF),
+    ],
+    // 車
+    &[
+        jump_two(UP),
+        jump_two(DOWN),
+        jump_two(LEFT),
+        jump_two(RIGHT),
+    ],
+    // 虎
+    &[one(UP_LEFT), one(UP_RIGHT), one(DOWN_LEFT), one(DOWN_RIGHT)],
+    // 馬
+    &[
+        jump_two(UP_LEFT),
+        jump_two(UP_RIGHT),
+        jump_two(DOWN_LEFT),
+        jump_two(DOWN_RIGHT),
+    ],
+    // 筆
+    &[one(LEFT), one(RIGHT), ray(UP, INF), ray(DOWN, INF)],
+    // 巫
+    &[one(UP), one(DOWN), ray(LEFT, INF), ray(RIGHT, INF)],
+    // 将
+    &[
+        one(UP_LEFT),
+        one(UP),
+        one(UP_RIGHT),
+        one(LEFT),
+        one(RIGHT),
+        one(DOWN_LEFT),
+        one(DOWN_RIGHT),
+    ],
+    // 王
+    EIGHT_NEIGHBORHOOD,
+];
+
+/// How each profession moves on a tam2 hue square, indexed in the same order as [`NORMAL_MOVEMENT`]
+/// ／皇処における各職種の動き方。[`NORMAL_MOVEMENT`] と同じ順に並ぶ。
+pub const TAM_HUE_MOVEMENT: [&[MovementPattern]; 10] = [
+    // 船
+    &[ray(LEFT, 2), ray(RIGHT, 2), ray(UP, INF), ray(DOWN, INF)],
+    // 兵
+    &[ray(UP, 2), one(DOWN), one(LEFT), one(RIGHT)],
+    // 弓
+    &[
+        ray(UP_LEFT, INF),
+        ray(UP_RIGHT, INF),
+        ray(DOWN_LEFT, INF),
+        ray(DOWN_RIGHT, INF),
+    ],
+    // 車
+    &[
+        jump_two(UP_LEFT),
+        jump_two(UP_RIGHT),
+        jump_two(DOWN_LEFT),
+        jump_two(DOWN_RIGHT),
+    ],
+    // 虎
+    &[
+        ray(UP_LEFT, INF),
+        ray(UP_RIGHT, INF),
+        ray(DOWN_LEFT, INF),
+        ray(DOWN_RIGHT, INF),
+    ],
+    // 馬
+    &[
+        MovementPattern::new(UP_LEFT, 2, INF, Blocking::BlockedExceptFirst),
+        MovementPattern::new(UP_RIGHT, 2, INF, Blocking::BlockedExceptFirst),
+        MovementPattern::new(DOWN_LEFT, 2, INF, Blocking::BlockedExceptFirst),
+        MovementPattern::new(DOWN_RIGHT, 2, INF, Blocking::BlockedExceptFirst),
+    ],
+    // 筆
+    &[
+        ray(UP, INF),
+        ray(DOWN, INF),
+        ray(LEFT, INF),
+        ray(RIGHT, INF),
+    ],
+    // 巫
+    &[
+        MovementPattern::new(UP_LEFT, 1, INF, Blocking::OverOne),
+        MovementPattern::new(UP, 1, INF, Blocking::OverOne),
+        MovementPattern::new(UP_RIGHT, 1, INF, Blocking::OverOne),
+        MovementPattern::new(LEFT, 1, INF, Blocking::OverOne),
+        MovementPattern::new(RIGHT, 1, INF, Blocking::OverOne),
+        MovementPattern::new(DOWN_LEFT, 1, INF, Blocking::OverOne),
+        MovementPattern::new(DOWN, 1, INF, Blocking::OverOne),
+        MovementPattern::new(DOWN_RIGHT, 1, INF, Blocking::OverOne),
+    ],
+    // 将
+    EIGHT_NEIGHBORHOOD,
+    // 王
+    EIGHT_NEIGHBORHOOD,
+];
+
+/// How a piece of the profession `prof` moves, for a piece moving upward; see [`MovementPattern`]
+/// ／職種 `prof` の上向きの駒の動き方。[`MovementPattern`] を参照。
+///
+/// `tam_hue` tells whether the piece stands on a tam2 hue square.
+/// ／`tam_hue` は駒が皇処にいるかどうかを表す。
+#[must_use]
+pub const fn movement_patterns(prof: Profession, tam_hue: bool) -> &'static [MovementPattern] {
+    let table = if tam_hue {
+        &TAM_HUE_MOVEMENT
+    } else {
+        &NORMAL_MOVEMENT
+    };
+    table[prof_index(prof) as usize]
+}
+
+/// The destinations that a piece of the profession `prof` belonging to `side` could reach from `coord` if nothing stood in its way
+/// ／`side` に属する職種 `prof` の駒が、何にも妨げられなければ `coord` から到達できる移動先
+///
+/// Only [`movement_patterns`] is consulted, without looking at any board, so the result is a superset of the legal destinations,
+/// listed pattern by pattern and nearest first. `tam_hue` tells whether `coord` is tam2 hue.
+/// ／盤面を見ずに [`movement_patterns`] のみを参照するので、結果は合法な移動先の上位集合であり、パターンごとに近い順に並ぶ。
+/// `tam_hue` は `coord` が皇処であるかどうかを表す。
+pub fn raw_destinations<R: CetkaikRepresentation>(
+    coord: R::AbsoluteCoord,
+    prof: Profession,
+    side: AbsoluteSide,
+    tam_hue: bool,
+) -> RawDestIter<R::AbsoluteCoord> {
+    let index = R::coord_to_index(coord);
+    movement_patterns(prof, tam_hue)
+        .iter()
+        .flat_map(|pattern| {
+            let pattern = pattern.oriented(side);
+            (pattern.min..=pattern.max).map_while(move |n| {
+                offset_index(index, pattern.delta(n)).and_then(R::index_to_coord)
+            })
+        })
+        .collect()
+}
+
+/// Whether a piece moving along a [`MovementPattern`] can reach its destination, as answered by [`can_reach`]
+/// ／[`MovementPattern`] に沿って動く駒が移動先に到達できるかどうか。[`can_reach`] が答える。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Reachability<C> {
+    /// No movement of the piece leads there
+    /// ／駒のどの動き方でもそこには至らない
+    Unreachable,
+    /// A movement leads there and nothing in between stands in its way
+    /// ／ある動き方でそこに至り、間にあるものに妨げられない
+    Clear,
+    /// Every movement leading there is blocked, and the nearest piece in the way is at the given square
+    /// ／そこに至る動き方はすべて妨げられており、最も近くで行く手を阻む駒は与えられたマスにある
+    BlockedAt(C),
+    /// A movement leading there is blocked only by the piece at the given square, so the destination can be reached only by stepping on it
+    /// ／そこに至るある動き方が与えられたマスの駒のみに妨げられているので、その駒を踏み越えることでしか移動先に到達できない
+    RequiresStepOver(C),
+}
+
+/// Whether a piece of `prof` and `side` can move from `from` to `to` on `board`, and if not, what is in the way
+/// ／`board` 上で `side` の職種 `prof` の駒が `from` から `to` に動けるかどうか。動けなければ何が行く手を阻むか
+///
+/// The patterns of [`movement_patterns`] are followed with the same blocking rules as [`crate::is_legal`], and `tam_hue` tells whether `from` is tam2 hue.
+/// A clear movement is reported as [`Reachability::Clear`]. Otherwise, a movement that would be clear without the nearest piece in its way
+/// is reported as [`Reachability::RequiresStepOver`] with that piece; whether the stepping move is legal is left to [`crate::is_legal`].
+/// Failing that, the nearest piece in the way of the first movement leading to `to` is reported as [`Reachability::BlockedAt`].
+/// What stands on `to` itself is not looked at.
+/// ／[`movement_patterns`] の動き方に [`crate::is_legal`] と同じ妨げの規則で従う。`tam_hue` は `from` が皇処であるかどうかを表す。
+/// 妨げられない動き方があれば [`Reachability::Clear`] とする。さもなくば、最も近くで行く手を阻む駒がなければ妨げられない動き方があれば、
+/// その駒とともに [`Reachability::RequiresStepOver`] とする。その踏越えが合法かどうかは [`crate::is_legal`] に任せる。
+/// それもなければ、`to` に至る最初の動き方で最も近くで行く手を阻む駒を [`Reachability::BlockedAt`] とする。`to` そのものにあるものは見ない。
+pub fn can_reach<R: CetkaikRepresentation>(
+    board: &R::AbsoluteBoard,
+    from: R::AbsoluteCoord,
+    to: R::AbsoluteCoord,
+    prof: Profession,
+    side: AbsoluteSide,
+    tam_hue: bool,
+) -> Reachability<R::AbsoluteCoord> {
+    let (src, dest) = (R::coord_to_index(from), R::coord_to_index(to));
+    let occupied = |index| R::index_to_coord(index).is_some_and(|c| board.peek(c).is_some());
+    let mut found = Reachability::Unreachable;
+    for pattern in movement_patterns(prof, tam_hue) {
+        let pattern = pattern.oriented(side);
+        let Some(steps) = steps_to(pattern, src, dest) else {
+            continue;
+        };
+        let mut in_the_way = obstacles(pattern, src, steps, occupied);
+        let nearest = in_the_way.next();
+        let count = nearest.map_or(0, |_| 1 + in_the_way.count());
+        let allowed = allowed_obstacles(pattern.blocking);
+        if count <= allowed {
+            return Reachability::Clear;
+        }
+        let Some(nearest) = nearest.and_then(R::index_to_coord) else {
+            continue;
+        };
+        found = match (found, count == allowed + 1) {
+            (Reachability::Unreachable | Reachability::BlockedAt(_), true) => {
+                Reachability::RequiresStepOver(nearest)
+            }
+            (Reachability::Unreachable, false) => Reachability::BlockedAt(nearest),
+            (found, _) => found,
+        };
+    }
+    found
+}
+
+/// The number of steps along `pattern` from `src` to `dest`, if `pattern` leads there
+pub(crate) fn steps_to(pattern: MovementPattern, src: u8, dest: u8) -> Option<u8> {
+    (pattern.min..=pattern.max).find(|&n| offset_index(src, pattern.delta(n)) == Some(dest))
+}
+
+/// The occupied squares in between that count against the [`Blocking`] of `pattern`, when moving `steps` steps from `src`, nearest first
+pub(crate) fn obstacles(
+    pattern: MovementPattern,
+    src: u8,
+    steps: u8,
+    occupied: impl Fn(u8) -> bool,
+) -> impl Iterator<Item = u8> {
+    (1..steps)
+        .filter(move |&k| !(pattern.blocking == Blocking::BlockedExceptFirst && k == 1))
+        .filter_map(move |k| offset_index(src, pattern.delta(k)))
+        .filter(move |&i| occupied(i))
+}
+
+/// The number of obstacles that `blocking` tolerates
+pub(crate) const fn allowed_obstacles(blocking: Blocking) -> usize {
+    match blocking {
+        Blocking::Jump => usize::MAX,
+        Blocking::Blocked | Blocking::BlockedExceptFirst => 0,
+        Blocking::OverOne => 1,
+    }
+}
+
+/// The canonical index reached by moving `[row, column]` from `index`, if it is on the board
+pub(crate) fn offset_index(index: u8, [dr, dc]: [i8; 2]) -> Option<u8> {
+    let (r, c) = (
+        i32::from(index / COLS_U8) + i32::from(dr),
+        i32::from(index % COLS_U8) + i32::from(dc),
+    );
+    if (0..i32::from(ROWS_U8)).contains(&r) && (0..i32::from(COLS_U8)).contains(&c) {
+        u8::try_from(r * i32::from(COLS_U8) + c).ok()
+    } else {
+        None
+    }
+}
+
+/// The canonical indices of the squares adjacent to `index`, diagonally included
+pub(crate) fn neighbor_indices(index: u8) -> impl Iterator<Item = u8> {
+    TAM2_MOVEMENT
+        .iter()
+        .filter_map(move |pattern| offset_index(index, pattern.dir))
+}
@@ -0,0 +1,560 @@
+use crate::dynamic::{move_to_coord, sort_hop1zuo1, CanonicalPiece};
+use crate::{
+    CetkaikRepresentation, IsAbsoluteField, IsBoard, IsField, IsPieceWithSide, PureMove, COLS,
+    COLS_U8, ROWS,
+};
+use cetkaik_fundamental::{AbsoluteSide, Color, ColorAndProf, Profession, PureMove_};
+
+/// The names of the rows, in the order of canonical indices
+pub(crate) const ROW_NAMES: [&str; ROWS] = ["A", "E", "I", "U", "O", "Y", "AI", "AU", "IA"];
+
+/// The names of the columns, in the order of canonical indices
+pub(crate) const COLUMN_NAMES: [&str; COLS] = ["K", "L", "N", "T", "Z", "X", "C", "M", "P"];
+
+/// The full-width numerals naming the columns in [`NotationStyle::Kanji`]
+const KANJI_COLUMN_NAMES: [char; COLS] = ['１', '２', '３', '４', '５', '６', '７', '８', '９'];
+
+/// The kanji numerals naming the rows in [`NotationStyle::Kanji`]
+const KANJI_ROW_NAMES: [char; ROWS] = ['一', '二', '三', '四', '五', '六', '七', '八', '九'];
+
+/// The digits naming both the columns and the rows in [`NotationStyle::Numeric`]
+const DIGITS: [char; COLS] = ['1', '2', '3', '4', '5', '6', '7', '8', '9'];
+
+/// 赤 for Kok1 and 黒 for Huok2
+pub(crate) const fn color_glyph(color: Color) -> char {
+    match color {
+        Color::Kok1 => '赤',
+        Color::Huok2 => '黒',
+    }
+}
+
+/// The glyph of a profession, such as 兵 for Kauk2
+pub(crate) const fn prof_glyph(prof: Profession) -> char {
+    match prof {
+        Profession::Nuak1 => '船',
+        Profession::Kauk2 => '兵',
+        Profession::Gua2 => '弓',
+        Profession::Kaun1 => '車',
+        Profession::Dau2 => '虎',
+        Profession::Maun1 => '馬',
+        Profession::Kua2 => '筆',
+        Profession::Tuk2 => '巫',
+        Profession::Uai1 => '将',
+        Profession::Io => '王',
+    }
+}
+
+/// ↓ for `ASide`, which starts at the top, and ↑ for `IASide`
+pub(crate) const fn side_arrow(side: AbsoluteSide) -> char {
+    match side {
+        AbsoluteSide::ASide => '↓',
+        AbsoluteSide::IASide => '↑',
+    }
+}
+
+/// A stable, human-readable dump of a field, as returned by [`CetkaikRepresentation::dump_field`]
+/// ／`Field` の安定した人間可読なダンプ。[`CetkaikRepresentation::dump_field`] が返す。
+///
+/// The format is defined by this crate, so equal positions dump to the same bytes in every representation.
+/// The board is printed row by row from A to IA, each row from K to P, with a piece written as its color, profession
+/// and an arrow pointing in the direction its side advances (e.g. `赤兵↑`), Tam2 as `皇`, and an empty square as `・`;
+/// no line has trailing whitespace.
+/// The hop1zuo1s of `ASide` and `IASide` follow, sorted by color (赤 first) and then by profession.
+/// ／書式はこのクレートが定めるので、等しい局面はどの表現でも同じバイト列になる。
+/// 盤面は A から IA まで一行ずつ、各行は K から P の順に出力する。駒は色・職種・陣営の進む向きの矢印で（例：`赤兵↑`）、
+/// 皇は `皇`、空きマスは `・` で表す。行末に空白は付かない。その後に `ASide` と `IASide` の手駒が、色（赤が先）、次に職種の順に整列されて続く。
+pub struct FieldDump<'a, R: CetkaikRepresentation> {
+    field: &'a R::AbsoluteField,
+}
+
+impl<R: CetkaikRepresentation> Clone for FieldDump<'_, R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R: CetkaikRepresentation> Copy for FieldDump<'_, R> {}
+
+impl<R: CetkaikRepresentation> core::fmt::Debug for FieldDump<'_, R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FieldDump")
+            .field("field", self.field)
+            .finish()
+    }
+}
+
+impl<'a, R: CetkaikRepresentation> FieldDump<'a, R> {
+    /// Dumps `field`
+    /// ／`field` をダンプする
+    pub const fn new(field: &'a R::AbsoluteField) -> Self {
+        Self { field }
+    }
+}
+
+impl<R: CetkaikRepresentation> core::fmt::Display for FieldDump<'_, R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let board = self.field.as_board();
+        write!(f, "  ")?;
+        for (i, column) in COLUMN_NAMES.into_iter().enumerate() {
+            let padding = if i + 1 == COLS { "" } else { "  " };
+            write!(f, " {column}{padding}")?;
+        }
+        writeln!(f)?;
+        for (row, row_name) in (0_u8..).zip(ROW_NAMES) {
+            write!(f, "{row_name:<2}")?;
+            for column in 0..COLS_U8 {
+                let piece = R::index_to_coord(COLS_U8 * row + column).and_then(|c| board.peek(c));
+                let padding = if column == COLS_U8 - 1 { "" } else { "  " };
+                match piece {
+                    None => write!(f, " ・{padding}")?,
+                    Some(piece) if piece == R::absolute_tam2() => write!(f, " 皇{padding}")?,
+                    Some(piece) => write!(f, " {}", piece.glyphs())?,
+                }
+            }
+            writeln!(f)?;
+        }
+        for (side, label) in [
+            (AbsoluteSide::ASide, "ASide"),
+            (AbsoluteSide::IASide, "IASide"),
+        ] {
+            let mut hop1zuo1 = self.field.hop1zuo1_of(side).collect::<Vec<_>>();
+            sort_hop1zuo1(&mut hop1zuo1);
+            write!(f, "{label}:")?;
+            for ColorAndProf { color, prof } in hop1zuo1 {
+                write!(f, " {}{}", color_glyph(color), prof_glyph(prof))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// A piece written in the standard glyphs, as returned by [`PieceNotation::glyphs`]
+/// ／標準の字形で書かれた駒。[`PieceNotation::glyphs`] が返す。
+///
+/// A piece other than Tam2 is written as its color, its profession and an arrow pointing in the direction its side advances,
+/// i.e. `↓` for `ASide` and `↑` for `IASide` (e.g. `赤兵↑`, `黒将↓`), and Tam2 is written as `皇`.
+/// This is the notation [`FieldDump`] uses for the pieces on the board, and it is read back by [`core::str::FromStr`].
+/// ／皇以外の駒は色・職種・陣営の進む向きの矢印（`ASide` なら `↓`、`IASide` なら `↑`）で書き（例：`赤兵↑`、`黒将↓`）、皇は `皇` と書く。
+/// [`FieldDump`] が盤上の駒に用いる表記と同じであり、[`core::str::FromStr`] で読み戻せる。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PieceGlyphs<P>(pub P);
+
+impl<P: PieceNotation + Copy> core::fmt::Display for PieceGlyphs<P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match CanonicalPiece::from_piece(self.0) {
+            CanonicalPiece::Tam2 => write!(f, "皇"),
+            CanonicalPiece::NonTam2Piece { color, prof, side } => write!(
+                f,
+                "{}{}{}",
+                color_glyph(color),
+                prof_glyph(prof),
+                side_arrow(side)
+            ),
+        }
+    }
+}
+
+impl<P: PieceNotation> core::str::FromStr for PieceGlyphs<P> {
+    type Err = ParsePieceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "皇" {
+            return Ok(Self(P::tam2()));
+        }
+        let mut reader = MoveReader(s);
+        let color = reader.glyph().ok_or(ParsePieceError)?;
+        let prof = reader.glyph().ok_or(ParsePieceError)?;
+        let side = match reader.0 {
+            "↓" => AbsoluteSide::ASide,
+            "↑" => AbsoluteSide::IASide,
+            _ => return Err(ParsePieceError),
+        };
+        Ok(Self(P::non_tam2_piece(color, prof, side)))
+    }
+}
+
+/// The error returned when a string is not a piece written in [`PieceGlyphs`]
+/// ／文字列が [`PieceGlyphs`] で書かれた駒でないときに返るエラー
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ParsePieceError;
+
+impl core::fmt::Display for ParsePieceError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "not a piece written in the standard glyphs")
+    }
+}
+
+impl std::error::Error for ParsePieceError {}
+
+/// Writing and reading a piece in the standard glyphs of [`PieceGlyphs`], implemented by every piece with an absolute side
+/// ／[`PieceGlyphs`] の標準の字形による駒の読み書き。絶対的な陣営を持つすべての駒が実装する。
+pub trait PieceNotation: IsPieceWithSide<Side = AbsoluteSide> + Sized {
+    /// The piece written in the standard glyphs, e.g. `赤兵↑` or `皇`
+    /// ／標準の字形で書かれた駒。例：`赤兵↑`、`皇`
+    fn glyphs(self) -> PieceGlyphs<Self> {
+        PieceGlyphs(self)
+    }
+
+    /// Reads a piece written in the standard glyphs
+    /// ／標準の字形で書かれた駒を読む
+    ///
+    /// # Errors
+    /// Returns [`ParsePieceError`] if `s` is not a piece written as [`PieceNotation::glyphs`] writes it.
+    /// ／`s` が [`PieceNotation::glyphs`] の書く形の駒でなければ [`ParsePieceError`] を返す。
+    fn from_glyphs(s: &str) -> Result<Self, ParsePieceError> {
+        s.parse().map(|PieceGlyphs(piece)| piece)
+    }
+}
+
+impl<P: IsPieceWithSide<Side = AbsoluteSide>> PieceNotation for P {}
+
+/// A move written in the notation of `cetkaik_fundamental`, with the squares named by their column and row (e.g. `LAU`)
+/// ／`cetkaik_fundamental` の記法で書かれた指し手。マスは列と行の名前（例：`LAU`）で表す。
+///
+/// The notation is `{src}片{dest}`, `{src}片{step}{dest}` (each followed by `水` when a water entry is to be cast),
+/// `{src}片{step}心{planned_direction}`, `{color}{prof}{dest}` for parachuting (e.g. `黒弓ZI`),
+/// and `{src}皇[{first_dest}]{second_dest}`, `{src}皇{step}[{first_dest}]{second_dest}`
+/// or `{src}皇[{first_dest}]{step}{second_dest}` for Tam2. It does not depend on the representation and is read back by [`parse_move`].
+/// The squares are named in [`NotationStyle::Latin`] unless another style is chosen by [`MoveNotation::style`].
+/// ／記法は `{src}片{dest}`、`{src}片{step}{dest}`（入水判定を行う場合はそれぞれの後に `水` が付く）、
+/// `{src}片{step}心{planned_direction}`、打つ手は `{color}{prof}{dest}`（例：`黒弓ZI`）、
+/// 皇の手は `{src}皇[{first_dest}]{second_dest}`、`{src}皇{step}[{first_dest}]{second_dest}`、
+/// `{src}皇[{first_dest}]{step}{second_dest}` である。表現に依らず、[`parse_move`] で読み戻せる。
+/// マスは、[`MoveNotation::style`] で別の方法を選ばない限り [`NotationStyle::Latin`] で表す。
+pub struct MoveNotation<R: CetkaikRepresentation> {
+    mv: PureMove<R>,
+    style: NotationStyle,
+}
+
+impl<R: CetkaikRepresentation> Clone for MoveNotation<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R: CetkaikRepresentation> Copy for MoveNotation<R> {}
+
+impl<R: CetkaikRepresentation> core::fmt::Debug for MoveNotation<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MoveNotation")
+            .field("mv", &self.mv)
+            .field("style", &self.style)
+            .finish()
+    }
+}
+
+impl<R: CetkaikRepresentation> MoveNotation<R> {
+    /// Writes `mv`, naming the squares in [`NotationStyle::Latin`]
+    /// ／`mv` を書き表す。マスは [`NotationStyle::Latin`] で表す。
+    pub const fn new(mv: PureMove<R>) -> Self {
+        Self {
+            mv,
+            style: NotationStyle::Latin,
+        }
+    }
+
+    /// Names the squares in `style` instead
+    /// ／マスを代わりに `style` で表す
+    #[must_use]
+    pub const fn style(mut self, style: NotationStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+/// How [`CoordNotation`] and [`MoveNotation`] name a square
+/// ／[`CoordNotation`] と [`MoveNotation`] がマスを表す方法
+///
+/// Every style names the column first and the row second, and is read back by [`parse_move`].
+/// The square `LAU`, for example, is `２八` in `Kanji` and `28` in `Numeric`.
+/// ／どの方法でも列を先に、行を後に書き、[`parse_move`] で読み戻せる。
+/// 例えばマス `LAU` は `Kanji` では `２八`、`Numeric` では `28` となる。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NotationStyle {
+    /// The Latin names of the column and the row, e.g. `LAU`
+    /// ／列と行のラテン文字による名前。例：`LAU`
+    #[default]
+    Latin,
+    /// The column as a full-width numeral and the row as a kanji numeral, each counted from 1, in the manner of shogi notation, e.g. `２八`
+    /// ／将棋の棋譜のように、1 から数えた列を全角数字で、行を漢数字で書く。例：`２八`
+    Kanji,
+    /// The column and the row as digits, each counted from 1, e.g. `28`
+    /// ／1 から数えた列と行を数字で書く。例：`28`
+    Numeric,
+}
+
+/// The name of a square, its column followed by its row
+struct SquareName(u8, NotationStyle);
+
+impl core::fmt::Display for SquareName {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (row, column) = (usize::from(self.0 / COLS_U8), usize::from(self.0 % COLS_U8));
+        match self.1 {
+            NotationStyle::Latin => write!(f, "{}{}", COLUMN_NAMES[column], ROW_NAMES[row]),
+            NotationStyle::Kanji => {
+                write!(f, "{}{}", KANJI_COLUMN_NAMES[column], KANJI_ROW_NAMES[row])
+            }
+            NotationStyle::Numeric => write!(f, "{}{}", DIGITS[column], DIGITS[row]),
+        }
+    }
+}
+
+/// A square named in a [`NotationStyle`]
+/// ／[`NotationStyle`] で表したマス
+pub struct CoordNotation<R: CetkaikRepresentation> {
+    coord: R::AbsoluteCoord,
+    style: NotationStyle,
+}
+
+impl<R: CetkaikRepresentation> Clone for CoordNotation<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R: CetkaikRepresentation> Copy for CoordNotation<R> {}
+
+impl<R: CetkaikRepresentation> core::fmt::Debug for CoordNotation<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CoordNotation")
+            .field("coord", &self.coord)
+            .field("style", &self.style)
+            .finish()
+    }
+}
+
+impl<R: CetkaikRepresentation> CoordNotation<R> {
+    /// Names `coord` in [`NotationStyle::Latin`]
+    /// ／`coord` を [`NotationStyle::Latin`] で表す
+    pub const fn new(coord: R::AbsoluteCoord) -> Self {
+        Self {
+            coord,
+            style: NotationStyle::Latin,
+        }
+    }
+
+    /// Names the square in `style` instead
+    /// ／マスを代わりに `style` で表す
+    #[must_use]
+    pub const fn style(mut self, style: NotationStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl<R: CetkaikRepresentation> core::fmt::Display for CoordNotation<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        SquareName(R::coord_to_index(self.coord), self.style).fmt(f)
+    }
+}
+
+impl<R: CetkaikRepresentation> core::fmt::Display for MoveNotation<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let s = |c: R::AbsoluteCoord| SquareName(R::coord_to_index(c), self.style);
+        let water = |is_water_entry_ciurl: bool| if is_water_entry_ciurl { "水" } else { "" };
+        match self.mv {
+            PureMove_::NonTamMoveSrcDst {
+                src,
+                dest,
+                is_water_entry_ciurl,
+            } => write!(f, "{}片{}{}", s(src), s(dest), water(is_water_entry_ciurl)),
+            PureMove_::NonTamMoveSrcStepDstFinite {
+                src,
+                step,
+                dest,
+                is_water_entry_ciurl,
+            } => write!(
+                f,
+                "{}片{}{}{}",
+                s(src),
+                s(step),
+                s(dest),
+                water(is_water_entry_ciurl)
+            ),
+            PureMove_::InfAfterStep {
+                src,
+                step,
+                planned_direction,
+            } => write!(f, "{}片{}心{}", s(src), s(step), s(planned_direction)),
+            PureMove_::NonTamMoveFromHopZuo { color, prof, dest } => {
+                write!(f, "{}{}{}", color_glyph(color), prof_glyph(prof), s(dest))
+            }
+            PureMove_::TamMoveNoStep {
+                src,
+                first_dest,
+                second_dest,
+            } => write!(f, "{}皇[{}]{}", s(src), s(first_dest), s(second_dest)),
+            PureMove_::TamMoveStepsDuringFormer {
+                src,
+                step,
+                first_dest,
+                second_dest,
+            } => write!(
+                f,
+                "{}皇{}[{}]{}",
+                s(src),
+                s(step),
+                s(first_dest),
+                s(second_dest)
+            ),
+            PureMove_::TamMoveStepsDuringLatter {
+                src,
+                first_dest,
+                step,
+                second_dest,
+            } => write!(
+                f,
+                "{}皇[{}]{}{}",
+                s(src),
+                s(first_dest),
+                s(step),
+                s(second_dest)
+            ),
+        }
+    }
+}
+
+/// Reads what is left of a move written in [`MoveNotation`]
+struct MoveReader<'a>(&'a str);
+
+impl MoveReader<'_> {
+    /// Consumes `token` if the rest starts with it
+    fn eat(&mut self, token: &str) -> bool {
+        self.0
+            .strip_prefix(token)
+            .map(|rest| self.0 = rest)
+            .is_some()
+    }
+
+    /// Consumes a square name in any [`NotationStyle`]
+    fn square(&mut self) -> Option<u8> {
+        self.latin_square()
+            .or_else(|| self.numbered_square(&KANJI_COLUMN_NAMES, &KANJI_ROW_NAMES))
+            .or_else(|| self.numbered_square(&DIGITS, &DIGITS))
+    }
+
+    /// Consumes a square name made of one character for the column and one for the row
+    fn numbered_square(&mut self, columns: &[char; COLS], rows: &[char; ROWS]) -> Option<u8> {
+        let mut chars = self.0.chars();
+        let (c, r) = (chars.next()?, chars.next()?);
+        let column = columns.iter().position(|&name| name == c)?;
+        let row = rows.iter().position(|&name| name == r)?;
+        self.0 = chars.as_str();
+        u8::try_from(COLS * row + column).ok()
+    }
+
+    /// Consumes a square name in [`NotationStyle::Latin`], a column letter followed by the vowels naming a row
+    fn latin_square(&mut self) -> Option<u8> {
+        let column = COLUMN_NAMES
+            .iter()
+            .position(|&name| self.0.starts_with(name))?;
+        let rest = &self.0[1..];
+        let len = rest
+            .find(|c| !matches!(c, 'A' | 'E' | 'I' | 'O' | 'U' | 'Y'))
+            .unwrap_or(rest.len());
+        let row = ROW_NAMES.iter().position(|&name| name == &rest[..len])?;
+        self.0 = &rest[len..];
+        u8::try_from(COLS * row + column).ok()
+    }
+
+    /// Consumes the glyph of a color or a profession
+    fn glyph<T: core::str::FromStr>(&mut self) -> Option<T> {
+        let c = self.0.chars().next()?;
+        let parsed = self.0[..c.len_utf8()].parse().ok()?;
+        self.0 = &self.0[c.len_utf8()..];
+        Some(parsed)
+    }
+
+    fn tam2_move(&mut self, src: u8) -> Option<PureMove_<u8>> {
+        if self.eat("[") {
+            let first_dest = self.square()?;
+            if !self.eat("]") {
+                return None;
+            }
+            let second = self.square()?;
+            Some(self.square().map_or(
+                PureMove_::TamMoveNoStep {
+                    src,
+                    first_dest,
+                    second_dest: second,
+                },
+                |second_dest| PureMove_::TamMoveStepsDuringLatter {
+                    src,
+                    first_dest,
+                    step: second,
+                    second_dest,
+                },
+            ))
+        } else {
+            let step = self.square()?;
+            if !self.eat("[") {
+                return None;
+            }
+            let first_dest = self.square()?;
+            if !self.eat("]") {
+                return None;
+            }
+            Some(PureMove_::TamMoveStepsDuringFormer {
+                src,
+                step,
+                first_dest,
+                second_dest: self.square()?,
+            })
+        }
+    }
+
+    fn non_tam2_move(&mut self, src: u8) -> Option<PureMove_<u8>> {
+        let first = self.square()?;
+        if self.eat("心") {
+            return Some(PureMove_::InfAfterStep {
+                src,
+                step: first,
+                planned_direction: self.square()?,
+            });
+        }
+        let second = self.square();
+        let is_water_entry_ciurl = self.eat("水");
+        Some(second.map_or(
+            PureMove_::NonTamMoveSrcDst {
+                src,
+                dest: first,
+                is_water_entry_ciurl,
+            },
+            |dest| PureMove_::NonTamMoveSrcStepDstFinite {
+                src,
+                step: first,
+                dest,
+                is_water_entry_ciurl,
+            },
+        ))
+    }
+
+    fn read(&mut self) -> Option<PureMove_<u8>> {
+        let mv = if let Some(src) = self.square() {
+            if self.eat("皇") {
+                self.tam2_move(src)?
+            } else if self.eat("片") {
+                self.non_tam2_move(src)?
+            } else {
+                return None;
+            }
+        } else {
+            let color = self.glyph()?;
+            let prof = self.glyph()?;
+            PureMove_::NonTamMoveFromHopZuo {
+                color,
+                prof,
+                dest: self.square()?,
+            }
+        };
+        self.0.is_empty().then_some(mv)
+    }
+}
+
+/// Reads a move written in [`MoveNotation`] in any [`NotationStyle`], or returns `None` if `s` is not such a move
+/// ／任意の [`NotationStyle`] の [`MoveNotation`] で書かれた指し手を読む。`s` がそのような指し手でなければ `None` を返す。
+#[must_use]
+pub fn parse_move<R: CetkaikRepresentation>(s: &str) -> Option<PureMove<R>> {
+    move_to_coord::<R>(MoveReader(s).read()?).ok()
+}
@@ -0,0 +1,117 @@
+use crate::IsBoard;
+
+/// A change made to a board, as reported by [`ObservedBoard`]
+/// ／[`ObservedBoard`] が報告する、盤面への変更
+///
+/// Every change is reported as pieces leaving and arriving at squares, so that e.g. a piece-square sum can be kept up to date
+/// by subtracting on [`BoardEvent::Removed`] and adding on [`BoardEvent::Placed`].
+/// ／すべての変更は駒がマスから去ることとマスに来ることとして報告されるので、例えば駒と位置の評価値の和は、
+/// [`BoardEvent::Removed`] で引き [`BoardEvent::Placed`] で足すことで最新に保てる。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BoardEvent<C, P> {
+    /// `piece` has arrived at `coord`
+    /// ／`piece` が `coord` に来た
+    Placed {
+        /// the square／マス
+        coord: C,
+        /// the piece／駒
+        piece: P,
+    },
+    /// `piece` has left `coord`
+    /// ／`piece` が `coord` から去った
+    Removed {
+        /// the square／マス
+        coord: C,
+        /// the piece／駒
+        piece: P,
+    },
+}
+
+/// A board that forwards every operation to `B` while reporting each change to `F`
+/// ／すべての操作を `B` に転送しつつ、変更のたびに `F` に報告する盤面
+#[derive(Clone, Debug)]
+pub struct ObservedBoard<B, F> {
+    board: B,
+    observer: F,
+}
+
+impl<B: IsBoard, F: FnMut(BoardEvent<B::Coord, B::PieceWithSide>)> ObservedBoard<B, F> {
+    /// Starts observing `board`
+    /// ／`board` の観測を始める
+    pub const fn new(board: B, observer: F) -> Self {
+        Self { board, observer }
+    }
+
+    /// The observed board
+    /// ／観測されている盤面
+    pub const fn get_ref(&self) -> &B {
+        &self.board
+    }
+
+    /// Stops observing and returns the board
+    /// ／観測をやめて盤面を返す
+    pub fn into_inner(self) -> B {
+        self.board
+    }
+
+    fn removed(&mut self, coord: B::Coord, piece: Option<B::PieceWithSide>) {
+        if let Some(piece) = piece {
+            (self.observer)(BoardEvent::Removed { coord, piece });
+        }
+    }
+
+    fn placed(&mut self, coord: B::Coord, piece: Option<B::PieceWithSide>) {
+        if let Some(piece) = piece {
+            (self.observer)(BoardEvent::Placed { coord, piece });
+        }
+    }
+}
+
+impl<B: IsBoard, F: FnMut(BoardEvent<B::Coord, B::PieceWithSide>)> IsBoard for ObservedBoard<B, F> {
+    type PieceWithSide = B::PieceWithSide;
+    type Coord = B::Coord;
+
+    fn peek(&self, c: Self::Coord) -> Option<Self::PieceWithSide> {
+        self.board.peek(c)
+    }
+    fn pop(&mut self, c: Self::Coord) -> Option<Self::PieceWithSide> {
+        let piece = self.board.pop(c);
+        self.removed(c, piece);
+        piece
+    }
+    fn put(&mut self, c: Self::Coord, p: Option<Self::PieceWithSide>) {
+        let previous = self.board.peek(c);
+        self.board.put(c, p);
+        self.removed(c, previous);
+        self.placed(c, p);
+    }
+    fn assert_empty(&self, c: Self::Coord) {
+        self.board.assert_empty(c);
+    }
+    fn assert_occupied(&self, c: Self::Coord) {
+        self.board.assert_occupied(c);
+    }
+    fn mov(&mut self, from: Self::Coord, to: Self::Coord) {
+        let piece = self.board.peek(from);
+        self.board.mov(from, to);
+        self.removed(from, piece);
+        self.placed(to, piece);
+    }
+
+    type EmptySquaresIter = B::EmptySquaresIter;
+    fn empty_squares(&self) -> Self::EmptySquaresIter {
+        self.board.empty_squares()
+    }
+
+    type OccupiedSquaresIter = B::OccupiedSquaresIter;
+    fn occupied_squares(&self) -> Self::OccupiedSquaresIter {
+        self.board.occupied_squares()
+    }
+
+    fn empty_square_count(&self) -> usize {
+        self.board.empty_square_count()
+    }
+    fn occupied_count(&self) -> usize {
+        self.board.occupied_count()
+    }
+}
@@ -0,0 +1,211 @@
+use crate::dynamic::{kind_code, move_to_coord, move_to_index};
+use crate::{
+    base_value, CetkaikRepresentation, IsBoard, IsField, IsPieceWithSide, MoveList, PureMove, COLS,
+    ROWS,
+};
+use cetkaik_fundamental::{ColorAndProf, PureMove_};
+use core::cmp::Reverse;
+
+/// The number of entries in a [`HistoryTable`]: one for each pair of a source and a destination, and one for each pair of a kind of piece and a destination
+const HISTORY_SIZE: usize = SQUARES * SQUARES + 20 * SQUARES;
+
+/// The number of squares, i.e. the number of canonical indices
+const SQUARES: usize = ROWS * COLS;
+
+/// The value of the piece that `mv` takes and that of the piece taking it, or `None` if `mv` takes nothing
+fn capture<R: CetkaikRepresentation>(
+    field: &R::AbsoluteField,
+    mv: PureMove<R>,
+) -> Option<(i32, i32)> {
+    let (PureMove_::NonTamMoveSrcDst { src, dest, .. }
+    | PureMove_::NonTamMoveSrcStepDstFinite { src, dest, .. }) = mv
+    else {
+        return None;
+    };
+    let board = field.as_board();
+    let (_, victim) = board.peek(dest)?.kind()?;
+    let (_, attacker) = board.peek(src)?.kind()?;
+    Some((base_value(victim), base_value(attacker)))
+}
+
+/// Sorts `moves` so that captures come first, in the order of MVV-LVA (most valuable victim, least valuable attacker)
+/// ／取る手が先に来るよう、MVV-LVA（最も価値の高い駒を、最も価値の低い駒で取る順）に `moves` を並べ替える
+///
+/// The pieces are valued by [`base_value`]. A capture of a more valuable piece comes first,
+/// and among captures of equally valuable pieces, the one made by the less valuable piece comes first.
+/// The moves that take nothing follow, in the order they were given, since the sort is stable.
+/// An `InfAfterStep` is not regarded as a capture, since where it ends is not known until the sticks are cast.
+/// ／駒は [`base_value`] で評価する。より価値の高い駒を取る手が先に来て、同じ価値の駒を取る手の間では、より価値の低い駒で取る手が先に来る。
+/// 何も取らない手はその後に、与えられた順のまま続く（安定な整列であるため）。
+/// `InfAfterStep` は、投げ棒を投げるまで行き先が分からないので、取る手とはみなさない。
+pub fn order_moves<R: CetkaikRepresentation, L: MoveList<PureMove<R>>>(
+    field: &R::AbsoluteField,
+    moves: &mut L,
+) {
+    moves.sort_by_key(|&mv| mvv_lva_key::<R>(field, mv));
+}
+
+/// The key by which [`order_moves`] sorts, which is smaller for a move to be tried earlier
+pub(crate) fn mvv_lva_key<R: CetkaikRepresentation>(
+    field: &R::AbsoluteField,
+    mv: PureMove<R>,
+) -> Reverse<Option<(i32, Reverse<i32>)>> {
+    Reverse(capture::<R>(field, mv).map(|(victim, attacker)| (victim, Reverse(attacker))))
+}
+
+/// The entry of a [`HistoryTable`] for a move written with canonical indices
+///
+/// A move from the board is keyed by its source and its final destination (the planned direction for an `InfAfterStep`),
+/// and a parachute by the kind of the piece and its destination.
+fn history_index(mv: PureMove_<u8>) -> usize {
+    let (src, dest) = match mv {
+        PureMove_::NonTamMoveFromHopZuo { color, prof, dest } => {
+            return SQUARES * SQUARES
+                + usize::from(kind_code(ColorAndProf { color, prof })) * SQUARES
+                + usize::from(dest);
+        }
+        PureMove_::NonTamMoveSrcDst { src, dest, .. }
+        | PureMove_::NonTamMoveSrcStepDstFinite { src, dest, .. }
+        | PureMove_::InfAfterStep {
+            src,
+            planned_direction: dest,
+            ..
+        }
+        | PureMove_::TamMoveNoStep {
+            src,
+            second_dest: dest,
+            ..
+        }
+        | PureMove_::TamMoveStepsDuringFormer {
+            src,
+            second_dest: dest,
+            ..
+        }
+        | PureMove_::TamMoveStepsDuringLatter {
+            src,
+            second_dest: dest,
+            ..
+        } => (src, dest),
+    };
+    usize::from(src) * SQUARES + usize::from(dest)
+}
+
+/// Scores of the history heuristic, which remembers the quiet moves that caused a cutoff, in a form shared by every representation
+/// ／ヒストリーヒューリスティクスの得点。カットオフを起こした静かな手を、すべての表現で共通の形で覚えておく。
+///
+/// A move from the board is keyed by the canonical indices (see [`CetkaikRepresentation::coord_to_index`]) of its source and its final destination,
+/// which is the planned direction for an `InfAfterStep` and the second destination for a Tam2 move,
+/// and a parachute is keyed by the color and the profession of the piece and the canonical index of its destination.
+/// ／盤上からの手は、元のマスと最終的な行き先（`InfAfterStep` では予定の方向、皇の手では二つ目の行き先）の正準な番号（[`CetkaikRepresentation::coord_to_index`] を参照）をキーとし、
+/// 打つ手は駒の色と職種および行き先の正準な番号をキーとする。
+#[derive(Clone, Debug)]
+pub struct HistoryTable {
+    scores: Box<[u32]>,
+}
+
+impl Default for HistoryTable {
+    fn default() -> Self {
+        Self {
+            scores: vec![0; HISTORY_SIZE].into_boxed_slice(),
+        }
+    }
+}
+
+impl HistoryTable {
+    /// A table in which every move scores 0
+    /// ／すべての手の得点が 0 である表
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `mv` caused a cutoff at a node searched to `depth`, adding `depth * depth` to its score
+    /// ／深さ `depth` まで探索した節点で `mv` がカットオフを起こしたことを記録し、その得点に `depth * depth` を加える
+    pub fn record<R: CetkaikRepresentation>(&mut self, mv: PureMove<R>, depth: u32) {
+        let score = &mut self.scores[history_index(move_to_index::<R>(mv))];
+        *score = score.saturating_add(depth.saturating_mul(depth));
+    }
+
+    /// The score of `mv`
+    /// ／`mv` の得点
+    #[must_use]
+    pub fn score<R: CetkaikRepresentation>(&self, mv: PureMove<R>) -> u32 {
+        self.scores[history_index(move_to_index::<R>(mv))]
+    }
+
+    /// Halves every score, so that old cutoffs weigh less than new ones, e.g. between iterations of iterative deepening
+    /// ／すべての得点を半分にし、古いカットオフが新しいものより軽くなるようにする。反復深化の反復の間などに用いる。
+    pub fn age(&mut self) {
+        for score in &mut self.scores {
+            *score /= 2;
+        }
+    }
+
+    /// Resets every score to 0
+    /// ／すべての得点を 0 に戻す
+    pub fn clear(&mut self) {
+        self.scores.fill(0);
+    }
+}
+
+/// Two killer moves for each ply, i.e. the most recent quiet moves that caused a cutoff at that distance from the root
+/// ／各手数ごとに二つのキラー手。すなわち、根からその距離でカットオフを起こした直近の静かな手
+///
+/// The moves are kept with their squares written as canonical indices, so a table can be shared between representations.
+/// ／指し手はマスを正準な番号で書いた形で保持するので、表を表現の間で共有できる。
+#[derive(Clone, Debug, Default)]
+pub struct KillerTable {
+    killers: Vec<[Option<PureMove_<u8>>; 2]>,
+}
+
+impl KillerTable {
+    /// A table with no killer moves
+    /// ／キラー手をひとつも持たない表
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `mv` caused a cutoff at `ply`, pushing out the older of the two killer moves there
+    /// ／`ply` で `mv` がカットオフを起こしたことを記録し、そこにある二つのキラー手のうち古い方を押し出す
+    pub fn record<R: CetkaikRepresentation>(&mut self, ply: usize, mv: PureMove<R>) {
+        if self.killers.len() <= ply {
+            self.killers.resize(ply + 1, [None; 2]);
+        }
+        let mv = Some(move_to_index::<R>(mv));
+        let slots = &mut self.killers[ply];
+        if slots[0] != mv {
+            slots[1] = slots[0];
+            slots[0] = mv;
+        }
+    }
+
+    /// Whether `mv` is one of the killer moves at `ply`
+    /// ／`mv` が `ply` のキラー手のひとつであるかどうか
+    #[must_use]
+    pub fn is_killer<R: CetkaikRepresentation>(&self, ply: usize, mv: PureMove<R>) -> bool {
+        let mv = Some(move_to_index::<R>(mv));
+        self.killers
+            .get(ply)
+            .is_some_and(|slots| slots.contains(&mv))
+    }
+
+    /// The killer moves at `ply`, the most recent first
+    /// ／`ply` のキラー手。新しいものから順に並ぶ。
+    pub fn killers<R: CetkaikRepresentation>(
+        &self,
+        ply: usize,
+    ) -> impl Iterator<Item = PureMove<R>> + '_ {
+        self.killers
+            .get(ply)
+            .into_iter()
+            .flatten()
+            .filter_map(|&mv| move_to_coord::<R>(mv?).ok())
+    }
+
+    /// Forgets every killer move
+    /// ／すべてのキラー手を忘れる
+    pub fn clear(&mut self) {
+        self.killers.clear();
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{CetkaikRepresentation, IsBoard, IsMoveGenerator, PureMove};
+use cetkaik_fundamental::AbsoluteSide;
+use rayon::prelude::*;
+
+/// Iterates over the empty squares of `board` in parallel
+/// ／`board` の空いているマスを並列に列挙する
+pub fn par_empty_squares<B: IsBoard>(board: &B) -> rayon::vec::IntoIter<B::Coord>
+where
+    B::Coord: Send,
+{
+    board.empty_squares().collect::<Vec<_>>().into_par_iter()
+}
+
+/// Iterates over the occupied squares of `board` in parallel
+/// ／`board` の駒があるマスを並列に列挙する
+pub fn par_occupied_squares<B: IsBoard>(board: &B) -> rayon::vec::IntoIter<B::Coord>
+where
+    B::Coord: Send,
+{
+    board.occupied_squares().collect::<Vec<_>>().into_par_iter()
+}
+
+/// Iterates over the moves of `whose_turn` in parallel, so that each subtree of a root-level search can be explored on its own thread
+/// ／`whose_turn` の指し手を並列に列挙する。ルートでの探索において、各部分木を別々のスレッドで調べるのに使える。
+pub fn par_all_moves<R, G>(
+    generator: &G,
+    field: &R::AbsoluteField,
+    whose_turn: AbsoluteSide,
+) -> rayon::vec::IntoIter<PureMove<R>>
+where
+    R: CetkaikRepresentation,
+    R::AbsoluteCoord: Send,
+    G: IsMoveGenerator<R>,
+{
+    generator.all_moves(field, whose_turn).into_par_iter()
+}
@@ -0,0 +1,93 @@
+use crate::movegen::{apply_move_in_place, apply_move_with_config};
+use crate::{ArrayMoveList, CetkaikRepresentation, FieldPool, IsMoveGenerator, MoveList, PureMove};
+use cetkaik_fundamental::AbsoluteSide;
+
+/// Reference node counts for the yhuap initial arrangement, indexed by depth
+/// ／yhuap の初期配置に対する、深さごとのノード数の参照値
+///
+/// The counts are the same whichever side moves first.
+/// ／どちらの陣営が先手でも同じ値になる。
+///
+/// These were obtained with `not_from_hop1zuo1_candidates_vec` and `from_hop1zuo1_candidates_vec` of `cetkaik_yhuap_move_candidates` 1.3.1
+/// as the move generator, called with `allow_kut2tam2: true` and `tam_itself_is_tam_hue: true`,
+/// under [`RuleConfig::yhuap`](crate::RuleConfig::yhuap), which is also [`RuleConfig::default`](crate::RuleConfig::default);
+/// each move is applied through [`apply_move_with_config`], so every stick cast succeeds.
+/// Stepping moves are counted once per declared destination or direction.
+/// ／`cetkaik_yhuap_move_candidates` 1.3.1 の `not_from_hop1zuo1_candidates_vec` と `from_hop1zuo1_candidates_vec` を
+/// `allow_kut2tam2: true`、`tam_itself_is_tam_hue: true` として指し手生成に用い、
+/// [`RuleConfig::yhuap`](crate::RuleConfig::yhuap)（[`RuleConfig::default`](crate::RuleConfig::default) でもある）のもとで
+/// [`apply_move_with_config`] により着手して得た値。したがって投げ棒判定はすべて成功する。踏越えを伴う手は、宣言された行き先・方向ごとに数える。
+pub const PERFT_YHUAP_INITIAL: [u64; 4] = [1, 249, 60_852, 15_008_477];
+
+/// Counts the leaf nodes of the game tree of the given depth
+/// ／与えられた深さのゲーム木の葉の数を数える
+///
+/// This is the canonical way to verify a move generator and to compare the speed of representations.
+/// ／指し手生成の正しさを確かめたり、表現どうしの速度を比べたりする標準的な方法。
+///
+/// Moves are applied with [`apply_move_with_config`] under the configuration of `rules`,
+/// so every stick cast is assumed to succeed.
+/// ／指し手は `rules` の設定のもとで [`apply_move_with_config`] により適用されるので、投げ棒判定はすべて成功するものとして扱われる。
+/// # Panics
+/// Panics if `rules` yields a move that [`apply_move_with_config`] rejects.
+pub fn perft<R: CetkaikRepresentation, G: IsMoveGenerator<R>>(
+    field: &R::AbsoluteField,
+    whose_turn: AbsoluteSide,
+    depth: u32,
+    rules: &G,
+) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    let config = rules.config();
+    let mut moves = ArrayMoveList::<PureMove<R>>::new();
+    rules.generate_moves(field, whose_turn, &mut moves);
+    match depth {
+        1 => moves.len() as u64,
+        _ => moves
+            .iter()
+            .map(|&mv| {
+                let next = apply_move_with_config::<R>(field, mv, whose_turn, &config)
+                    .unwrap_or_else(|e| {
+                        panic!("The move generator yielded a move that cannot be applied: {e}")
+                    });
+                perft::<R, G>(&next, !whose_turn, depth - 1, rules)
+            })
+            .sum(),
+    }
+}
+
+/// Counts the leaf nodes in the manner of [`perft`], taking the child fields from `pool` instead of allocating them
+/// ／[`perft`] と同様に葉の数を数える。子の `Field` は新たに確保する代わりに `pool` から取り出す。
+///
+/// Moves are applied with [`apply_move_in_place`] under the configuration of `rules`, and the children are released to `pool` when done.
+/// ／指し手は `rules` の設定のもとで [`apply_move_in_place`] により適用され、子は使い終わると `pool` に返却される。
+/// # Panics
+/// Panics if `rules` yields a move that [`apply_move_in_place`] rejects.
+pub fn perft_with_pool<R: CetkaikRepresentation, G: IsMoveGenerator<R>>(
+    field: &R::AbsoluteField,
+    whose_turn: AbsoluteSide,
+    depth: u32,
+    rules: &G,
+    pool: &mut FieldPool<R::AbsoluteField>,
+) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    let config = rules.config();
+    let mut moves = ArrayMoveList::<PureMove<R>>::new();
+    rules.generate_moves(field, whose_turn, &mut moves);
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+    let mut count = 0;
+    for &mv in moves.iter() {
+        let mut child = pool.acquire_copy_of(field);
+        apply_move_in_place::<R>(&mut child, mv, whose_turn, &config).unwrap_or_else(|e| {
+            panic!("The move generator yielded a move that cannot be applied: {e}")
+        });
+        count += perft_with_pool::<R, G>(&child, !whose_turn, depth - 1, rules, pool);
+        pool.release(child);
+    }
+    count
+}
@@ -0,0 +1,55 @@
+use crate::dynamic::{color_index, prof_index};
+use crate::repetition::side_index;
+use crate::{CanonicalPiece, CetkaikRepresentation, IsAbsoluteField, IsBoard, COLS, ROWS};
+use cetkaik_fundamental::{AbsoluteSide, ColorAndProf};
+
+/// The number of squares in a plane
+/// ／一枚のプレーンに含まれるマスの数
+pub const PLANE_SIZE: usize = ROWS * COLS;
+
+/// The number of planes written by [`encode_planes`]
+/// ／[`encode_planes`] が書き出すプレーンの枚数
+pub const PLANE_COUNT: usize = 81;
+
+/// Encodes `field` as [`PLANE_COUNT`] planes of [`PLANE_SIZE`] values each, written into `out` one plane after another
+/// ／`field` を [`PLANE_SIZE`] 個の値からなるプレーン [`PLANE_COUNT`] 枚として符号化し、`out` に順に書き出す
+///
+/// Within a plane, the squares are in the order of canonical indices (see [`CetkaikRepresentation::coord_to_index`]).
+/// - Plane 0 holds 1.0 where Tam2 is.
+/// - Plane `1 + 20 * side + 10 * color + prof` holds 1.0 where a piece of that kind is,
+///   where `side` is 0 for `ASide` and 1 for `IASide`, `color` is 0 for Kok1 and 1 for Huok2,
+///   and `prof` numbers Nuak1, Kauk2, Gua2, Kaun1, Dau2, Maun1, Kua2, Tuk2, Uai1, Io from 0 to 9.
+/// - Plane `41 + 20 * side + 10 * color + prof` is filled with the number of pieces of that kind in the hop1zuo1 of `side`.
+///
+/// Every other value is 0.0. The layout is the same across representations, so that trained weights can be shared.
+/// ／プレーン内ではマスを正準な番号順（[`CetkaikRepresentation::coord_to_index`] を参照）に並べる。
+/// - プレーン 0 は皇のあるマスが 1.0 となる。
+/// - プレーン `1 + 20 * side + 10 * color + prof` はその種類の駒のあるマスが 1.0 となる。
+///   ただし `side` は `ASide` が 0、`IASide` が 1、`color` は紅が 0、黒が 1、`prof` は船・兵・弓・車・虎・馬・筆・巫・将・王の順に 0 から 9 である。
+/// - プレーン `41 + 20 * side + 10 * color + prof` は、`side` の手駒にあるその種類の駒の数で埋められる。
+///
+/// それ以外の値はすべて 0.0 である。この配置は表現によらないので、学習した重みを共有できる。
+/// # Panics
+/// Panics if `out` is shorter than `PLANE_COUNT * PLANE_SIZE`.
+/// ／`out` が `PLANE_COUNT * PLANE_SIZE` より短ければパニックする。
+pub fn encode_planes<R: CetkaikRepresentation>(field: &R::AbsoluteField, out: &mut [f32]) {
+    let out = &mut out[..PLANE_COUNT * PLANE_SIZE];
+    out.fill(0.0);
+    let board = R::as_board_absolute(field);
+    for coord in board.occupied_squares() {
+        if let Some(piece) = board.peek(coord) {
+            let plane = usize::from(CanonicalPiece::from_piece(piece).code());
+            out[plane * PLANE_SIZE + usize::from(R::coord_to_index(coord))] = 1.0;
+        }
+    }
+    for side in [AbsoluteSide::ASide, AbsoluteSide::IASide] {
+        for ColorAndProf { color, prof } in field.hop1zuo1_of(side) {
+            let plane = usize::from(
+                41 + 20 * side_index(side) + 10 * color_index(color) + prof_index(prof),
+            );
+            for value in &mut out[plane * PLANE_SIZE..(plane + 1) * PLANE_SIZE] {
+                *value += 1.0;
+            }
+        }
+    }
+}
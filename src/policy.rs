@@ -0,0 +1,232 @@
+use crate::dynamic::{color_from_index, color_index, prof_from_index, prof_index};
+use crate::movement::offset_index;
+use crate::{CetkaikRepresentation, PureMove, COLS, COLS_U8, ROWS};
+use cetkaik_fundamental::PureMove_;
+
+/// The number of squares, i.e. the number of canonical indices
+const SQUARES: usize = ROWS * COLS;
+
+/// The longest distance a piece can travel in one direction
+const DISTANCES: usize = if ROWS > COLS { ROWS } else { COLS } - 1;
+
+/// The number of legs, i.e. pairs of a direction and a distance
+const LEGS: usize = 8 * DISTANCES;
+
+const SRC_DST: usize = 0;
+const SRC_STEP_DST_FINITE: usize = SRC_DST + SQUARES * LEGS * 2;
+const INF_AFTER_STEP: usize = SRC_STEP_DST_FINITE + SQUARES * LEGS * LEGS * 2;
+const FROM_HOP1ZUO1: usize = INF_AFTER_STEP + SQUARES * LEGS * LEGS;
+const TAM_NO_STEP: usize = FROM_HOP1ZUO1 + 2 * 10 * SQUARES;
+const TAM_STEPS_DURING_FORMER: usize = TAM_NO_STEP + SQUARES * 8 * 8;
+const TAM_STEPS_DURING_LATTER: usize = TAM_STEPS_DURING_FORMER + SQUARES * 8 * 8 * 8;
+
+/// The number of entries in the policy, i.e. one more than the largest value of [`move_to_policy_index`]
+/// ／方策の要素数。すなわち [`move_to_policy_index`] の最大値に 1 を足したもの
+pub const POLICY_SIZE: usize = TAM_STEPS_DURING_LATTER + SQUARES * 8 * 8 * 8;
+
+/// The eight directions as (row, column) offsets, in the order used by the policy index
+const DIRECTIONS: [(i8, i8); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// The leg from `from` to `to`, as `direction * DISTANCES + distance - 1`
+///
+/// The direction is given by the signs of the row and column offsets and the distance by the larger of the two,
+/// so that every pair of squares has a leg; a pair not along one of the eight directions shares it with one that is.
+fn leg(from: u8, to: u8) -> usize {
+    let (dr, dc) = (
+        i16::from(to / COLS_U8) - i16::from(from / COLS_U8),
+        i16::from(to % COLS_U8) - i16::from(from % COLS_U8),
+    );
+    let direction = DIRECTIONS
+        .iter()
+        .position(|&(r, c)| (i16::from(r), i16::from(c)) == (dr.signum(), dc.signum()))
+        .unwrap_or(0);
+    let distance = usize::from(dr.unsigned_abs().max(dc.unsigned_abs()).max(1)) - 1;
+    direction * DISTANCES + distance.min(DISTANCES - 1)
+}
+
+/// The square reached from `from` by `leg`, if it is on the board
+fn leg_target(from: u8, leg: usize) -> Option<u8> {
+    let (dr, dc) = DIRECTIONS[leg / DISTANCES];
+    let distance = i8::try_from(leg % DISTANCES + 1).ok()?;
+    offset_index(from, [dr * distance, dc * distance])
+}
+
+/// Maps a move to its entry in a policy of [`POLICY_SIZE`] entries, in a way that does not depend on the representation
+/// ／指し手を、[`POLICY_SIZE`] 個の要素からなる方策の中の位置に表現によらない方法で対応させる
+///
+/// With squares written as canonical indices (see [`CetkaikRepresentation::coord_to_index`]), `color` as 0 for Kok1 and 1 for Huok2,
+/// `prof` as 0 to 9 for Nuak1, Kauk2, Gua2, Kaun1, Dau2, Maun1, Kua2, Tuk2, Uai1, Io,
+/// `d` as one of the eight directions (0 to 7 for up-left, up, up-right, left, right, down-left, down, down-right, where up is towards the row A),
+/// and `l(a, b)` as the leg from `a` to `b`, i.e. `d * 8 + distance - 1`,
+/// the policy consists of the following blocks in this order:
+/// - `NonTamMoveSrcDst`: `(src * 64 + l(src, dest)) * 2 + is_water_entry_ciurl`
+/// - `NonTamMoveSrcStepDstFinite`: `((src * 64 + l(src, step)) * 64 + l(step, dest)) * 2 + is_water_entry_ciurl`
+/// - `InfAfterStep`: `(src * 64 + l(src, step)) * 64 + l(step, planned_direction)`
+/// - `NonTamMoveFromHopZuo`: `(color * 10 + prof) * 81 + dest`
+/// - `TamMoveNoStep`: `(src * 8 + d(src, first_dest)) * 8 + d(first_dest, second_dest)`
+/// - `TamMoveStepsDuringFormer`: `((src * 8 + d(src, step)) * 8 + d(step, first_dest)) * 8 + d(first_dest, second_dest)`
+/// - `TamMoveStepsDuringLatter`: `((src * 8 + d(src, first_dest)) * 8 + d(first_dest, step)) * 8 + d(step, second_dest)`
+///
+/// ／マスを正準な番号（[`CetkaikRepresentation::coord_to_index`] を参照）で、`color` を紅が 0、黒が 1 として、
+/// `prof` を船・兵・弓・車・虎・馬・筆・巫・将・王の順に 0 から 9 として、`d` を八方向のいずれか
+/// （A の行の方向を上として、左上・上・右上・左・右・左下・下・右下の順に 0 から 7）として、
+/// `l(a, b)` を `a` から `b` への向きと距離、すなわち `d * 8 + 距離 - 1` として書いたとき、
+/// 方策は上に挙げたブロックをこの順に並べたものである。
+///
+/// Every move is mapped, but a move that no piece can make, such as one whose leg is not along one of the eight directions
+/// or a Tam2 move whose leg goes further than a neighboring square, may share its entry with a move allowed by the rules.
+/// Moves allowed by the rules are mapped to distinct entries, which [`policy_index_to_move`] maps back.
+/// ／すべての指し手が対応付けられるが、八方向のいずれにも沿わない区間を持つ手や、隣のマスより遠くへ行く皇の移動など、
+/// どの駒にも指せない手は、ルール上許される手と同じ位置を共有することがある。
+/// ルール上許される手は互いに異なる位置に対応付けられ、[`policy_index_to_move`] によって元に戻る。
+#[must_use]
+pub fn move_to_policy_index<R: CetkaikRepresentation>(mv: PureMove<R>) -> usize {
+    let i = |c: R::AbsoluteCoord| usize::from(R::coord_to_index(c));
+    let l =
+        |a: R::AbsoluteCoord, b: R::AbsoluteCoord| leg(R::coord_to_index(a), R::coord_to_index(b));
+    let d = |a: R::AbsoluteCoord, b: R::AbsoluteCoord| l(a, b) / DISTANCES;
+    match mv {
+        PureMove_::NonTamMoveSrcDst {
+            src,
+            dest,
+            is_water_entry_ciurl,
+        } => SRC_DST + (i(src) * LEGS + l(src, dest)) * 2 + usize::from(is_water_entry_ciurl),
+        PureMove_::NonTamMoveSrcStepDstFinite {
+            src,
+            step,
+            dest,
+            is_water_entry_ciurl,
+        } => {
+            SRC_STEP_DST_FINITE
+                + ((i(src) * LEGS + l(src, step)) * LEGS + l(step, dest)) * 2
+                + usize::from(is_water_entry_ciurl)
+        }
+        PureMove_::InfAfterStep {
+            src,
+            step,
+            planned_direction,
+        } => INF_AFTER_STEP + (i(src) * LEGS + l(src, step)) * LEGS + l(step, planned_direction),
+        PureMove_::NonTamMoveFromHopZuo { color, prof, dest } => {
+            FROM_HOP1ZUO1
+                + usize::from(color_index(color) * 10 + prof_index(prof)) * SQUARES
+                + i(dest)
+        }
+        PureMove_::TamMoveNoStep {
+            src,
+            first_dest,
+            second_dest,
+        } => TAM_NO_STEP + (i(src) * 8 + d(src, first_dest)) * 8 + d(first_dest, second_dest),
+        PureMove_::TamMoveStepsDuringFormer {
+            src,
+            step,
+            first_dest,
+            second_dest,
+        } => {
+            TAM_STEPS_DURING_FORMER
+                + ((i(src) * 8 + d(src, step)) * 8 + d(step, first_dest)) * 8
+                + d(first_dest, second_dest)
+        }
+        PureMove_::TamMoveStepsDuringLatter {
+            src,
+            step,
+            first_dest,
+            second_dest,
+        } => {
+            TAM_STEPS_DURING_LATTER
+                + ((i(src) * 8 + d(src, first_dest)) * 8 + d(first_dest, step)) * 8
+                + d(step, second_dest)
+        }
+    }
+}
+
+/// The inverse of [`move_to_policy_index`]
+/// ／[`move_to_policy_index`] の逆
+///
+/// Returns `None` if `index` is [`POLICY_SIZE`] or more, or if it designates a move that leaves the board.
+/// ／`index` が [`POLICY_SIZE`] 以上であるか、盤外に出る手を指していれば `None` を返す。
+#[must_use]
+pub fn policy_index_to_move<R: CetkaikRepresentation>(index: usize) -> Option<PureMove<R>> {
+    let c = |i: u8| R::index_to_coord(i);
+    let square = |i: usize| u8::try_from(i).ok().filter(|&i| usize::from(i) < SQUARES);
+    let n = |from: u8, dir: usize| leg_target(from, dir * DISTANCES);
+    Some(if index < SRC_STEP_DST_FINITE {
+        let i = index - SRC_DST;
+        let src = square(i / 2 / LEGS)?;
+        PureMove_::NonTamMoveSrcDst {
+            src: c(src)?,
+            dest: c(leg_target(src, i / 2 % LEGS)?)?,
+            is_water_entry_ciurl: i % 2 == 1,
+        }
+    } else if index < INF_AFTER_STEP {
+        let i = index - SRC_STEP_DST_FINITE;
+        let src = square(i / 2 / LEGS / LEGS)?;
+        let step = leg_target(src, i / 2 / LEGS % LEGS)?;
+        PureMove_::NonTamMoveSrcStepDstFinite {
+            src: c(src)?,
+            step: c(step)?,
+            dest: c(leg_target(step, i / 2 % LEGS)?)?,
+            is_water_entry_ciurl: i % 2 == 1,
+        }
+    } else if index < FROM_HOP1ZUO1 {
+        let i = index - INF_AFTER_STEP;
+        let src = square(i / LEGS / LEGS)?;
+        let step = leg_target(src, i / LEGS % LEGS)?;
+        PureMove_::InfAfterStep {
+            src: c(src)?,
+            step: c(step)?,
+            planned_direction: c(leg_target(step, i % LEGS)?)?,
+        }
+    } else if index < TAM_NO_STEP {
+        let i = index - FROM_HOP1ZUO1;
+        PureMove_::NonTamMoveFromHopZuo {
+            color: color_from_index(u8::try_from(i / SQUARES / 10).ok()?)?,
+            prof: prof_from_index(u8::try_from(i / SQUARES % 10).ok()?)?,
+            dest: c(square(i % SQUARES)?)?,
+        }
+    } else if index < TAM_STEPS_DURING_FORMER {
+        let i = index - TAM_NO_STEP;
+        let src = square(i / 8 / 8)?;
+        let first_dest = n(src, i / 8 % 8)?;
+        let second_dest = n(first_dest, i % 8)?;
+        PureMove_::TamMoveNoStep {
+            src: c(src)?,
+            first_dest: c(first_dest)?,
+            second_dest: c(second_dest)?,
+        }
+    } else if index < TAM_STEPS_DURING_LATTER {
+        let i = index - TAM_STEPS_DURING_FORMER;
+        let src = square(i / 8 / 8 / 8)?;
+        let step = n(src, i / 8 / 8 % 8)?;
+        let first_dest = n(step, i / 8 % 8)?;
+        let second_dest = n(first_dest, i % 8)?;
+        PureMove_::TamMoveStepsDuringFormer {
+            src: c(src)?,
+            step: c(step)?,
+            first_dest: c(first_dest)?,
+            second_dest: c(second_dest)?,
+        }
+    } else if index < POLICY_SIZE {
+        let i = index - TAM_STEPS_DURING_LATTER;
+        let src = square(i / 8 / 8 / 8)?;
+        let first_dest = n(src, i / 8 / 8 % 8)?;
+        let step = n(first_dest, i / 8 % 8)?;
+        let second_dest = n(step, i % 8)?;
+        PureMove_::TamMoveStepsDuringLatter {
+            src: c(src)?,
+            step: c(step)?,
+            first_dest: c(first_dest)?,
+            second_dest: c(second_dest)?,
+        }
+    } else {
+        return None;
+    })
+}
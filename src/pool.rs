@@ -0,0 +1,62 @@
+use crate::IsAbsoluteField;
+
+/// A pool of fields whose allocations are recycled instead of being freed
+/// ／確保した領域を解放せずに再利用する `Field` のプール
+///
+/// [`FieldPool::acquire_copy_of`] copies into a recycled field with [`Clone::clone_from`],
+/// so this pays off when the field type overrides `clone_from` to reuse its buffers.
+/// ／[`FieldPool::acquire_copy_of`] は [`Clone::clone_from`] で再利用する `Field` に複製するので、
+/// `Field` の型がバッファを再利用するよう `clone_from` を上書きしている場合に効果がある。
+#[derive(Clone, Debug, Default)]
+pub struct FieldPool<F> {
+    free: Vec<F>,
+}
+
+impl<F: IsAbsoluteField> FieldPool<F> {
+    /// An empty pool
+    /// ／空のプール
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { free: vec![] }
+    }
+
+    /// Takes a field out of the pool, or creates an empty one if the pool is empty
+    /// ／プールから `Field` を取り出す。プールが空なら空の `Field` を作る。
+    ///
+    /// A recycled field holds whatever it held when released.
+    /// ／再利用される `Field` は、返却されたときの中身のままである。
+    pub fn acquire(&mut self) -> F {
+        self.free.pop().unwrap_or_else(F::empty)
+    }
+
+    /// Takes a field out of the pool and makes it a copy of `field`
+    /// ／プールから `Field` を取り出し、`field` の複製にする
+    pub fn acquire_copy_of(&mut self, field: &F) -> F
+    where
+        F: Clone,
+    {
+        let mut copy = self.acquire();
+        copy.clone_from(field);
+        copy
+    }
+
+    /// Returns a field to the pool
+    /// ／`Field` をプールに返す
+    pub fn release(&mut self, field: F) {
+        self.free.push(field);
+    }
+
+    /// The number of fields waiting in the pool
+    /// ／プールで待機している `Field` の数
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Whether no field is waiting in the pool
+    /// ／プールで待機している `Field` がないかどうか
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.free.is_empty()
+    }
+}
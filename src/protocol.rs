@@ -0,0 +1,243 @@
+use crate::{
+    apply_move_with_config, parse_move, CetkaikRepresentation, IsAbsoluteField, MoveNotation,
+    PureMove, RuleConfig, SearchLimits,
+};
+use cetkaik_fundamental::AbsoluteSide;
+use std::io::{BufRead, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+/// The limits given by a `go` command; a limit left as `None` is up to the engine
+/// ／`go` コマンドで与えられる制限。`None` の制限はエンジンに任される。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GoParams {
+    /// `go depth N`: the depth to search to, in plies／探索する深さ（手数）
+    pub depth: Option<u32>,
+    /// `go nodes N`: the number of nodes to search／探索するノード数
+    pub nodes: Option<u64>,
+    /// `go movetime MS`: the time to spend on the move／その手に費やす時間
+    pub movetime: Option<Duration>,
+}
+
+/// A trait that signifies that you can drive it through [`run_engine`]
+/// ／[`run_engine`] を通じて動かせる型を表すトレイト
+pub trait Engine<R: CetkaikRepresentation> {
+    /// The name reported in reply to `cetkaik`
+    /// ／`cetkaik` に対して報告する名前
+    fn name(&self) -> String;
+
+    /// The rule configuration under which the moves of `position` are applied
+    /// ／`position` の指し手を適用する際のルール設定
+    fn config(&self) -> RuleConfig {
+        RuleConfig::default()
+    }
+
+    /// Forgets whatever the engine learned from the previous game, in reply to `newgame`
+    /// ／`newgame` に応じて、前のゲームから得た情報を忘れる
+    fn new_game(&mut self) {}
+
+    /// The move `whose_turn` should make on `field`, or `None` if there is none
+    /// ／`field` において `whose_turn` が指すべき手。指せる手がなければ `None`
+    fn best_move(
+        &mut self,
+        field: &R::AbsoluteField,
+        whose_turn: AbsoluteSide,
+        params: &GoParams,
+    ) -> Option<PureMove<R>>;
+
+    /// The move `whose_turn` should make on `field` within `limits`, in reply to `go`
+    /// ／`go` に応じて、`limits` の範囲内で `field` において `whose_turn` が指すべき手
+    ///
+    /// By default this calls [`Engine::best_move`] with the limits as given by `go`.
+    /// An engine that honors `stop` overrides this and watches `limits` through [`crate::SearchController`].
+    /// ／既定では `go` で与えられた形の制限で [`Engine::best_move`] を呼ぶ。
+    /// `stop` に従うエンジンはこれを上書きし、[`crate::SearchController`] を通じて `limits` を見張る。
+    fn go(
+        &mut self,
+        field: &R::AbsoluteField,
+        whose_turn: AbsoluteSide,
+        limits: &SearchLimits,
+    ) -> Option<PureMove<R>> {
+        self.best_move(field, whose_turn, &limits.params())
+    }
+}
+
+/// The field and the side to move set up by `position`
+struct Position<R: CetkaikRepresentation> {
+    field: R::AbsoluteField,
+    whose_turn: AbsoluteSide,
+}
+
+impl<R: CetkaikRepresentation> Position<R> {
+    fn initial(whose_turn: AbsoluteSide) -> Self {
+        Self {
+            field: R::AbsoluteField::yhuap_initial(),
+            whose_turn,
+        }
+    }
+
+    /// Parses the arguments of `position`: `startpos A|IA [moves <move>...]`
+    fn parse<'a>(
+        mut args: impl Iterator<Item = &'a str>,
+        config: RuleConfig,
+    ) -> Result<Self, String> {
+        if args.next() != Some("startpos") {
+            return Err("expected `startpos`".to_owned());
+        }
+        let first_mover = args
+            .next()
+            .and_then(|side| side.parse().ok())
+            .ok_or("expected the side to move first, `A` or `IA`")?;
+        let mut position = Self::initial(first_mover);
+        match args.next() {
+            None => return Ok(position),
+            Some("moves") => {}
+            Some(token) => return Err(format!("expected `moves`, found `{token}`")),
+        }
+        for token in args {
+            let mv = parse_move::<R>(token).ok_or_else(|| format!("invalid move `{token}`"))?;
+            position.field =
+                apply_move_with_config::<R>(&position.field, mv, position.whose_turn, &config)
+                    .map_err(|e| format!("cannot apply `{token}`: {e}"))?;
+            position.whose_turn = !position.whose_turn;
+        }
+        Ok(position)
+    }
+}
+
+/// Parses the arguments of `go`: any of `depth N`, `nodes N` and `movetime MS`
+fn parse_go<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<GoParams, String> {
+    let mut params = GoParams::default();
+    while let Some(key) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("expected a value for `{key}`"))?;
+        let invalid = |_| format!("invalid value `{value}` for `{key}`");
+        match key {
+            "depth" => params.depth = Some(value.parse().map_err(invalid)?),
+            "nodes" => params.nodes = Some(value.parse().map_err(invalid)?),
+            "movetime" => {
+                params.movetime = Some(Duration::from_millis(value.parse().map_err(invalid)?));
+            }
+            _ => return Err(format!("unknown limit `{key}`")),
+        }
+    }
+    Ok(params)
+}
+
+/// Reads the lines of `input` and passes them on to `lines`, setting `stop` at once on `stop` and `quit` and clearing it on `go`
+///
+/// This runs beside the main loop, so that `stop` reaches a search that is still running.
+fn read_lines(
+    input: impl BufRead,
+    stop: &AtomicBool,
+    lines: &mpsc::Sender<std::io::Result<String>>,
+) {
+    for line in input.lines() {
+        let command = line
+            .as_ref()
+            .ok()
+            .and_then(|line| line.split_whitespace().next());
+        match command {
+            Some("go") => stop.store(false, Ordering::Relaxed),
+            Some("stop" | "quit") => stop.store(true, Ordering::Relaxed),
+            _ => {}
+        }
+        let quit = command == Some("quit") || line.is_err();
+        if lines.send(line).is_err() || quit {
+            break;
+        }
+    }
+}
+
+/// Talks to a GUI through a line-based text protocol in the style of UCI/USI, until `quit` or the end of `input`
+/// ／UCI/USI 風の行ベースのテキストプロトコルを通じて、`quit` または `input` の終わりまで GUI と対話する
+///
+/// The commands are:
+/// - `cetkaik`: replies `id name <name>` and then `cetkaikok`
+/// - `isready`: replies `readyok`
+/// - `newgame`: calls [`Engine::new_game`] and goes back to the initial position with `IASide` to move
+/// - `position startpos A|IA [moves <move>...]`: the yhuap initial position with the given side to move first,
+///   followed by moves written in [`MoveNotation`] and applied with [`apply_move_with_config`] under [`Engine::config`]
+/// - `go [depth N] [nodes N] [movetime MS]`: calls [`Engine::go`] and replies `bestmove <move>` in [`MoveNotation`], or `bestmove none`
+/// - `stop`: sets the stop flag of the [`SearchLimits`] of the running `go`, so that the engine replies early
+/// - `quit`: returns
+///
+/// A command that cannot be understood is answered by `info string <reason>` and otherwise ignored.
+/// Since the stick casts are not part of a move, every move of `position` is applied as if its casts succeeded.
+/// `input` is read on a thread of its own so that `stop` can arrive while the engine is thinking; `BufReader::new(std::io::stdin())` can be passed as `input`.
+/// The thread is not joined: when writing to `output` fails, the error is returned at once,
+/// and the thread ends by itself at the next line or the end of `input`.
+/// ／コマンドは次の通り。
+/// - `cetkaik`：`id name <名前>` に続けて `cetkaikok` を返す
+/// - `isready`：`readyok` を返す
+/// - `newgame`：[`Engine::new_game`] を呼び、`IASide` が手番の初期局面に戻る
+/// - `position startpos A|IA [moves <指し手>...]`：与えられた陣営が先手である官定の初期局面から、
+///   [`MoveNotation`] で書かれた指し手を [`Engine::config`] のもとで [`apply_move_with_config`] により適用した局面
+/// - `go [depth N] [nodes N] [movetime MS]`：[`Engine::go`] を呼び、[`MoveNotation`] で書いた `bestmove <指し手>` または `bestmove none` を返す
+/// - `stop`：実行中の `go` の [`SearchLimits`] のフラグを立て、エンジンが早めに答えるようにする
+/// - `quit`：終了する
+///
+/// 解釈できないコマンドには `info string <理由>` を返し、それ以外は無視する。
+/// 投げ棒の結果は指し手に含まれないので、`position` の指し手はすべて投げ棒判定が成功したものとして適用する。
+/// エンジンの思考中にも `stop` が届くよう、`input` は専用のスレッドで読む。`input` には `BufReader::new(std::io::stdin())` を渡せる。
+/// このスレッドは待ち合わせないので、`output` への書き込みが失敗すればエラーをすぐに返し、スレッドは `input` の次の行か終わりで自ら終了する。
+/// # Errors
+/// Fails if reading from `input` or writing to `output` fails.
+/// ／`input` からの読み込みか `output` への書き込みが失敗したらエラー。
+pub fn run_engine<R: CetkaikRepresentation, E: Engine<R>>(
+    engine: &mut E,
+    input: impl BufRead + Send + 'static,
+    mut output: impl Write,
+) -> std::io::Result<()> {
+    let stop = Arc::new(AtomicBool::new(false));
+    let (sender, lines) = mpsc::channel();
+    let reader_stop = Arc::clone(&stop);
+    // not joined, so that an error can be returned while the reader is still blocked on `input`
+    std::thread::spawn(move || read_lines(input, &reader_stop, &sender));
+    let mut position = Position::<R>::initial(AbsoluteSide::IASide);
+    for line in lines {
+        let line = line?;
+        let mut tokens = line.split_whitespace();
+        let reply = match tokens.next() {
+            None | Some("stop") => Ok(()),
+            Some("cetkaik") => {
+                writeln!(output, "id name {}", engine.name())?;
+                writeln!(output, "cetkaikok")?;
+                Ok(())
+            }
+            Some("isready") => {
+                writeln!(output, "readyok")?;
+                Ok(())
+            }
+            Some("newgame") => {
+                engine.new_game();
+                position = Position::initial(AbsoluteSide::IASide);
+                Ok(())
+            }
+            Some("position") => Position::parse(tokens, engine.config()).map(|new| position = new),
+            Some("go") => match parse_go(tokens) {
+                Ok(params) => {
+                    let limits =
+                        SearchLimits::from_params(&params).with_stop_flag(Arc::clone(&stop));
+                    match engine.go(&position.field, position.whose_turn, &limits) {
+                        Some(mv) => {
+                            writeln!(output, "bestmove {}", MoveNotation::<R>::new(mv))?;
+                        }
+                        None => writeln!(output, "bestmove none")?,
+                    }
+                    Ok(())
+                }
+                Err(reason) => Err(reason),
+            },
+            Some("quit") => break,
+            Some(command) => Err(format!("unknown command `{command}`")),
+        };
+        if let Err(reason) = reply {
+            writeln!(output, "info string {reason}")?;
+        }
+        output.flush()?;
+    }
+    Ok(())
+}
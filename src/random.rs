@@ -0,0 +1,78 @@
+use crate::movegen::play_out;
+use crate::{CetkaikRepresentation, IsAbsoluteField, IsMoveGenerator, PureMove};
+use cetkaik_fundamental::AbsoluteSide;
+use rand::Rng;
+
+/// Picks one of the moves available to `whose_turn` uniformly at random, or `None` if there is none
+/// ／`whose_turn` が指せる手から一様ランダムにひとつ選ぶ。指せる手がなければ `None`。
+pub fn random_legal_move<R, G, Rn>(
+    rules: &G,
+    field: &R::AbsoluteField,
+    whose_turn: AbsoluteSide,
+    rng: &mut Rn,
+) -> Option<PureMove<R>>
+where
+    R: CetkaikRepresentation,
+    G: IsMoveGenerator<R>,
+    Rn: Rng + ?Sized,
+{
+    let moves = rules.all_moves(field, whose_turn);
+    if moves.is_empty() {
+        None
+    } else {
+        Some(moves[rng.random_range(0..moves.len())])
+    }
+}
+
+/// Plays at most `max_plies` random moves from `field`, returning the resulting field, the side to move next and the moves played
+/// ／`field` からランダムな手を最大 `max_plies` 手指し、得られた `Field`、次の手番の陣営、指した手を返す
+///
+/// The playout stops early if the side to move has no move.
+/// ／手番の陣営が指せる手がなくなった時点で打ち切る。
+pub fn random_playout<R, G, Rn>(
+    rules: &G,
+    field: R::AbsoluteField,
+    whose_turn: AbsoluteSide,
+    max_plies: usize,
+    rng: &mut Rn,
+) -> (R::AbsoluteField, AbsoluteSide, Vec<PureMove<R>>)
+where
+    R: CetkaikRepresentation,
+    G: IsMoveGenerator<R>,
+    Rn: Rng + ?Sized,
+{
+    play_out::<R, G>(rules, field, whose_turn, max_plies, |n| {
+        rng.random_range(0..n)
+    })
+}
+
+/// Generates a random position reachable from the yhuap initial arrangement within `max_plies` moves, together with the side to move
+/// ／yhuap の初期配置から `max_plies` 手以内で到達できるランダムな局面を、手番の陣営とともに生成する
+///
+/// Since the position is obtained by actually playing moves, it always respects the piece census.
+/// ／実際に手を指して得られる局面なので、駒の数は常に正しい。
+pub fn random_position<R, G, Rn>(
+    rules: &G,
+    max_plies: usize,
+    rng: &mut Rn,
+) -> (R::AbsoluteField, AbsoluteSide)
+where
+    R: CetkaikRepresentation,
+    G: IsMoveGenerator<R>,
+    Rn: Rng + ?Sized,
+{
+    let first_mover = if rng.random() {
+        AbsoluteSide::ASide
+    } else {
+        AbsoluteSide::IASide
+    };
+    let plies = rng.random_range(0..=max_plies);
+    let (field, whose_turn, _) = random_playout::<R, G, Rn>(
+        rules,
+        R::AbsoluteField::yhuap_initial(),
+        first_mover,
+        plies,
+        rng,
+    );
+    (field, whose_turn)
+}
@@ -0,0 +1,196 @@
+use crate::{
+    apply_move_with_ciurl_and_config, apply_move_with_config, is_legal, CetkaikRepresentation,
+    FixedCiurl, IllegalMove, RuleConfig,
+};
+use cetkaik_fundamental::{AbsoluteSide, PureMove_};
+
+/// A move as it was played, together with the sticks cast for it
+/// ／実際に指された指し手と、そのために投げられた投げ棒
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PlayedMove<C> {
+    /// the move／指し手
+    pub mv: PureMove_<C>,
+    /// how many sticks fell face up, or `None` if no cast was recorded, in which case the cast is assumed to have succeeded
+    /// ／表になった投げ棒の本数。記録されていなければ `None` であり、その場合は判定が成功したものとみなす
+    pub ciurl: Option<u8>,
+}
+
+/// A record of a game: where it started, who moved first, the rules followed and the moves played
+/// ／ゲームの記録。開始局面、先手、従ったルール、指された手からなる。
+pub struct GameRecord<R: CetkaikRepresentation> {
+    /// the field the game started from／ゲームの開始時の `Field`
+    pub initial: R::AbsoluteField,
+    /// the side that moved first／先手の陣営
+    pub first_mover: AbsoluteSide,
+    /// the rule configuration the game followed／ゲームが従ったルール設定
+    pub config: RuleConfig,
+    /// the moves played, in order／指された手。順に並ぶ。
+    pub moves: Vec<PlayedMove<R::AbsoluteCoord>>,
+}
+
+impl<R: CetkaikRepresentation> Clone for GameRecord<R> {
+    fn clone(&self) -> Self {
+        Self {
+            initial: self.initial.clone(),
+            first_mover: self.first_mover,
+            config: self.config,
+            moves: self.moves.clone(),
+        }
+    }
+}
+
+impl<R: CetkaikRepresentation> core::fmt::Debug for GameRecord<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("GameRecord")
+            .field("initial", &self.initial)
+            .field("first_mover", &self.first_mover)
+            .field("config", &self.config)
+            .field("moves", &self.moves)
+            .finish()
+    }
+}
+
+impl<R: CetkaikRepresentation> PartialEq for GameRecord<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.initial == other.initial
+            && self.first_mover == other.first_mover
+            && self.config == other.config
+            && self.moves == other.moves
+    }
+}
+
+impl<R: CetkaikRepresentation> GameRecord<R> {
+    /// A record with no moves yet
+    /// ／まだ手が指されていない記録
+    pub const fn new(
+        initial: R::AbsoluteField,
+        first_mover: AbsoluteSide,
+        config: RuleConfig,
+    ) -> Self {
+        Self {
+            initial,
+            first_mover,
+            config,
+            moves: vec![],
+        }
+    }
+
+    /// Appends a move, with the number of sticks that fell face up if any were cast
+    /// ／指し手を末尾に加える。投げ棒を投げたなら、表になった本数も添える。
+    pub fn push(&mut self, mv: PureMove_<R::AbsoluteCoord>, ciurl: Option<u8>) {
+        self.moves.push(PlayedMove { mv, ciurl });
+    }
+
+    /// Lazily replays the game, yielding the field after each move
+    /// ／ゲームを遅延的に再生し、各手の後の `Field` を返す
+    ///
+    /// Each move is checked by [`is_legal`] under [`GameRecord::config`] before it is applied.
+    /// A move whose sticks were recorded is applied as in [`apply_move_with_ciurl_and_config`], and any other as in [`apply_move_with_config`].
+    /// The first illegal move is reported together with its index, after which the iterator ends.
+    /// ／各手は適用される前に [`GameRecord::config`] のもとで [`is_legal`] により検査される。
+    /// 投げ棒が記録された手は [`apply_move_with_ciurl_and_config`] と同様に、それ以外は [`apply_move_with_config`] と同様に適用される。
+    /// 最初の不正な手はその番号とともに報告され、その後イテレータは終わる。
+    pub fn replay(&self) -> ReplayIter<'_, R> {
+        ReplayIter {
+            record: self,
+            field: Some(self.initial.clone()),
+            whose_turn: self.first_mover,
+            index: 0,
+        }
+    }
+}
+
+/// The first illegal move found by [`GameRecord::replay`]
+/// ／[`GameRecord::replay`] が見つけた最初の不正な手
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ReplayError {
+    /// the index of the move in [`GameRecord::moves`]／[`GameRecord::moves`] における指し手の番号
+    pub index: usize,
+    /// why the move is illegal／指し手が不正である理由
+    pub reason: IllegalMove,
+}
+
+impl core::fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "move {} is illegal: {}", self.index, self.reason)
+    }
+}
+
+impl std::error::Error for ReplayError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.reason)
+    }
+}
+
+/// The iterator returned by [`GameRecord::replay`]
+/// ／[`GameRecord::replay`] が返すイテレータ
+pub struct ReplayIter<'a, R: CetkaikRepresentation> {
+    record: &'a GameRecord<R>,
+    /// `None` once an illegal move has been reported
+    field: Option<R::AbsoluteField>,
+    whose_turn: AbsoluteSide,
+    index: usize,
+}
+
+impl<R: CetkaikRepresentation> Clone for ReplayIter<'_, R> {
+    fn clone(&self) -> Self {
+        Self {
+            record: self.record,
+            field: self.field.clone(),
+            whose_turn: self.whose_turn,
+            index: self.index,
+        }
+    }
+}
+
+impl<R: CetkaikRepresentation> core::fmt::Debug for ReplayIter<'_, R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ReplayIter")
+            .field("field", &self.field)
+            .field("whose_turn", &self.whose_turn)
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: CetkaikRepresentation> Iterator for ReplayIter<'_, R> {
+    type Item = Result<R::AbsoluteField, ReplayError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let field = self.field.as_ref()?;
+        let PlayedMove { mv, ciurl } = *self.record.moves.get(self.index)?;
+        let config = &self.record.config;
+        if let Err(reason) = is_legal::<R>(field, mv, self.whose_turn, config) {
+            self.field = None;
+            return Some(Err(ReplayError {
+                index: self.index,
+                reason,
+            }));
+        }
+        let next = match ciurl {
+            Some(ciurl) => apply_move_with_ciurl_and_config::<R, _>(
+                field,
+                mv,
+                self.whose_turn,
+                &mut FixedCiurl(ciurl),
+                config,
+            ),
+            None => apply_move_with_config::<R>(field, mv, self.whose_turn, config),
+        }
+        .expect("a legal move can be applied");
+        self.field = Some(next.clone());
+        self.whose_turn = !self.whose_turn;
+        self.index += 1;
+        Some(Ok(next))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.field.is_none() {
+            return (0, Some(0));
+        }
+        let rest = self.record.moves.len() - self.index;
+        (usize::from(rest > 0), Some(rest))
+    }
+}
+
+impl<R: CetkaikRepresentation> core::iter::FusedIterator for ReplayIter<'_, R> {}
@@ -0,0 +1,251 @@
+use crate::{CanonicalPiece, CetkaikRepresentation, IsBoard};
+
+/// An operation on a board, as journaled by [`Recorded`]
+/// ／[`Recorded`] が記録する、盤面への操作
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BoardOp<C, P> {
+    /// [`IsBoard::put`]
+    Put {
+        /// the square／マス
+        coord: C,
+        /// the piece, or `None`／駒、または `None`
+        piece: Option<P>,
+    },
+    /// [`IsBoard::pop`]
+    Pop {
+        /// the square／マス
+        coord: C,
+    },
+    /// [`IsBoard::mov`]
+    Mov {
+        /// the origin／移動元
+        from: C,
+        /// the destination／移動先
+        to: C,
+    },
+}
+
+impl<C: Copy, P: Copy> BoardOp<C, P> {
+    /// Performs the operation on `board`
+    /// ／`board` に対して操作を行う
+    pub fn apply<B: IsBoard<Coord = C, PieceWithSide = P>>(self, board: &mut B) {
+        match self {
+            Self::Put { coord, piece } => board.put(coord, piece),
+            Self::Pop { coord } => {
+                board.pop(coord);
+            }
+            Self::Mov { from, to } => board.mov(from, to),
+        }
+    }
+}
+
+impl BoardOp<u8, CanonicalPiece> {
+    /// Converts an operation written in the representation `R` into one written in canonical indices and [`CanonicalPiece`]
+    /// ／表現 `R` で書かれた操作を、正準な番号と [`CanonicalPiece`] で書かれた操作に変換する
+    pub fn from_representation<R: CetkaikRepresentation>(
+        op: BoardOp<R::AbsoluteCoord, R::AbsolutePiece>,
+    ) -> Self {
+        match op {
+            BoardOp::Put { coord, piece } => Self::Put {
+                coord: R::coord_to_index(coord),
+                piece: piece.map(CanonicalPiece::from_piece),
+            },
+            BoardOp::Pop { coord } => Self::Pop {
+                coord: R::coord_to_index(coord),
+            },
+            BoardOp::Mov { from, to } => Self::Mov {
+                from: R::coord_to_index(from),
+                to: R::coord_to_index(to),
+            },
+        }
+    }
+
+    /// Converts into an operation written in the representation `R`, or `None` if an index is 81 or more
+    /// ／表現 `R` で書かれた操作に変換する。番号が 81 以上なら `None`
+    #[must_use]
+    pub fn to_representation<R: CetkaikRepresentation>(
+        self,
+    ) -> Option<BoardOp<R::AbsoluteCoord, R::AbsolutePiece>> {
+        Some(match self {
+            Self::Put { coord, piece } => BoardOp::Put {
+                coord: R::index_to_coord(coord)?,
+                piece: piece.map(CanonicalPiece::to_piece::<R>),
+            },
+            Self::Pop { coord } => BoardOp::Pop {
+                coord: R::index_to_coord(coord)?,
+            },
+            Self::Mov { from, to } => BoardOp::Mov {
+                from: R::index_to_coord(from)?,
+                to: R::index_to_coord(to)?,
+            },
+        })
+    }
+}
+
+/// Serializes a journal written in canonical indices into three bytes per operation
+/// ／正準な番号で書かれた記録を、操作ひとつにつき 3 バイトで書き出す
+///
+/// The first byte is 0 for putting `None`, 1 for putting a piece, 2 for popping and 3 for moving.
+/// The second byte is the square, or the origin of a move; the third is the code of the piece put
+/// (0 for Tam2 and `1 + 20 * side + 10 * color + prof` otherwise, as in [`crate::planes`]) or the destination of a move, and 0 otherwise.
+/// ／1 バイト目は `None` を置くなら 0、駒を置くなら 1、取り除くなら 2、移動なら 3。
+/// 2 バイト目はマス、または移動元。3 バイト目は置く駒の番号（[`crate::planes`] と同様に、皇なら 0、それ以外は `1 + 20 * side + 10 * color + prof`）または移動先で、それ以外の場合は 0。
+#[must_use]
+pub fn encode_journal(journal: &[BoardOp<u8, CanonicalPiece>]) -> Vec<u8> {
+    journal
+        .iter()
+        .flat_map(|op| match *op {
+            BoardOp::Put { coord, piece: None } => [0, coord, 0],
+            BoardOp::Put {
+                coord,
+                piece: Some(piece),
+            } => [1, coord, piece.code()],
+            BoardOp::Pop { coord } => [2, coord, 0],
+            BoardOp::Mov { from, to } => [3, from, to],
+        })
+        .collect()
+}
+
+/// The inverse of [`encode_journal`]; `None` if the bytes are malformed
+/// ／[`encode_journal`] の逆。バイト列が不正なら `None`
+#[must_use]
+pub fn decode_journal(bytes: &[u8]) -> Option<Vec<BoardOp<u8, CanonicalPiece>>> {
+    if !bytes.len().is_multiple_of(3) {
+        return None;
+    }
+    bytes
+        .chunks_exact(3)
+        .map(|op| match *op {
+            [0, coord, 0] => Some(BoardOp::Put { coord, piece: None }),
+            [1, coord, code] => Some(BoardOp::Put {
+                coord,
+                piece: Some(CanonicalPiece::from_code(code)?),
+            }),
+            [2, coord, 0] => Some(BoardOp::Pop { coord }),
+            [3, from, to] => Some(BoardOp::Mov { from, to }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Replays a journal written in canonical indices onto a board of the representation `R`
+/// ／正準な番号で書かれた記録を、表現 `R` の盤面の上で再生する
+/// # Errors
+/// Fails, leaving the operations before the offending one applied, if an index is 81 or more.
+/// ／番号が 81 以上であれば失敗する。その場合、それより前の操作は適用されたままになる。
+pub fn replay<R: CetkaikRepresentation>(
+    journal: &[BoardOp<u8, CanonicalPiece>],
+    board: &mut R::AbsoluteBoard,
+) -> Result<(), &'static str> {
+    for op in journal {
+        op.to_representation::<R>()
+            .ok_or("The journal contains an index of 81 or more")?
+            .apply(board);
+    }
+    Ok(())
+}
+
+/// The operations journaled on a board of type `B`
+/// ／型 `B` の盤面に対して記録された操作
+pub type Journal<B> = Vec<BoardOp<<B as IsBoard>::Coord, <B as IsBoard>::PieceWithSide>>;
+
+/// A board that forwards every operation to `B` while journaling it
+/// ／すべての操作を `B` に転送しつつ記録する盤面
+pub struct Recorded<B: IsBoard> {
+    board: B,
+    journal: Journal<B>,
+}
+
+impl<B: IsBoard + Clone> Clone for Recorded<B> {
+    fn clone(&self) -> Self {
+        Self {
+            board: self.board.clone(),
+            journal: self.journal.clone(),
+        }
+    }
+}
+
+impl<B: IsBoard + core::fmt::Debug> core::fmt::Debug for Recorded<B>
+where
+    B::PieceWithSide: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Recorded")
+            .field("board", &self.board)
+            .field("journal", &self.journal)
+            .finish()
+    }
+}
+
+impl<B: IsBoard> Recorded<B> {
+    /// Starts journaling the operations on `board`
+    /// ／`board` への操作の記録を始める
+    pub const fn new(board: B) -> Self {
+        Self {
+            board,
+            journal: vec![],
+        }
+    }
+
+    /// The board being journaled
+    /// ／記録されている盤面
+    pub const fn get_ref(&self) -> &B {
+        &self.board
+    }
+
+    /// The operations performed so far
+    /// ／これまでに行われた操作
+    pub fn journal(&self) -> &[BoardOp<B::Coord, B::PieceWithSide>] {
+        &self.journal
+    }
+
+    /// Stops journaling and returns the board and the journal
+    /// ／記録をやめて、盤面と記録を返す
+    pub fn into_parts(self) -> (B, Journal<B>) {
+        (self.board, self.journal)
+    }
+}
+
+impl<B: IsBoard> IsBoard for Recorded<B> {
+    type PieceWithSide = B::PieceWithSide;
+    type Coord = B::Coord;
+
+    fn peek(&self, c: Self::Coord) -> Option<Self::PieceWithSide> {
+        self.board.peek(c)
+    }
+    fn pop(&mut self, c: Self::Coord) -> Option<Self::PieceWithSide> {
+        self.journal.push(BoardOp::Pop { coord: c });
+        self.board.pop(c)
+    }
+    fn put(&mut self, c: Self::Coord, p: Option<Self::PieceWithSide>) {
+        self.journal.push(BoardOp::Put { coord: c, piece: p });
+        self.board.put(c, p);
+    }
+    fn assert_empty(&self, c: Self::Coord) {
+        self.board.assert_empty(c);
+    }
+    fn assert_occupied(&self, c: Self::Coord) {
+        self.board.assert_occupied(c);
+    }
+    fn mov(&mut self, from: Self::Coord, to: Self::Coord) {
+        self.journal.push(BoardOp::Mov { from, to });
+        self.board.mov(from, to);
+    }
+
+    type EmptySquaresIter = B::EmptySquaresIter;
+    fn empty_squares(&self) -> Self::EmptySquaresIter {
+        self.board.empty_squares()
+    }
+
+    type OccupiedSquaresIter = B::OccupiedSquaresIter;
+    fn occupied_squares(&self) -> Self::OccupiedSquaresIter {
+        self.board.occupied_squares()
+    }
+
+    fn empty_square_count(&self) -> usize {
+        self.board.empty_square_count()
+    }
+    fn occupied_count(&self) -> usize {
+        self.board.occupied_count()
+    }
+}
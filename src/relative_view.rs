@@ -0,0 +1,598 @@
+use crate::{CetkaikRepresentation, IsAbsoluteField, IsBoard, IsField, IsHop1Zuo1};
+use cetkaik_fundamental::{Color, Profession};
+
+/// A read-only view of an absolute field as seen from a perspective
+/// ／ある視点から見た、絶対座標の `Field` の読み取り専用ビュー
+///
+/// Coordinates and pieces are translated on every access, so the field itself is never copied.
+/// Use [`RelativeViewMut`] to pass the field to code written against [`IsField`] in relative coordinates,
+/// or [`AsRelative`] to pass just the board to code written against [`IsBoard`].
+/// ／アクセスのたびに座標と駒を変換するので、`Field` そのものはコピーされない。
+/// 相対座標の [`IsField`] に対して書かれたコードに `Field` を渡すには [`RelativeViewMut`] を、
+/// [`IsBoard`] に対して書かれたコードに盤面だけを渡すには [`AsRelative`] を用いる。
+pub struct RelativeView<'a, R: CetkaikRepresentation> {
+    field: &'a R::AbsoluteField,
+    perspective: R::Perspective,
+}
+
+impl<R: CetkaikRepresentation> Clone for RelativeView<'_, R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R: CetkaikRepresentation> Copy for RelativeView<'_, R> {}
+
+impl<R: CetkaikRepresentation> core::fmt::Debug for RelativeView<'_, R>
+where
+    R::Perspective: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RelativeView")
+            .field("field", self.field)
+            .field("perspective", &self.perspective)
+            .finish()
+    }
+}
+
+impl<'a, R: CetkaikRepresentation> RelativeView<'a, R> {
+    /// Views `field` from the perspective `p`
+    /// ／`field` を視点 `p` から見る
+    pub const fn new(field: &'a R::AbsoluteField, p: R::Perspective) -> Self {
+        Self {
+            field,
+            perspective: p,
+        }
+    }
+
+    /// The perspective from which the field is viewed
+    /// ／ビューの視点
+    pub const fn perspective(&self) -> R::Perspective {
+        self.perspective
+    }
+
+    /// The underlying absolute field
+    /// ／元になっている絶対座標の `Field`
+    pub const fn as_absolute(&self) -> &'a R::AbsoluteField {
+        self.field
+    }
+
+    /// Looks at the square designated by a relative coordinate, returning the piece as seen from the perspective
+    /// ／相対座標で指定されたマスを見て、そこにある駒を視点から見た相対的な駒として返す
+    pub fn peek(&self, c: R::RelativeCoord) -> Option<R::RelativePiece> {
+        self.field
+            .as_board()
+            .peek(R::to_absolute_coord(c, self.perspective))
+            .map(|piece| R::to_relative_piece(piece, self.perspective))
+    }
+
+    /// Whether the square designated by a relative coordinate is empty
+    /// ／相対座標で指定されたマスが空いているかどうか
+    pub fn is_empty(&self, c: R::RelativeCoord) -> bool {
+        self.field
+            .as_board()
+            .is_empty(R::to_absolute_coord(c, self.perspective))
+    }
+
+    /// Iterates over the empty squares in relative coordinates
+    /// ／空いているマスを相対座標で列挙する
+    pub fn empty_squares(
+        &self,
+    ) -> RelativizingIter<R, <R::AbsoluteBoard as IsBoard>::EmptySquaresIter> {
+        RelativizingIter::new(self.field.as_board().empty_squares(), self.perspective)
+    }
+
+    /// Iterates over the occupied squares (including the one with Tam2) in relative coordinates
+    /// ／駒（皇を含む）のあるマスを相対座標で列挙する
+    pub fn occupied_squares(
+        &self,
+    ) -> RelativizingIter<R, <R::AbsoluteBoard as IsBoard>::OccupiedSquaresIter> {
+        RelativizingIter::new(self.field.as_board().occupied_squares(), self.perspective)
+    }
+
+    /// Iterates over the hop1zuo1 of the relative side `side`
+    /// ／相対的な陣営 `side` の手駒を列挙する
+    pub fn hop1zuo1_of(
+        &self,
+        side: R::RelativeSide,
+    ) -> <<R::AbsoluteField as IsAbsoluteField>::Hop1Zuo1 as IsHop1Zuo1>::Iter {
+        self.field
+            .hop1zuo1_of(R::to_absolute_side(side, self.perspective))
+    }
+
+    /// Materializes the view into an owned relative field, cloning the underlying field
+    /// ／ビューを実体化し、相対座標の `Field` を得る。元の `Field` はクローンされる。
+    pub fn to_relative_field(&self) -> R::RelativeField {
+        R::to_relative_field(self.field.clone(), self.perspective)
+    }
+}
+
+/// A mutable view of an absolute field as seen from a perspective, which is an [`IsField`] and an [`IsBoard`] in relative terms
+/// ／ある視点から見た絶対座標の `Field` の可変なビュー。相対的な [`IsField`] および [`IsBoard`] として扱える。
+///
+/// Like [`RelativeView`], it translates coordinates and pieces on every access, but it borrows the field mutably,
+/// so that [`IsBoard::pop`] and [`IsBoard::put`] write through to it.
+/// The methods of [`IsField`] that return a new field return a view that owns the new absolute field; the borrowed field is left as it was.
+/// ／[`RelativeView`] と同様にアクセスのたびに座標と駒を変換するが、`Field` を可変で借用するので、
+/// [`IsBoard::pop`] や [`IsBoard::put`] は元の `Field` に書き込む。
+/// 新たな `Field` を返す [`IsField`] のメソッドは、新たな絶対座標の `Field` を所有するビューを返す。借用している `Field` は変化しない。
+pub struct RelativeViewMut<'a, R: CetkaikRepresentation> {
+    field: FieldHandle<'a, R::AbsoluteField>,
+    perspective: R::Perspective,
+}
+
+/// The field behind a [`RelativeViewMut`], which is borrowed unless the view was returned by a method of [`IsField`]
+enum FieldHandle<'a, F> {
+    Borrowed(&'a mut F),
+    Owned(F),
+}
+
+impl<R: CetkaikRepresentation> core::fmt::Debug for RelativeViewMut<'_, R>
+where
+    R::Perspective: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RelativeViewMut")
+            .field("field", self.as_absolute())
+            .field("perspective", &self.perspective)
+            .finish()
+    }
+}
+
+impl<'a, R: CetkaikRepresentation> RelativeViewMut<'a, R> {
+    /// Views `field` from the perspective `p`
+    /// ／`field` を視点 `p` から見る
+    pub const fn new(field: &'a mut R::AbsoluteField, p: R::Perspective) -> Self {
+        Self {
+            field: FieldHandle::Borrowed(field),
+            perspective: p,
+        }
+    }
+
+    const fn owned(field: R::AbsoluteField, p: R::Perspective) -> Self {
+        Self {
+            field: FieldHandle::Owned(field),
+            perspective: p,
+        }
+    }
+
+    /// The perspective from which the field is viewed
+    /// ／ビューの視点
+    pub const fn perspective(&self) -> R::Perspective {
+        self.perspective
+    }
+
+    /// The underlying absolute field
+    /// ／元になっている絶対座標の `Field`
+    pub const fn as_absolute(&self) -> &R::AbsoluteField {
+        match &self.field {
+            FieldHandle::Borrowed(field) => field,
+            FieldHandle::Owned(field) => field,
+        }
+    }
+
+    /// Mutably borrows the underlying absolute field
+    /// ／元になっている絶対座標の `Field` を可変で借用する
+    pub const fn as_absolute_mut(&mut self) -> &mut R::AbsoluteField {
+        match &mut self.field {
+            FieldHandle::Borrowed(field) => field,
+            FieldHandle::Owned(field) => field,
+        }
+    }
+
+    /// Takes the underlying absolute field out of a view returned by a method of [`IsField`], or clones the borrowed one
+    /// ／[`IsField`] のメソッドが返したビューからは元の絶対座標の `Field` を取り出し、借用したものであればクローンする
+    pub fn into_absolute(self) -> R::AbsoluteField {
+        match self.field {
+            FieldHandle::Borrowed(field) => field.clone(),
+            FieldHandle::Owned(field) => field,
+        }
+    }
+
+    /// Iterates over the hop1zuo1 of the relative side `side`
+    /// ／相対的な陣営 `side` の手駒を列挙する
+    pub fn hop1zuo1_of(
+        &self,
+        side: R::RelativeSide,
+    ) -> <<R::AbsoluteField as IsAbsoluteField>::Hop1Zuo1 as IsHop1Zuo1>::Iter {
+        self.as_absolute()
+            .hop1zuo1_of(R::to_absolute_side(side, self.perspective))
+    }
+}
+
+impl<R: CetkaikRepresentation> IsBoard for RelativeViewMut<'_, R>
+where
+    R::RelativeCoord: core::fmt::Debug,
+{
+    type PieceWithSide = R::RelativePiece;
+    type Coord = R::RelativeCoord;
+
+    fn peek(&self, c: Self::Coord) -> Option<Self::PieceWithSide> {
+        let p = self.perspective;
+        self.as_absolute()
+            .as_board()
+            .peek(R::to_absolute_coord(c, p))
+            .map(|piece| R::to_relative_piece(piece, p))
+    }
+    fn pop(&mut self, c: Self::Coord) -> Option<Self::PieceWithSide> {
+        let p = self.perspective;
+        self.as_absolute_mut()
+            .as_board_mut()
+            .pop(R::to_absolute_coord(c, p))
+            .map(|piece| R::to_relative_piece(piece, p))
+    }
+    fn put(&mut self, c: Self::Coord, piece: Option<Self::PieceWithSide>) {
+        let p = self.perspective;
+        self.as_absolute_mut().as_board_mut().put(
+            R::to_absolute_coord(c, p),
+            piece.map(|piece| R::to_absolute_piece(piece, p)),
+        );
+    }
+    fn is_empty(&self, c: Self::Coord) -> bool {
+        self.as_absolute()
+            .as_board()
+            .is_empty(R::to_absolute_coord(c, self.perspective))
+    }
+    fn mov(&mut self, from: Self::Coord, to: Self::Coord) {
+        let p = self.perspective;
+        self.as_absolute_mut()
+            .as_board_mut()
+            .mov(R::to_absolute_coord(from, p), R::to_absolute_coord(to, p));
+    }
+    fn mov_unchecked(&mut self, from: Self::Coord, to: Self::Coord) {
+        let p = self.perspective;
+        self.as_absolute_mut()
+            .as_board_mut()
+            .mov_unchecked(R::to_absolute_coord(from, p), R::to_absolute_coord(to, p));
+    }
+    fn swap(&mut self, a: Self::Coord, b: Self::Coord) {
+        let p = self.perspective;
+        self.as_absolute_mut()
+            .as_board_mut()
+            .swap(R::to_absolute_coord(a, p), R::to_absolute_coord(b, p));
+    }
+
+    type EmptySquaresIter = RelativizingIter<R, <R::AbsoluteBoard as IsBoard>::EmptySquaresIter>;
+    fn empty_squares(&self) -> Self::EmptySquaresIter {
+        RelativizingIter::new(
+            self.as_absolute().as_board().empty_squares(),
+            self.perspective,
+        )
+    }
+
+    type OccupiedSquaresIter =
+        RelativizingIter<R, <R::AbsoluteBoard as IsBoard>::OccupiedSquaresIter>;
+    fn occupied_squares(&self) -> Self::OccupiedSquaresIter {
+        RelativizingIter::new(
+            self.as_absolute().as_board().occupied_squares(),
+            self.perspective,
+        )
+    }
+
+    fn empty_square_count(&self) -> usize {
+        self.as_absolute().as_board().empty_square_count()
+    }
+    fn occupied_count(&self) -> usize {
+        self.as_absolute().as_board().occupied_count()
+    }
+}
+
+impl<R: CetkaikRepresentation> IsField for RelativeViewMut<'_, R>
+where
+    R::RelativeCoord: core::fmt::Debug,
+{
+    type Board = Self;
+    type Coord = R::RelativeCoord;
+    type PieceWithSide = R::RelativePiece;
+    type Side = R::RelativeSide;
+
+    fn move_nontam_piece_from_src_to_dest_while_taking_opponent_piece_if_needed(
+        &self,
+        from: Self::Coord,
+        to: Self::Coord,
+        whose_turn: Self::Side,
+    ) -> Result<Self, &'static str> {
+        let p = self.perspective;
+        self.as_absolute()
+            .move_nontam_piece_from_src_to_dest_while_taking_opponent_piece_if_needed(
+                R::to_absolute_coord(from, p),
+                R::to_absolute_coord(to, p),
+                R::to_absolute_side(whose_turn, p),
+            )
+            .map(|field| Self::owned(field, p))
+    }
+
+    fn search_from_hop1zuo1_and_parachute_at(
+        &self,
+        color: Color,
+        prof: Profession,
+        side: Self::Side,
+        dest: Self::Coord,
+    ) -> Option<Self> {
+        let p = self.perspective;
+        self.as_absolute()
+            .search_from_hop1zuo1_and_parachute_at(
+                color,
+                prof,
+                R::to_absolute_side(side, p),
+                R::to_absolute_coord(dest, p),
+            )
+            .map(|field| Self::owned(field, p))
+    }
+
+    fn as_board(&self) -> &Self {
+        self
+    }
+
+    fn as_board_mut(&mut self) -> &mut Self {
+        self
+    }
+}
+
+/// An iterator adapter that converts the absolute coordinates yielded by `I` into relative ones seen from a perspective
+/// ／`I` が返す絶対座標を、ある視点から見た相対座標に変換するイテレータアダプタ
+///
+/// It keeps the length, the fusedness and the double-endedness of `I`.
+/// ／`I` の長さ・尽きた後の振る舞い・両端からの読み進めを保つ。
+pub struct RelativizingIter<R: CetkaikRepresentation, I> {
+    iter: I,
+    perspective: R::Perspective,
+}
+
+impl<R: CetkaikRepresentation, I> RelativizingIter<R, I> {
+    /// Converts the coordinates of `iter` to the perspective `p`
+    /// ／`iter` の座標を視点 `p` のものに変換する
+    pub fn new(iter: impl IntoIterator<IntoIter = I>, p: R::Perspective) -> Self {
+        Self {
+            iter: iter.into_iter(),
+            perspective: p,
+        }
+    }
+
+    /// The perspective of the coordinates yielded
+    /// ／返す座標の視点
+    pub const fn perspective(&self) -> R::Perspective {
+        self.perspective
+    }
+
+    /// The underlying iterator
+    /// ／元になっているイテレータ
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<R: CetkaikRepresentation, I: Clone> Clone for RelativizingIter<R, I> {
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            perspective: self.perspective,
+        }
+    }
+}
+
+impl<R: CetkaikRepresentation, I: core::fmt::Debug> core::fmt::Debug for RelativizingIter<R, I>
+where
+    R::Perspective: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RelativizingIter")
+            .field("iter", &self.iter)
+            .field("perspective", &self.perspective)
+            .finish()
+    }
+}
+
+impl<R: CetkaikRepresentation, I: Iterator<Item = R::AbsoluteCoord>> Iterator
+    for RelativizingIter<R, I>
+{
+    type Item = R::RelativeCoord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let p = self.perspective;
+        self.iter.next().map(|c| R::to_relative_coord(c, p))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<R: CetkaikRepresentation, I: DoubleEndedIterator<Item = R::AbsoluteCoord>> DoubleEndedIterator
+    for RelativizingIter<R, I>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let p = self.perspective;
+        self.iter.next_back().map(|c| R::to_relative_coord(c, p))
+    }
+}
+
+impl<R: CetkaikRepresentation, I: ExactSizeIterator<Item = R::AbsoluteCoord>> ExactSizeIterator
+    for RelativizingIter<R, I>
+{
+}
+
+impl<R: CetkaikRepresentation, I: core::iter::FusedIterator<Item = R::AbsoluteCoord>>
+    core::iter::FusedIterator for RelativizingIter<R, I>
+{
+}
+
+/// An iterator adapter that converts the relative coordinates yielded by `I`, seen from a perspective, into absolute ones
+/// ／`I` が返す、ある視点から見た相対座標を、絶対座標に変換するイテレータアダプタ
+///
+/// It keeps the length, the fusedness and the double-endedness of `I`.
+/// ／`I` の長さ・尽きた後の振る舞い・両端からの読み進めを保つ。
+pub struct AbsolutizingIter<R: CetkaikRepresentation, I> {
+    iter: I,
+    perspective: R::Perspective,
+}
+
+impl<R: CetkaikRepresentation, I> AbsolutizingIter<R, I> {
+    /// Converts the coordinates of `iter`, seen from the perspective `p`, to absolute ones
+    /// ／視点 `p` から見た `iter` の座標を絶対座標に変換する
+    pub fn new(iter: impl IntoIterator<IntoIter = I>, p: R::Perspective) -> Self {
+        Self {
+            iter: iter.into_iter(),
+            perspective: p,
+        }
+    }
+
+    /// The perspective of the coordinates converted
+    /// ／変換する座標の視点
+    pub const fn perspective(&self) -> R::Perspective {
+        self.perspective
+    }
+
+    /// The underlying iterator
+    /// ／元になっているイテレータ
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<R: CetkaikRepresentation, I: Clone> Clone for AbsolutizingIter<R, I> {
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            perspective: self.perspective,
+        }
+    }
+}
+
+impl<R: CetkaikRepresentation, I: core::fmt::Debug> core::fmt::Debug for AbsolutizingIter<R, I>
+where
+    R::Perspective: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AbsolutizingIter")
+            .field("iter", &self.iter)
+            .field("perspective", &self.perspective)
+            .finish()
+    }
+}
+
+impl<R: CetkaikRepresentation, I: Iterator<Item = R::RelativeCoord>> Iterator
+    for AbsolutizingIter<R, I>
+{
+    type Item = R::AbsoluteCoord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let p = self.perspective;
+        self.iter.next().map(|c| R::to_absolute_coord(c, p))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<R: CetkaikRepresentation, I: DoubleEndedIterator<Item = R::RelativeCoord>> DoubleEndedIterator
+    for AbsolutizingIter<R, I>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let p = self.perspective;
+        self.iter.next_back().map(|c| R::to_absolute_coord(c, p))
+    }
+}
+
+impl<R: CetkaikRepresentation, I: ExactSizeIterator<Item = R::RelativeCoord>> ExactSizeIterator
+    for AbsolutizingIter<R, I>
+{
+}
+
+impl<R: CetkaikRepresentation, I: core::iter::FusedIterator<Item = R::RelativeCoord>>
+    core::iter::FusedIterator for AbsolutizingIter<R, I>
+{
+}
+
+/// An absolute board seen from a perspective, which is an [`IsBoard`] in relative coordinates and relative pieces
+/// ／ある視点から見た絶対座標の盤面。相対座標と相対的な駒による [`IsBoard`] として扱える。
+///
+/// Coordinates and pieces are translated on every access, so code written against the relative board
+/// can run directly on an absolute board, without building a relative field.
+/// The board is borrowed mutably so that [`IsBoard::pop`] and [`IsBoard::put`] write through to it;
+/// use [`RelativeView`] for a read-only view of a whole field.
+/// ／アクセスのたびに座標と駒を変換するので、相対座標の盤面に対して書かれたコードを、相対座標の `Field` を作らずに絶対座標の盤面の上で直接動かせる。
+/// [`IsBoard::pop`] や [`IsBoard::put`] が元の盤面に書き込めるよう、盤面は可変で借用する。
+/// `Field` 全体を読むだけなら [`RelativeView`] を用いる。
+pub struct AsRelative<'a, R: CetkaikRepresentation>(
+    pub &'a mut R::AbsoluteBoard,
+    pub R::Perspective,
+);
+
+impl<R: CetkaikRepresentation> core::fmt::Debug for AsRelative<'_, R>
+where
+    R::Perspective: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("AsRelative")
+            .field(&self.0)
+            .field(&self.1)
+            .finish()
+    }
+}
+
+impl<R: CetkaikRepresentation> IsBoard for AsRelative<'_, R>
+where
+    R::RelativeCoord: core::fmt::Debug,
+{
+    type PieceWithSide = R::RelativePiece;
+    type Coord = R::RelativeCoord;
+
+    fn peek(&self, c: Self::Coord) -> Option<Self::PieceWithSide> {
+        self.0
+            .peek(R::to_absolute_coord(c, self.1))
+            .map(|piece| R::to_relative_piece(piece, self.1))
+    }
+    fn pop(&mut self, c: Self::Coord) -> Option<Self::PieceWithSide> {
+        self.0
+            .pop(R::to_absolute_coord(c, self.1))
+            .map(|piece| R::to_relative_piece(piece, self.1))
+    }
+    fn put(&mut self, c: Self::Coord, p: Option<Self::PieceWithSide>) {
+        let perspective = self.1;
+        self.0.put(
+            R::to_absolute_coord(c, perspective),
+            p.map(|piece| R::to_absolute_piece(piece, perspective)),
+        );
+    }
+    fn is_empty(&self, c: Self::Coord) -> bool {
+        self.0.is_empty(R::to_absolute_coord(c, self.1))
+    }
+    fn mov(&mut self, from: Self::Coord, to: Self::Coord) {
+        self.0.mov(
+            R::to_absolute_coord(from, self.1),
+            R::to_absolute_coord(to, self.1),
+        );
+    }
+    fn mov_unchecked(&mut self, from: Self::Coord, to: Self::Coord) {
+        self.0.mov_unchecked(
+            R::to_absolute_coord(from, self.1),
+            R::to_absolute_coord(to, self.1),
+        );
+    }
+    fn swap(&mut self, a: Self::Coord, b: Self::Coord) {
+        self.0.swap(
+            R::to_absolute_coord(a, self.1),
+            R::to_absolute_coord(b, self.1),
+        );
+    }
+
+    type EmptySquaresIter = RelativizingIter<R, <R::AbsoluteBoard as IsBoard>::EmptySquaresIter>;
+    fn empty_squares(&self) -> Self::EmptySquaresIter {
+        RelativizingIter::new(self.0.empty_squares(), self.1)
+    }
+
+    type OccupiedSquaresIter =
+        RelativizingIter<R, <R::AbsoluteBoard as IsBoard>::OccupiedSquaresIter>;
+    fn occupied_squares(&self) -> Self::OccupiedSquaresIter {
+        RelativizingIter::new(self.0.occupied_squares(), self.1)
+    }
+
+    fn empty_square_count(&self) -> usize {
+        self.0.empty_square_count()
+    }
+    fn occupied_count(&self) -> usize {
+        self.0.occupied_count()
+    }
+}
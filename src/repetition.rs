@@ -0,0 +1,127 @@
+use crate::dynamic::{color_index, prof_index, sort_hop1zuo1};
+use crate::{CanonicalPiece, CetkaikRepresentation, IsAbsoluteField, IsBoard};
+use cetkaik_fundamental::{AbsoluteSide, ColorAndProf};
+use std::collections::HashMap;
+
+/// The finalizer of splitmix64, which spreads the bits of a small key over the whole word
+pub(crate) const fn mix(key: u64) -> u64 {
+    let mut x = key.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+pub(crate) const fn side_index(side: AbsoluteSide) -> u8 {
+    match side {
+        AbsoluteSide::ASide => 0,
+        AbsoluteSide::IASide => 1,
+    }
+}
+
+/// Mixed into the key when it is `IASide`'s turn
+pub(crate) const IA_SIDE_TO_MOVE: u64 = mix(1 << 40);
+
+/// The hash key for `piece` standing on `coord`
+/// ／`coord` に `piece` があることに対するハッシュのキー
+///
+/// This is what [`CetkaikRepresentation::canonical_hash`] mixes in for each occupied square.
+/// Moving, placing or removing a piece updates the hash by XOR-ing the keys of the squares before and after.
+/// ／[`CetkaikRepresentation::canonical_hash`] は駒のあるマスごとにこれを混ぜ込む。
+/// 駒を動かしたり置いたり取り除いたりしたときは、前後のマスのキーを XOR することでハッシュを更新できる。
+pub fn square_key<R: CetkaikRepresentation>(
+    coord: R::AbsoluteCoord,
+    piece: R::AbsolutePiece,
+) -> u64 {
+    mix(u64::from(R::coord_to_index(coord)) * 64
+        + u64::from(CanonicalPiece::from_piece(piece).code()))
+}
+
+/// The hash key for `side` holding `count` pieces like `piece` in its hop1zuo1
+/// ／`side` が手駒に `piece` と同じ駒を `count` 個持つことに対するハッシュのキー
+///
+/// This is what [`CetkaikRepresentation::canonical_hash`] mixes in for each kind of piece in a hop1zuo1.
+/// The key for a count of 0 is 0, so adding the `n`-th piece of a kind updates the hash by XOR-ing the keys for `n - 1` and `n`.
+/// ／[`CetkaikRepresentation::canonical_hash`] は手駒の駒の種類ごとにこれを混ぜ込む。
+/// 個数 0 のキーは 0 なので、ある種類の `n` 個目の駒を加えたときは、`n - 1` と `n` のキーを XOR することでハッシュを更新できる。
+#[must_use]
+pub const fn hop1zuo1_key(side: AbsoluteSide, piece: ColorAndProf, count: usize) -> u64 {
+    if count == 0 {
+        return 0;
+    }
+    let kind = 10 * color_index(piece.color) + prof_index(piece.prof);
+    mix((1 << 32) + (side_index(side) as u64 * 20 + kind as u64) * 64 + count as u64)
+}
+
+pub(crate) fn canonical_hash<R: CetkaikRepresentation>(field: &R::AbsoluteField) -> u64 {
+    let board = R::as_board_absolute(field);
+    let mut hash = board
+        .occupied_squares()
+        .filter_map(|c| Some(square_key::<R>(c, board.peek(c)?)))
+        .fold(0, |acc, h| acc ^ h);
+    for side in [AbsoluteSide::ASide, AbsoluteSide::IASide] {
+        let mut pieces = field.hop1zuo1_of(side).collect::<Vec<_>>();
+        sort_hop1zuo1(&mut pieces);
+        for run in pieces.chunk_by(|a, b| a == b) {
+            hash ^= hop1zuo1_key(side, run[0], run.len());
+        }
+    }
+    hash
+}
+
+pub(crate) fn repetition_key<R: CetkaikRepresentation>(
+    field: &R::AbsoluteField,
+    whose_turn: AbsoluteSide,
+) -> u64 {
+    match whose_turn {
+        AbsoluteSide::ASide => canonical_hash::<R>(field),
+        AbsoluteSide::IASide => canonical_hash::<R>(field) ^ IA_SIDE_TO_MOVE,
+    }
+}
+
+/// Counts how many times each position has occurred, keyed by [`CetkaikRepresentation::repetition_key`]
+/// ／各局面が何回現れたかを [`CetkaikRepresentation::repetition_key`] をキーとして数える
+#[derive(Clone, Debug, Default)]
+pub struct RepetitionTable {
+    counts: HashMap<u64, u32>,
+}
+
+impl RepetitionTable {
+    /// An empty table
+    /// ／空の表
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records an occurrence of `key` and returns how many times it has occurred, including this one
+    /// ／`key` の出現を記録し、今回を含めた出現回数を返す
+    pub fn record(&mut self, key: u64) -> u32 {
+        let count = self.counts.entry(key).or_insert(0);
+        *count += 1;
+        *count
+    }
+
+    /// Undoes one occurrence of `key`, as when taking back a move
+    /// ／`key` の出現をひとつ取り消す。手を戻すときに用いる。
+    pub fn unrecord(&mut self, key: u64) {
+        if let Some(count) = self.counts.get_mut(&key) {
+            *count -= 1;
+            if *count == 0 {
+                self.counts.remove(&key);
+            }
+        }
+    }
+
+    /// How many times `key` has occurred
+    /// ／`key` が出現した回数
+    #[must_use]
+    pub fn count(&self, key: u64) -> u32 {
+        self.counts.get(&key).copied().unwrap_or(0)
+    }
+
+    /// Forgets every occurrence
+    /// ／すべての出現を忘れる
+    pub fn clear(&mut self) {
+        self.counts.clear();
+    }
+}
@@ -0,0 +1,101 @@
+use crate::movement::neighbor_indices;
+use crate::{CetkaikRepresentation, IsBoard, IsField, PureMove};
+use cetkaik_fundamental::PureMove_;
+
+/// The rule variants that affect how moves are generated and applied
+/// ／指し手の生成と適用に影響するルールの差異
+///
+/// [`RuleConfig::default`] is the configuration that the rest of this crate assumes when none is given.
+/// ／[`RuleConfig::default`] は、設定が与えられないときにこのクレートの他の部分が想定する設定である。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct RuleConfig {
+    /// Whether a piece may step over Tam2 (撃皇)
+    /// ／皇を踏み越えてよいか（撃皇）
+    pub allow_kut2tam2: bool,
+    /// Whether the square that Tam2 itself is in is tam2 hue
+    /// ／皇のあるマス自身が皇処になるか
+    pub tam_itself_is_tam_hue: bool,
+    /// Whether the squares that are tam2 hue by default (see [`CetkaikRepresentation::is_tam_hue_by_default`]) are tam2 hue
+    /// ／既定で皇処であるマス（[`CetkaikRepresentation::is_tam_hue_by_default`] を参照）が皇処になるか
+    pub default_squares_are_tam_hue: bool,
+    /// Whether entering water requires a stick cast; if `false`, every water entry succeeds
+    /// ／入水に投げ棒判定が必要か。`false` なら入水は常に成功する。
+    pub water_entry_requires_ciurl: bool,
+}
+
+impl Default for RuleConfig {
+    fn default() -> Self {
+        Self::yhuap()
+    }
+}
+
+impl RuleConfig {
+    /// The configuration of the official (yhuap) rule, in which Tam2 may be stepped over and its own square is tam2 hue
+    /// ／官定のルール設定。皇を踏み越えてよく、皇のあるマス自身も皇処である。
+    #[must_use]
+    pub const fn yhuap() -> Self {
+        Self {
+            allow_kut2tam2: true,
+            tam_itself_is_tam_hue: true,
+            default_squares_are_tam_hue: true,
+            water_entry_requires_ciurl: true,
+        }
+    }
+
+    /// Whether `coord` is tam2 hue on `field` under this configuration
+    /// ／この設定のもとで、`field` 上の `coord` が皇処であるかどうか
+    ///
+    /// Besides the squares governed by the two flags, the squares adjacent to Tam2 are always tam2 hue.
+    /// ／二つのフラグで決まるマスに加えて、皇に隣接するマスは常に皇処である。
+    pub fn is_tam_hue<R: CetkaikRepresentation>(
+        &self,
+        field: &R::AbsoluteField,
+        coord: R::AbsoluteCoord,
+    ) -> bool {
+        self.is_tam_hue_on::<R>(field.as_board(), R::coord_to_index(coord), None)
+    }
+
+    /// Whether the square of the canonical index `index` is tam2 hue, treating the square `removed` as empty
+    pub(crate) fn is_tam_hue_on<R: CetkaikRepresentation>(
+        self,
+        board: &R::AbsoluteBoard,
+        index: u8,
+        removed: Option<u8>,
+    ) -> bool {
+        let is_tam2 = |i: u8| {
+            Some(i) != removed
+                && R::index_to_coord(i).and_then(|c| board.peek(c)) == Some(R::absolute_tam2())
+        };
+        (self.default_squares_are_tam_hue
+            && R::index_to_coord(index).is_some_and(R::is_tam_hue_by_default_absolute))
+            || (self.tam_itself_is_tam_hue && is_tam2(index))
+            || neighbor_indices(index).any(is_tam2)
+    }
+}
+
+/// Whether applying `mv` under `config` requires casting the sticks
+/// ／`config` のもとで `mv` を適用するのに投げ棒を投げる必要があるかどうか
+///
+/// An `InfAfterStep` always requires a cast, and a move flagged as a water entry does so when `config` says so.
+/// ／`InfAfterStep` は常に投げ棒判定を要し、入水の印が付いた手は `config` がそう定める場合に要する。
+pub const fn is_ciurl_required<R: CetkaikRepresentation>(
+    mv: &PureMove<R>,
+    config: &RuleConfig,
+) -> bool {
+    match mv {
+        PureMove_::NonTamMoveSrcDst {
+            is_water_entry_ciurl,
+            ..
+        }
+        | PureMove_::NonTamMoveSrcStepDstFinite {
+            is_water_entry_ciurl,
+            ..
+        } => *is_water_entry_ciurl && config.water_entry_requires_ciurl,
+        PureMove_::InfAfterStep { .. } => true,
+        PureMove_::NonTamMoveFromHopZuo { .. }
+        | PureMove_::TamMoveNoStep { .. }
+        | PureMove_::TamMoveStepsDuringFormer { .. }
+        | PureMove_::TamMoveStepsDuringLatter { .. } => false,
+    }
+}
@@ -0,0 +1,378 @@
+use crate::dynamic::{kind_code, kind_from_code, move_to_coord, move_to_index};
+use crate::repetition::side_index;
+use crate::{
+    CanonicalPiece, CetkaikRepresentation, GameRecord, IsAbsoluteField, IsBoard, IsField,
+    PlayedMove, RuleConfig, SEASON_COUNT, SQUARE_COUNT,
+};
+use cetkaik_fundamental::{AbsoluteSide, ColorAndProf, PureMove_};
+
+/// The first four bytes of a save file written by [`save`]
+/// ／[`save`] が書き出すセーブファイルの先頭 4 バイト
+pub const SAVE_MAGIC: [u8; 4] = *b"CTKS";
+
+/// The version of the format written by [`save`]; [`load`] reads this version only
+/// ／[`save`] が書き出す形式の版。[`load`] はこの版のみを読む。
+pub const SAVE_VERSION: u16 = 1;
+
+/// An in-progress game as stored by [`save`]: the state at the start of the current season, and the moves played since
+/// ／[`save`] が格納する進行中のゲーム。現在の季節の開始時の状態と、それ以降に指された手からなる。
+pub struct SavedGame<R: CetkaikRepresentation> {
+    /// the current season, counted from 0 (spring, 春)／0（春）から数えた現在の季節
+    pub season_index: u8,
+    /// the score held by `ASide`／`ASide` の持ち点
+    pub a_side_score: i32,
+    /// the score held by `IASide`／`IASide` の持ち点
+    pub ia_side_score: i32,
+    /// the doubling rate／レート
+    pub rate: i32,
+    /// the field the season started from, the rules and the moves played since／季節の開始時の `Field`、ルール、それ以降に指された手
+    pub record: GameRecord<R>,
+}
+
+impl<R: CetkaikRepresentation> Clone for SavedGame<R> {
+    fn clone(&self) -> Self {
+        Self {
+            season_index: self.season_index,
+            a_side_score: self.a_side_score,
+            ia_side_score: self.ia_side_score,
+            rate: self.rate,
+            record: self.record.clone(),
+        }
+    }
+}
+
+impl<R: CetkaikRepresentation> core::fmt::Debug for SavedGame<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SavedGame")
+            .field("season_index", &self.season_index)
+            .field("a_side_score", &self.a_side_score)
+            .field("ia_side_score", &self.ia_side_score)
+            .field("rate", &self.rate)
+            .field("record", &self.record)
+            .finish()
+    }
+}
+
+impl<R: CetkaikRepresentation> PartialEq for SavedGame<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.season_index == other.season_index
+            && self.a_side_score == other.a_side_score
+            && self.ia_side_score == other.ia_side_score
+            && self.rate == other.rate
+            && self.record == other.record
+    }
+}
+
+/// A reason why [`load`] cannot read a save file
+/// ／[`load`] がセーブファイルを読めない理由
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LoadError {
+    /// The file does not start with [`SAVE_MAGIC`]
+    /// ／ファイルが [`SAVE_MAGIC`] で始まらない
+    BadMagic,
+    /// The file is of a version other than [`SAVE_VERSION`]
+    /// ／ファイルの版が [`SAVE_VERSION`] ではない
+    UnsupportedVersion(u16),
+    /// The file ends in the middle
+    /// ／ファイルが途中で終わっている
+    Truncated,
+    /// The file contains a value out of range, such as an unknown piece code, a season past winter or more than five sticks face up
+    /// ／ファイルが範囲外の値（未知の駒の番号、冬より後の季節、5 本を超える表の投げ棒など）を含む
+    Malformed,
+    /// The file goes on after the last move
+    /// ／最後の指し手の後にもファイルが続いている
+    TrailingBytes,
+}
+
+impl core::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "not a save file"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported save file version {version}")
+            }
+            Self::Truncated => write!(f, "the save file ends in the middle"),
+            Self::Malformed => write!(f, "the save file contains a value out of range"),
+            Self::TrailingBytes => write!(f, "the save file goes on after the last move"),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+const NO_CIURL: u8 = 0xff;
+
+/// The number of sticks cast at once, i.e. the largest number of them that can fall face up
+const STICK_COUNT: u8 = 5;
+
+/// Packs the fields of `config` into bits, from the lowest bit in the order of declaration
+pub(crate) fn config_flags(config: RuleConfig) -> u8 {
+    u8::from(config.allow_kut2tam2)
+        | u8::from(config.tam_itself_is_tam_hue) << 1
+        | u8::from(config.default_squares_are_tam_hue) << 2
+        | u8::from(config.water_entry_requires_ciurl) << 3
+}
+
+/// The inverse of `config_flags`
+pub(crate) const fn config_from_flags(flags: u8) -> Option<RuleConfig> {
+    if flags >> 4 != 0 {
+        return None;
+    }
+    Some(RuleConfig {
+        allow_kut2tam2: flags & 1 != 0,
+        tam_itself_is_tam_hue: flags & 2 != 0,
+        default_squares_are_tam_hue: flags & 4 != 0,
+        water_entry_requires_ciurl: flags & 8 != 0,
+    })
+}
+
+fn write_move(out: &mut Vec<u8>, mv: PureMove_<u8>) {
+    match mv {
+        PureMove_::NonTamMoveSrcDst {
+            src,
+            dest,
+            is_water_entry_ciurl,
+        } => out.extend([0, src, dest, u8::from(is_water_entry_ciurl)]),
+        PureMove_::NonTamMoveSrcStepDstFinite {
+            src,
+            step,
+            dest,
+            is_water_entry_ciurl,
+        } => out.extend([1, src, step, dest, u8::from(is_water_entry_ciurl)]),
+        PureMove_::InfAfterStep {
+            src,
+            step,
+            planned_direction,
+        } => out.extend([2, src, step, planned_direction]),
+        PureMove_::NonTamMoveFromHopZuo { color, prof, dest } => {
+            out.extend([3, kind_code(ColorAndProf { color, prof }), dest]);
+        }
+        PureMove_::TamMoveNoStep {
+            src,
+            first_dest,
+            second_dest,
+        } => out.extend([4, src, first_dest, second_dest]),
+        PureMove_::TamMoveStepsDuringFormer {
+            src,
+            step,
+            first_dest,
+            second_dest,
+        } => out.extend([5, src, step, first_dest, second_dest]),
+        PureMove_::TamMoveStepsDuringLatter {
+            src,
+            first_dest,
+            step,
+            second_dest,
+        } => out.extend([6, src, first_dest, step, second_dest]),
+    }
+}
+
+/// Writes `game` into a self-contained byte string, which [`load`] reads back in any representation
+/// ／`game` を自己完結したバイト列に書き出す。[`load`] はこれをどの表現でも読み戻せる。
+///
+/// The layout, with every integer in little endian, is:
+/// [`SAVE_MAGIC`]; [`SAVE_VERSION`] as a `u16`; the [`RuleConfig`] as a byte of flags, in the order of its fields from the lowest bit;
+/// the first mover as a byte (0 for `ASide`, 1 for `IASide`); the season as a byte; the two scores and the rate as `i32`s;
+/// the 81 squares of the initial field in canonical order, each as the code of its piece (255 for an empty square;
+/// 0 for Tam2 and `1 + 20 * side + 10 * color + prof` otherwise, as in [`encode_journal`](crate::encode_journal));
+/// for `ASide` and then `IASide`, the number of pieces in the hop1zuo1 as a byte followed by each piece as `10 * color + prof`,
+/// in the canonical order (see [`IsAbsoluteField::hop1zuo1_sorted`]) so that the same game saves to the same bytes in any representation;
+/// and the number of moves as a `u32` followed by the moves.
+/// A move is a tag byte (0 to 6, in the order of the variants of `PureMove_`), the piece parachuted as `10 * color + prof` if any,
+/// the canonical indices of its squares in the order the piece passes them, the water-entry flag as 0 or 1 if any,
+/// and then the sticks cast, 255 meaning none recorded.
+/// ／レイアウトは次の通りで、整数はすべてリトルエンディアンである。
+/// [`SAVE_MAGIC`]、`u16` としての [`SAVE_VERSION`]、フィールドの順に最下位ビットから並べたフラグ 1 バイトとしての [`RuleConfig`]、
+/// 先手を表す 1 バイト（`ASide` なら 0、`IASide` なら 1）、季節を表す 1 バイト、`i32` としての両者の持ち点とレート、
+/// 開始時の `Field` の 81 マスを正準な順に、それぞれ駒の番号で（空きマスは 255、皇は 0、それ以外は
+/// [`encode_journal`](crate::encode_journal) と同様に `1 + 20 * side + 10 * color + prof`）、
+/// `ASide` と `IASide` の順に、手駒の数を表す 1 バイトとそれに続く `10 * color + prof` としての各駒
+/// （同じ対局がどの表現でも同じバイト列になるよう、正準な順序（[`IsAbsoluteField::hop1zuo1_sorted`] を参照）で並べる）、
+/// そして `u32` としての指し手の数とそれに続く指し手。
+/// 指し手は、タグ 1 バイト（`PureMove_` の列挙子の順に 0 から 6）、打つ駒があれば `10 * color + prof`、
+/// 駒が通る順に並べた各マスの正準な番号、入水の印があれば 0 か 1、そして投げ棒の結果（記録がなければ 255）からなる。
+/// # Panics
+/// Panics if a hop1zuo1 holds more than 255 pieces or there are more than `u32::MAX` moves.
+/// ／手駒が 255 個を超えるか、指し手が `u32::MAX` 個を超えるとパニックする。
+#[must_use]
+pub fn save<R: CetkaikRepresentation>(game: &SavedGame<R>) -> Vec<u8> {
+    let record = &game.record;
+    let mut out = SAVE_MAGIC.to_vec();
+    out.extend(SAVE_VERSION.to_le_bytes());
+    out.push(config_flags(record.config));
+    out.push(side_index(record.first_mover));
+    out.push(game.season_index);
+    for n in [game.a_side_score, game.ia_side_score, game.rate] {
+        out.extend(n.to_le_bytes());
+    }
+    let board = record.initial.as_board();
+    for index in 0..SQUARE_COUNT {
+        let piece = R::index_to_coord(index).and_then(|c| board.peek(c));
+        out.push(piece.map_or(0xff, |p| CanonicalPiece::from_piece(p).code()));
+    }
+    for side in [AbsoluteSide::ASide, AbsoluteSide::IASide] {
+        let hop1zuo1 = record.initial.hop1zuo1_sorted(side);
+        out.push(u8::try_from(hop1zuo1.len()).expect("a hop1zuo1 holds at most 255 pieces"));
+        out.extend(hop1zuo1.into_iter().map(kind_code));
+    }
+    let count = u32::try_from(record.moves.len()).expect("at most u32::MAX moves");
+    out.extend(count.to_le_bytes());
+    for &PlayedMove { mv, ciurl } in &record.moves {
+        write_move(&mut out, move_to_index::<R>(mv));
+        out.push(ciurl.unwrap_or(NO_CIURL));
+    }
+    out
+}
+
+/// Reads what is left of a save file
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N], LoadError> {
+        let (head, rest) = self.0.split_first_chunk().ok_or(LoadError::Truncated)?;
+        self.0 = rest;
+        Ok(*head)
+    }
+
+    fn u8(&mut self) -> Result<u8, LoadError> {
+        self.bytes::<1>().map(|[b]| b)
+    }
+
+    fn i32(&mut self) -> Result<i32, LoadError> {
+        self.bytes().map(i32::from_le_bytes)
+    }
+
+    fn flag(&mut self) -> Result<bool, LoadError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(LoadError::Malformed),
+        }
+    }
+
+    fn square(&mut self) -> Result<u8, LoadError> {
+        Some(self.u8()?)
+            .filter(|&index| index < SQUARE_COUNT)
+            .ok_or(LoadError::Malformed)
+    }
+
+    fn kind(&mut self) -> Result<ColorAndProf, LoadError> {
+        kind_from_code(self.u8()?).ok_or(LoadError::Malformed)
+    }
+
+    fn mv(&mut self) -> Result<PureMove_<u8>, LoadError> {
+        Ok(match self.u8()? {
+            0 => PureMove_::NonTamMoveSrcDst {
+                src: self.square()?,
+                dest: self.square()?,
+                is_water_entry_ciurl: self.flag()?,
+            },
+            1 => PureMove_::NonTamMoveSrcStepDstFinite {
+                src: self.square()?,
+                step: self.square()?,
+                dest: self.square()?,
+                is_water_entry_ciurl: self.flag()?,
+            },
+            2 => PureMove_::InfAfterStep {
+                src: self.square()?,
+                step: self.square()?,
+                planned_direction: self.square()?,
+            },
+            3 => {
+                let ColorAndProf { color, prof } = self.kind()?;
+                PureMove_::NonTamMoveFromHopZuo {
+                    color,
+                    prof,
+                    dest: self.square()?,
+                }
+            }
+            4 => PureMove_::TamMoveNoStep {
+                src: self.square()?,
+                first_dest: self.square()?,
+                second_dest: self.square()?,
+            },
+            5 => PureMove_::TamMoveStepsDuringFormer {
+                src: self.square()?,
+                step: self.square()?,
+                first_dest: self.square()?,
+                second_dest: self.square()?,
+            },
+            6 => PureMove_::TamMoveStepsDuringLatter {
+                src: self.square()?,
+                first_dest: self.square()?,
+                step: self.square()?,
+                second_dest: self.square()?,
+            },
+            _ => return Err(LoadError::Malformed),
+        })
+    }
+
+    fn field<R: CetkaikRepresentation>(&mut self) -> Result<R::AbsoluteField, LoadError> {
+        let mut field = R::AbsoluteField::empty();
+        for index in 0..SQUARE_COUNT {
+            let code = self.u8()?;
+            if code != 0xff {
+                let piece = CanonicalPiece::from_code(code).ok_or(LoadError::Malformed)?;
+                let coord = R::index_to_coord(index).ok_or(LoadError::Malformed)?;
+                field.as_board_mut().put(coord, Some(piece.to_piece::<R>()));
+            }
+        }
+        for side in [AbsoluteSide::ASide, AbsoluteSide::IASide] {
+            for _ in 0..self.u8()? {
+                let piece = self.kind()?;
+                field.add_to_hop1zuo1(side, piece);
+            }
+        }
+        Ok(field)
+    }
+}
+
+/// Reads a save file written by [`save`] into the representation `R`
+/// ／[`save`] が書き出したセーブファイルを表現 `R` に読み込む
+///
+/// The moves are read as they are; use [`GameRecord::replay`] on [`SavedGame::record`] to check them.
+/// ／指し手はそのまま読み込まれる。検査するには [`SavedGame::record`] に対して [`GameRecord::replay`] を用いる。
+/// # Errors
+/// Fails if `bytes` is not a save file of [`SAVE_VERSION`]; see [`LoadError`].
+/// ／`bytes` が [`SAVE_VERSION`] のセーブファイルでなければ失敗する。[`LoadError`] を参照。
+pub fn load<R: CetkaikRepresentation>(bytes: &[u8]) -> Result<SavedGame<R>, LoadError> {
+    let mut r = Reader(bytes);
+    if r.bytes::<4>().map_err(|_| LoadError::BadMagic)? != SAVE_MAGIC {
+        return Err(LoadError::BadMagic);
+    }
+    let version = u16::from_le_bytes(r.bytes()?);
+    if version != SAVE_VERSION {
+        return Err(LoadError::UnsupportedVersion(version));
+    }
+    let config = config_from_flags(r.u8()?).ok_or(LoadError::Malformed)?;
+    let first_mover = match r.u8()? {
+        0 => AbsoluteSide::ASide,
+        1 => AbsoluteSide::IASide,
+        _ => return Err(LoadError::Malformed),
+    };
+    let season_index = Some(r.u8()?)
+        .filter(|&season| usize::from(season) < SEASON_COUNT)
+        .ok_or(LoadError::Malformed)?;
+    let (a_side_score, ia_side_score, rate) = (r.i32()?, r.i32()?, r.i32()?);
+    let mut record = GameRecord::new(r.field::<R>()?, first_mover, config);
+    for _ in 0..u32::from_le_bytes(r.bytes()?) {
+        let mv = move_to_coord::<R>(r.mv()?).map_err(|_| LoadError::Malformed)?;
+        let ciurl = match r.u8()? {
+            NO_CIURL => None,
+            ciurl @ 0..=STICK_COUNT => Some(ciurl),
+            _ => return Err(LoadError::Malformed),
+        };
+        record.push(mv, ciurl);
+    }
+    if !r.0.is_empty() {
+        return Err(LoadError::TrailingBytes);
+    }
+    Ok(SavedGame {
+        season_index,
+        a_side_score,
+        ia_side_score,
+        rate,
+        record,
+    })
+}
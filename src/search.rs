@@ -0,0 +1,482 @@
+use crate::dynamic::move_to_index;
+use crate::ordering::mvv_lva_key;
+use crate::{
+    CetkaikRepresentation, FieldPool, GoParams, HashedField, HistoryTable, IsAbsoluteField,
+    IsMoveGenerator, KillerTable, PureMove, ReplacementPolicy, RuleConfig, TranspositionTable,
+};
+use cetkaik_fundamental::{AbsoluteSide, PureMove_};
+use core::cmp::Reverse;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// The score of a position from the point of view of the side to move; the higher, the better for that side
+/// ／手番側から見た局面の評価値。大きいほど手番側に有利である。
+pub type Score = i32;
+
+/// A score beyond any that an [`Evaluator`] returns, used as the initial bounds of [`search`]
+const INFINITY: Score = Score::MAX / 2;
+
+/// The number of slots of the transposition table of [`SearchState::new`]
+const DEFAULT_TT_CAPACITY: usize = 1 << 16;
+
+/// A trait that signifies that you can use it to evaluate a field
+/// ／`Field` を評価するのに使える型を表すトレイト
+///
+/// A search calls [`Evaluator::on_move`] just before it moves down the tree by a move and [`Evaluator::on_undo`] just after it comes back,
+/// so an evaluator can keep its state up to date incrementally instead of recomputing it from the field in [`Evaluator::eval`].
+/// Both do nothing by default.
+/// ／探索は、指し手によって木を下る直前に [`Evaluator::on_move`] を、戻った直後に [`Evaluator::on_undo`] を呼ぶので、
+/// 評価関数は [`Evaluator::eval`] で `Field` から再計算する代わりに、自身の状態を差分で更新し続けることができる。どちらも既定では何もしない。
+pub trait Evaluator<R: CetkaikRepresentation> {
+    /// The score of `field` from the point of view of `side`
+    /// ／`side` から見た `field` の評価値
+    fn eval(&self, field: &R::AbsoluteField, side: AbsoluteSide) -> Score;
+
+    /// Called when `mv` is about to be made
+    /// ／`mv` が指されようとしているときに呼ばれる
+    fn on_move(&mut self, mv: PureMove<R>) {
+        let _ = mv;
+    }
+
+    /// Called when `mv`, the last move made, has been taken back
+    /// ／最後に指された手 `mv` が取り消されたときに呼ばれる
+    fn on_undo(&mut self, mv: PureMove<R>) {
+        let _ = mv;
+    }
+}
+
+/// The evaluator that counts only the material, by [`crate::IsAbsoluteField::material_eval`]
+/// ／駒の価値だけを数える評価関数。[`crate::IsAbsoluteField::material_eval`] による
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MaterialEvaluator;
+
+impl<R: CetkaikRepresentation> Evaluator<R> for MaterialEvaluator {
+    fn eval(&self, field: &R::AbsoluteField, side: AbsoluteSide) -> Score {
+        field.material_eval(side)
+    }
+}
+
+/// A trait that signifies that it can tell a search when to stop
+/// ／探索にいつ止まるべきかを伝えられる型を表すトレイト
+///
+/// A search should call [`SearchController::should_stop`] every so often and return the best move found so far once it says so.
+/// ／探索は [`SearchController::should_stop`] を適宜呼び、真を返したらそれまでに見つけた最善手を返すべきである。
+pub trait SearchController {
+    /// Asks the search to stop as soon as possible
+    /// ／探索にできるだけ早く止まるよう求める
+    fn stop(&self);
+
+    /// Whether [`SearchController::stop`] has been called
+    /// ／[`SearchController::stop`] が呼ばれたかどうか
+    fn is_stopped(&self) -> bool;
+
+    /// Whether the search, having visited `nodes` nodes, should stop now
+    /// ／`nodes` 個のノードを訪れた探索が今止まるべきかどうか
+    fn should_stop(&self, nodes: u64) -> bool;
+
+    /// Whether the search may start an iteration to the depth `depth`, in plies
+    /// ／探索が深さ `depth`（手数）の反復を始めてよいかどうか
+    fn allows_depth(&self, depth: u32) -> bool;
+}
+
+/// The limits of a search: depth, nodes, wall-clock time, and a flag through which another thread can stop it
+/// ／探索の制限。深さ・ノード数・経過時間と、他のスレッドから探索を止めるためのフラグからなる。
+///
+/// A limit left as `None` does not restrict the search. The clock starts when the limits are created.
+/// [`crate::run_engine`] creates one for each `go` and sets its flag on `stop`, so an engine that honors the limits stops uniformly.
+/// ／`None` の制限は探索を制限しない。時計は制限を作った時点で動き始める。
+/// [`crate::run_engine`] は `go` ごとにこれを作り、`stop` でフラグを立てるので、制限に従うエンジンは一様に止まる。
+#[derive(Clone, Debug)]
+pub struct SearchLimits {
+    /// the depth to search to, in plies／探索する深さ（手数）
+    pub depth: Option<u32>,
+    /// the number of nodes to search／探索するノード数
+    pub nodes: Option<u64>,
+    /// the time to spend on the search／探索に費やす時間
+    pub movetime: Option<Duration>,
+    start: Instant,
+    stop: Arc<AtomicBool>,
+}
+
+impl Default for SearchLimits {
+    fn default() -> Self {
+        Self {
+            depth: None,
+            nodes: None,
+            movetime: None,
+            start: Instant::now(),
+            stop: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl SearchLimits {
+    /// No limits, with a flag of its own
+    /// ／制限なし。独自のフラグを持つ。
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The limits given by a `go` command, with a flag of its own
+    /// ／`go` コマンドで与えられた制限。独自のフラグを持つ。
+    #[must_use]
+    pub fn from_params(params: &GoParams) -> Self {
+        Self {
+            depth: params.depth,
+            nodes: params.nodes,
+            movetime: params.movetime,
+            ..Self::default()
+        }
+    }
+
+    /// The limits as given by a `go` command, i.e. without the flag
+    /// ／`go` コマンドの形での制限。すなわちフラグを除いたもの
+    #[must_use]
+    pub const fn params(&self) -> GoParams {
+        GoParams {
+            depth: self.depth,
+            nodes: self.nodes,
+            movetime: self.movetime,
+        }
+    }
+
+    /// Uses `stop` as the flag instead, so that whoever holds it can stop the search
+    /// ／代わりに `stop` をフラグとして使い、それを持つ者が探索を止められるようにする
+    #[must_use]
+    pub fn with_stop_flag(mut self, stop: Arc<AtomicBool>) -> Self {
+        self.stop = stop;
+        self
+    }
+
+    /// The flag, which stops the search when set
+    /// ／フラグ。立てると探索が止まる。
+    #[must_use]
+    pub fn stop_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.stop)
+    }
+
+    /// The time elapsed since the limits were created
+    /// ／制限を作ってから経過した時間
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+impl SearchController for SearchLimits {
+    fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    fn should_stop(&self, nodes: u64) -> bool {
+        self.is_stopped()
+            || self.nodes.is_some_and(|limit| nodes >= limit)
+            || self.movetime.is_some_and(|limit| self.elapsed() >= limit)
+    }
+
+    fn allows_depth(&self, depth: u32) -> bool {
+        self.depth.is_none_or(|limit| depth <= limit)
+    }
+}
+
+/// Which side of the true score a score stored in the transposition table lies on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Bound {
+    Exact,
+    Lower,
+    Upper,
+}
+
+/// What [`search`] stores in its transposition table, with the move written with canonical indices
+#[derive(Clone, Copy, Debug)]
+struct TtEntry {
+    depth: u32,
+    score: Score,
+    bound: Bound,
+    best: Option<PureMove_<u8>>,
+    /// the [`SearchState::generation`] of the search that stored the entry
+    generation: u32,
+}
+
+/// Keeps the entry searched to the greater depth, unless it was left by an earlier search
+#[derive(Clone, Copy, Debug, Default)]
+struct PreferDeeper;
+
+impl ReplacementPolicy<TtEntry> for PreferDeeper {
+    fn replaces(&self, old: &TtEntry, new: &TtEntry) -> bool {
+        new.generation != old.generation || new.depth >= old.depth
+    }
+}
+
+/// What [`search`] keeps between searches: the evaluator, the transposition table and the move-ordering tables
+/// ／[`search`] が探索をまたいで保持するもの。評価関数・置換表・指し手の並べ替え用の表からなる。
+///
+/// Nothing in it depends on the representation apart from the evaluator,
+/// so the same search over two representations visits the same nodes.
+/// ／評価関数以外は表現に依らないので、二つの表現で同じ探索を行えば同じノードを訪れる。
+#[derive(Debug)]
+pub struct SearchState<E> {
+    evaluator: E,
+    tt: TranspositionTable<TtEntry, PreferDeeper>,
+    history: HistoryTable,
+    killers: KillerTable,
+    nodes: u64,
+    /// bumped at the start of each search, so that deep entries of the positions searched before do not stay forever
+    generation: u32,
+}
+
+impl<E> SearchState<E> {
+    /// A state that evaluates with `evaluator`, with a transposition table of 65536 slots
+    /// ／`evaluator` で評価する状態。置換表の枠は 65536 個
+    pub fn new(evaluator: E) -> Self {
+        Self::with_tt_capacity(evaluator, DEFAULT_TT_CAPACITY)
+    }
+
+    /// A state that evaluates with `evaluator`, with a transposition table of at least `capacity` slots
+    /// ／`evaluator` で評価する状態。置換表の枠は少なくとも `capacity` 個
+    pub fn with_tt_capacity(evaluator: E, capacity: usize) -> Self {
+        Self {
+            evaluator,
+            tt: TranspositionTable::new(capacity),
+            history: HistoryTable::new(),
+            killers: KillerTable::new(),
+            nodes: 0,
+            generation: 0,
+        }
+    }
+
+    /// The evaluator
+    /// ／評価関数
+    pub const fn evaluator(&self) -> &E {
+        &self.evaluator
+    }
+
+    /// The number of nodes visited by the last search
+    /// ／直前の探索が訪れたノードの数
+    pub const fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    /// Forgets everything learned by the previous searches, e.g. at the start of a new game
+    /// ／それまでの探索で得た情報をすべて忘れる。新しいゲームの開始時などに用いる。
+    pub fn clear(&mut self) {
+        self.tt.clear();
+        self.history.clear();
+        self.killers.clear();
+    }
+}
+
+/// One search in progress
+struct Searcher<'a, R: CetkaikRepresentation, G, E, C> {
+    rules: &'a G,
+    config: RuleConfig,
+    state: &'a mut SearchState<E>,
+    limits: &'a C,
+    aborted: bool,
+    _representation: core::marker::PhantomData<R>,
+}
+
+impl<R, G, E, C> Searcher<'_, R, G, E, C>
+where
+    R: CetkaikRepresentation,
+    G: IsMoveGenerator<R>,
+    E: Evaluator<R>,
+    C: SearchController,
+{
+    /// The moves of `whose_turn` on `field` in the order to try them:
+    /// the move from the transposition table, captures by MVV-LVA, killer moves, and the other moves by their history scores
+    fn ordered_moves(
+        &self,
+        field: &R::AbsoluteField,
+        whose_turn: AbsoluteSide,
+        tt_move: Option<PureMove_<u8>>,
+        ply: usize,
+    ) -> Vec<PureMove<R>> {
+        let mut moves = self.rules.all_moves(field, whose_turn);
+        moves.sort_by_key(|&mv| {
+            (
+                Some(move_to_index::<R>(mv)) != tt_move,
+                mvv_lva_key::<R>(field, mv),
+                !self.state.killers.is_killer::<R>(ply, mv),
+                Reverse(self.state.history.score::<R>(mv)),
+            )
+        });
+        moves
+    }
+
+    /// The negamax score of `node` searched to `depth`, within the window from `alpha` to `beta`, together with the best move
+    fn negamax(
+        &mut self,
+        node: &mut HashedField<R>,
+        whose_turn: AbsoluteSide,
+        depth: u32,
+        mut alpha: Score,
+        beta: Score,
+        ply: usize,
+    ) -> (Score, Option<PureMove<R>>) {
+        self.state.nodes += 1;
+        if self.limits.should_stop(self.state.nodes) {
+            self.aborted = true;
+            return (0, None);
+        }
+        let key = node.repetition_key(whose_turn);
+        let entry = self.state.tt.get(key);
+        if let Some(entry) = entry.filter(|entry| entry.depth >= depth && ply > 0) {
+            let cutoff = match entry.bound {
+                Bound::Exact => true,
+                Bound::Lower => entry.score >= beta,
+                Bound::Upper => entry.score <= alpha,
+            };
+            if cutoff {
+                return (entry.score, None);
+            }
+        }
+        if depth == 0 {
+            return (self.state.evaluator.eval(node.field(), whose_turn), None);
+        }
+        let moves = self.ordered_moves(node.field(), whose_turn, entry.and_then(|e| e.best), ply);
+        let original_alpha = alpha;
+        let mut best: Option<(Score, PureMove<R>)> = None;
+        for mv in moves {
+            let Ok(undo) = node.make_move(mv, whose_turn, &self.config) else {
+                continue;
+            };
+            self.state.evaluator.on_move(mv);
+            let (score, _) = self.negamax(node, !whose_turn, depth - 1, -beta, -alpha, ply + 1);
+            self.state.evaluator.on_undo(mv);
+            node.unmake_move(undo);
+            if self.aborted {
+                return (0, best.map(|(_, mv)| mv));
+            }
+            let score = -score;
+            if best.is_none_or(|(best_score, _)| score > best_score) {
+                best = Some((score, mv));
+            }
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                if mvv_lva_key::<R>(node.field(), mv) == Reverse(None) {
+                    self.state.killers.record::<R>(ply, mv);
+                    self.state.history.record::<R>(mv, depth);
+                }
+                break;
+            }
+        }
+        let Some((score, mv)) = best else {
+            return (self.state.evaluator.eval(node.field(), whose_turn), None);
+        };
+        let bound = if score <= original_alpha {
+            Bound::Upper
+        } else if score >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.state.tt.insert(
+            key,
+            TtEntry {
+                depth,
+                score,
+                bound,
+                best: Some(move_to_index::<R>(mv)),
+                generation: self.state.generation,
+            },
+        );
+        (score, Some(mv))
+    }
+}
+
+/// Searches for the best move of `whose_turn` on `field` by iterative deepening alpha-beta, until `limits` says to stop
+/// ／`limits` が止まるよう告げるまで、反復深化のアルファベータ法で `field` における `whose_turn` の最善手を探す
+///
+/// The moves are generated by `rules`, made on a single [`HashedField`] and taken back on the way up (see [`HashedField::make_move`]);
+/// its hash keys the transposition table of `state`, in which an entry left by an earlier search gives way to any entry of this one.
+/// They are tried in the order of the move from the transposition table, captures by MVV-LVA (see [`crate::order_moves`]),
+/// killer moves and the other moves by their history scores. A field on which no move is available is scored by the evaluator.
+/// As in [`crate::apply_move`], every stick cast is assumed to succeed.
+/// The result is that of the deepest iteration completed. It is `None` if `whose_turn` has no move,
+/// or if `limits` said to stop before the first iteration was completed, in which case no score is known.
+/// The search is deliberately plain, so that it visits the same nodes whichever representation it runs on,
+/// which makes it a benchmark of the representation rather than of the search.
+/// ／指し手は `rules` で生成し、一つの [`HashedField`] の上で指しては戻りがけに取り消す（[`HashedField::make_move`] を参照）。
+/// そのハッシュが `state` の置換表のキーとなり、それより前の探索が残した項目は今回の探索の項目に必ず場所を譲る。
+/// 置換表の手、MVV-LVA（[`crate::order_moves`] を参照）による取る手、キラー手、ヒストリーの得点順の残りの手の順に試す。
+/// 指せる手のない `Field` は評価関数で評価する。[`crate::apply_move`] と同様に、投げ棒判定はすべて成功するものとする。
+/// 結果は完了した最も深い反復のものである。`whose_turn` に指せる手がないとき、
+/// または最初の反復が完了する前に `limits` が止まるよう告げたときは `None` となる。後者の場合、評価値は得られていない。
+/// 探索はあえて素朴にしてあり、どの表現の上で実行しても同じノードを訪れるので、探索ではなく表現の性能を測るのに使える。
+pub fn search<R, G, E, C>(
+    rules: &G,
+    state: &mut SearchState<E>,
+    field: &R::AbsoluteField,
+    whose_turn: AbsoluteSide,
+    limits: &C,
+) -> Option<(PureMove<R>, Score)>
+where
+    R: CetkaikRepresentation,
+    G: IsMoveGenerator<R>,
+    E: Evaluator<R>,
+    C: SearchController,
+{
+    search_with_pool::<R, G, E, C>(
+        rules,
+        state,
+        field,
+        whose_turn,
+        limits,
+        &mut FieldPool::new(),
+    )
+}
+
+/// Searches in the manner of [`search`], taking the field to search on from `pool` instead of cloning `field`
+/// ／[`search`] と同様に探索する。探索に用いる `Field` は `field` を複製する代わりに `pool` から取り出す。
+///
+/// The field is released to `pool` when the search is done, so that repeated searches, as in an engine, allocate no field.
+/// ／探索が終わるとその `Field` は `pool` に返却されるので、エンジンのように探索を繰り返しても `Field` を確保しない。
+pub fn search_with_pool<R, G, E, C>(
+    rules: &G,
+    state: &mut SearchState<E>,
+    field: &R::AbsoluteField,
+    whose_turn: AbsoluteSide,
+    limits: &C,
+    pool: &mut FieldPool<R::AbsoluteField>,
+) -> Option<(PureMove<R>, Score)>
+where
+    R: CetkaikRepresentation,
+    G: IsMoveGenerator<R>,
+    E: Evaluator<R>,
+    C: SearchController,
+{
+    state.nodes = 0;
+    state.generation = state.generation.wrapping_add(1);
+    let mut root = HashedField::<R>::new(pool.acquire_copy_of(field));
+    let mut searcher = Searcher {
+        rules,
+        config: rules.config(),
+        state,
+        limits,
+        aborted: false,
+        _representation: core::marker::PhantomData,
+    };
+    let mut result = None;
+    let mut depth = 1;
+    while limits.allows_depth(depth) {
+        let (score, mv) = searcher.negamax(&mut root, whose_turn, depth, -INFINITY, INFINITY, 0);
+        if searcher.aborted {
+            break;
+        }
+        result = mv.map(|mv| (mv, score));
+        if result.is_none() {
+            break;
+        }
+        searcher.state.history.age();
+        depth += 1;
+    }
+    pool.release(root.into_inner());
+    result
+}
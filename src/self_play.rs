@@ -0,0 +1,237 @@
+use crate::{
+    apply_move_with_ciurl_and_config, encode_planes, is_legal, move_to_policy_index,
+    CetkaikRepresentation, Engine, FnCiurl, GameRecord, GoParams, IllegalMove, IsAbsoluteField,
+    IsMoveGenerator, PureMove, ReplayIter, SearchLimits, SplitMix64, PLANE_COUNT, PLANE_SIZE,
+};
+use cetkaik_fundamental::AbsoluteSide;
+
+/// Casts the five sticks, each of which falls face up with probability one half
+fn cast_sticks(rng: &mut SplitMix64) -> u8 {
+    // the five lowest bits are the five sticks
+    u8::try_from((rng.next_u64() & 0b1_1111).count_ones()).expect("at most 5")
+}
+
+/// An illegal move returned by an engine during [`self_play`]
+/// ／[`self_play`] の途中でエンジンが返した不正な手
+pub struct IllegalEngineMove<R: CetkaikRepresentation> {
+    /// the game up to the move, which is not included／その手の直前までの対局。その手は含まない。
+    pub record: GameRecord<R>,
+    /// the side whose engine returned the move／その手を返したエンジンの陣営
+    pub side: AbsoluteSide,
+    /// the move／指し手
+    pub mv: PureMove<R>,
+    /// why the move is illegal／指し手が不正である理由
+    pub reason: IllegalMove,
+}
+
+impl<R: CetkaikRepresentation> core::fmt::Debug for IllegalEngineMove<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("IllegalEngineMove")
+            .field("record", &self.record)
+            .field("side", &self.side)
+            .field("mv", &self.mv)
+            .field("reason", &self.reason)
+            .finish()
+    }
+}
+
+impl<R: CetkaikRepresentation> core::fmt::Display for IllegalEngineMove<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "the engine playing {:?} returned an illegal move after {} moves: {}",
+            self.side,
+            self.record.moves.len(),
+            self.reason
+        )
+    }
+}
+
+impl<R: CetkaikRepresentation> std::error::Error for IllegalEngineMove<R> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.reason)
+    }
+}
+
+/// Plays a game between `engine_a`, playing `ASide`, and `engine_b`, playing `IASide`, from the yhuap initial arrangement
+/// ／yhuap の初期配置から、`ASide` を持つ `engine_a` と `IASide` を持つ `engine_b` を対局させる
+///
+/// Which side moves first and how the sticks fall are drawn from a [`SplitMix64`] seeded with `seed`,
+/// so the same engines, if deterministic, play the same game for the same seed.
+/// Each engine is told of the new game by [`Engine::new_game`] and asked for each move by [`Engine::go`] with fresh limits given by `params`.
+/// The game ends when the side to move has no move according to `rules`, when its engine returns `None`, or after `max_plies` moves;
+/// since the traits know nothing of hands (役), it is up to the consumer of the record to decide who won.
+/// The moves are played under the configuration of `rules`, and the sticks cast for them are recorded.
+/// ／どちらが先手か、投げ棒がどう落ちるかは `seed` をシードとする [`SplitMix64`] から引くので、決定的なエンジンどうしなら同じシードで同じ対局になる。
+/// 各エンジンには [`Engine::new_game`] で新しい対局を伝え、各手ごとに `params` から作った新しい制限で [`Engine::go`] を呼んで手を求める。
+/// 対局は、`rules` によれば手番の陣営が指せる手がないとき、そのエンジンが `None` を返したとき、または `max_plies` 手を指した後に終わる。
+/// トレイトは役について何も知らないので、勝敗を決めるのは記録を使う側に任される。
+/// 指し手は `rules` の設定のもとで指され、そのために投げた投げ棒が記録される。
+///
+/// # Errors
+/// Returns the game so far, with the move and the reason, if an engine returns an illegal move.
+/// ／エンジンが不正な手を返した場合、それまでの対局を、その手と理由とともに返す。
+///
+/// # Panics
+/// Panics only if [`is_legal`] accepts a move that [`apply_move_with_ciurl_and_config`] rejects, which would be a bug of this crate.
+/// ／[`is_legal`] が受け入れた手を [`apply_move_with_ciurl_and_config`] が拒否した場合にのみパニックする。これはこのクレートのバグである。
+pub fn self_play<R, G, A, B>(
+    engine_a: &mut A,
+    engine_b: &mut B,
+    rules: &G,
+    seed: u64,
+    max_plies: usize,
+    params: &GoParams,
+) -> Result<GameRecord<R>, IllegalEngineMove<R>>
+where
+    R: CetkaikRepresentation,
+    G: IsMoveGenerator<R>,
+    A: Engine<R> + ?Sized,
+    B: Engine<R> + ?Sized,
+{
+    let mut rng = SplitMix64::new(seed);
+    let first_mover = if rng.next_u64() & 1 == 0 {
+        AbsoluteSide::ASide
+    } else {
+        AbsoluteSide::IASide
+    };
+    let config = rules.config();
+    let mut record = GameRecord::<R>::new(R::AbsoluteField::yhuap_initial(), first_mover, config);
+    engine_a.new_game();
+    engine_b.new_game();
+    let mut field = record.initial.clone();
+    let mut whose_turn = first_mover;
+    for _ in 0..max_plies {
+        if rules.all_moves(&field, whose_turn).is_empty() {
+            break;
+        }
+        let limits = SearchLimits::from_params(params);
+        let mv = match whose_turn {
+            AbsoluteSide::ASide => engine_a.go(&field, whose_turn, &limits),
+            AbsoluteSide::IASide => engine_b.go(&field, whose_turn, &limits),
+        };
+        let Some(mv) = mv else {
+            break;
+        };
+        if let Err(reason) = is_legal::<R>(&field, mv, whose_turn, &config) {
+            return Err(IllegalEngineMove {
+                record,
+                side: whose_turn,
+                mv,
+                reason,
+            });
+        }
+        let mut ciurl = None;
+        field = apply_move_with_ciurl_and_config::<R, _>(
+            &field,
+            mv,
+            whose_turn,
+            &mut FnCiurl(|| *ciurl.insert(cast_sticks(&mut rng))),
+            &config,
+        )
+        .expect("a legal move can be applied");
+        record.push(mv, ciurl);
+        whose_turn = !whose_turn;
+    }
+    Ok(record)
+}
+
+/// A training sample: a position, the move played there and how the game went for the side that played it
+/// ／学習用のサンプル。局面、そこで指された手、その手を指した陣営にとって対局がどうなったかからなる。
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrainingSample {
+    /// the position encoded by [`encode_planes`], of length `PLANE_COUNT * PLANE_SIZE`
+    /// ／[`encode_planes`] で符号化した局面。長さは `PLANE_COUNT * PLANE_SIZE`
+    pub planes: Vec<f32>,
+    /// the side to move in the position／局面の手番の陣営
+    pub whose_turn: AbsoluteSide,
+    /// the move played, as given by [`move_to_policy_index`]／指された手。[`move_to_policy_index`] による
+    pub policy_target: usize,
+    /// 1.0, 0.0 or -1.0 as the side to move ended the game ahead, even or behind／手番の陣営が対局を優勢・互角・劣勢で終えたなら、それぞれ 1.0・0.0・-1.0
+    pub value_target: f32,
+}
+
+/// The iterator returned by [`training_samples`]
+/// ／[`training_samples`] が返すイテレータ
+pub struct TrainingSamples<'a, R: CetkaikRepresentation> {
+    record: &'a GameRecord<R>,
+    replay: ReplayIter<'a, R>,
+    field: R::AbsoluteField,
+    whose_turn: AbsoluteSide,
+    index: usize,
+    /// the material of `ASide` minus that of `IASide` at the end of the game
+    final_balance: i32,
+}
+
+impl<R: CetkaikRepresentation> core::fmt::Debug for TrainingSamples<'_, R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TrainingSamples")
+            .field("field", &self.field)
+            .field("whose_turn", &self.whose_turn)
+            .field("index", &self.index)
+            .field("final_balance", &self.final_balance)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Iterates over a [`TrainingSample`] for each move of `record`, in the encodings shared by every representation
+/// ／`record` の各手について、すべての表現で共通の符号化による [`TrainingSample`] を列挙する
+///
+/// The value target is decided by the material (see [`IsAbsoluteField::material_eval`]) at the end of the game,
+/// since the traits know nothing of hands (役); a consumer that scores games otherwise should overwrite it.
+/// The iteration stops at the first illegal move of the record, as [`GameRecord::replay`] does.
+/// ／役についてはトレイトが何も知らないので、価値の目標は対局終了時の駒の価値（[`IsAbsoluteField::material_eval`] を参照）で決める。
+/// 別の方法で対局を採点する場合は上書きすること。[`GameRecord::replay`] と同様に、記録の最初の不正な手で列挙を打ち切る。
+pub fn training_samples<R: CetkaikRepresentation>(
+    record: &GameRecord<R>,
+) -> TrainingSamples<'_, R> {
+    let end = record
+        .replay()
+        .map_while(Result::ok)
+        .last()
+        .unwrap_or_else(|| record.initial.clone());
+    TrainingSamples {
+        record,
+        replay: record.replay(),
+        field: record.initial.clone(),
+        whose_turn: record.first_mover,
+        index: 0,
+        final_balance: end.material_eval(AbsoluteSide::ASide),
+    }
+}
+
+impl<R: CetkaikRepresentation> Iterator for TrainingSamples<'_, R> {
+    type Item = TrainingSample;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mv = self.record.moves.get(self.index)?.mv;
+        let policy_target = move_to_policy_index::<R>(mv);
+        let next = self.replay.next()?.ok()?;
+        let mut planes = vec![0.0; PLANE_COUNT * PLANE_SIZE];
+        encode_planes::<R>(&self.field, &mut planes);
+        let balance = match self.whose_turn {
+            AbsoluteSide::ASide => self.final_balance,
+            AbsoluteSide::IASide => -self.final_balance,
+        };
+        let sample = TrainingSample {
+            planes,
+            whose_turn: self.whose_turn,
+            policy_target,
+            value_target: match balance.cmp(&0) {
+                core::cmp::Ordering::Greater => 1.0,
+                core::cmp::Ordering::Equal => 0.0,
+                core::cmp::Ordering::Less => -1.0,
+            },
+        };
+        self.field = next;
+        self.whose_turn = !self.whose_turn;
+        self.index += 1;
+        Some(sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.replay.size_hint().1)
+    }
+}
+
+impl<R: CetkaikRepresentation> core::iter::FusedIterator for TrainingSamples<'_, R> {}
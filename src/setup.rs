@@ -0,0 +1,438 @@
+use crate::dynamic::{color_from_index, color_index, prof_from_index, prof_index};
+use crate::{
+    CanonicalCoord, CetkaikRepresentation, IsAbsoluteBoard, IsAbsoluteCoord, IsAbsoluteField,
+    IsBoard, IsPieceWithSide,
+};
+use cetkaik_fundamental::{AbsoluteSide, Color, ColorAndProf, Profession};
+
+/// An error in setting up a position
+/// ／局面の設定における誤り
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SetupError {
+    /// More than one piece was placed on the same square
+    /// ／同じマスに二つ以上の駒が置かれた
+    SquareOccupiedTwice,
+    /// A canonical index was 81 or more
+    /// ／正準な番号が 81 以上だった
+    CoordOutOfRange(CanonicalCoord),
+    /// The resulting field has an illegal set of pieces
+    /// ／できあがった `Field` の駒の内訳が不正である
+    Invalid(ValidationError),
+}
+
+impl core::fmt::Display for SetupError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::SquareOccupiedTwice => {
+                write!(f, "more than one piece was placed on the same square")
+            }
+            Self::CoordOutOfRange(index) => {
+                write!(f, "the canonical index {index} is out of range")
+            }
+            Self::Invalid(e) => write!(f, "the position is invalid: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for SetupError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Invalid(e) => Some(e),
+            Self::SquareOccupiedTwice | Self::CoordOutOfRange(_) => None,
+        }
+    }
+}
+
+impl From<ValidationError> for SetupError {
+    fn from(e: ValidationError) -> Self {
+        Self::Invalid(e)
+    }
+}
+
+/// A reason why the pieces of a field, counting both the board and the hop1zuo1s, cannot occur in a game
+/// ／盤上と手駒を合わせた `Field` の駒の内訳が、ゲーム中に現れえない理由
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// There is no Tam2
+    /// ／皇がない
+    NoTam2,
+    /// There is more than one Tam2
+    /// ／皇が二つ以上ある
+    MoreThanOneTam2,
+    /// There are more pieces of a kind than the game has
+    /// ／ある種類の駒がゲームに存在する数より多い
+    TooManyPieces {
+        /// color of the piece／駒の色
+        color: Color,
+        /// profession of the piece／駒の職種
+        prof: Profession,
+    },
+}
+
+impl core::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NoTam2 => write!(f, "there is no Tam2"),
+            Self::MoreThanOneTam2 => write!(f, "there is more than one Tam2"),
+            Self::TooManyPieces { color, prof } => {
+                write!(f, "there are too many pieces of {color:?} {prof:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// The square on which Tam2 starts in the official (yhuap) rule, which is ZO
+/// ／官定で皇が最初に置かれるマス。ZO である。
+pub const YHUAP_INITIAL_TAM2: CanonicalCoord = 40;
+
+/// The pieces other than Tam2 in the initial arrangement of the official (yhuap) rule
+/// ／官定の初期配置における、皇以外の駒
+pub const YHUAP_INITIAL_PIECES: &[(CanonicalCoord, Color, Profession, AbsoluteSide)] = &[
+    (0, Color::Huok2, Profession::Kua2, AbsoluteSide::ASide), // KA
+    (1, Color::Huok2, Profession::Maun1, AbsoluteSide::ASide), // LA
+    (2, Color::Huok2, Profession::Kaun1, AbsoluteSide::ASide), // NA
+    (3, Color::Huok2, Profession::Uai1, AbsoluteSide::ASide), // TA
+    (4, Color::Kok1, Profession::Io, AbsoluteSide::ASide),    // ZA
+    (5, Color::Kok1, Profession::Uai1, AbsoluteSide::ASide),  // XA
+    (6, Color::Kok1, Profession::Kaun1, AbsoluteSide::ASide), // CA
+    (7, Color::Kok1, Profession::Maun1, AbsoluteSide::ASide), // MA
+    (8, Color::Kok1, Profession::Kua2, AbsoluteSide::ASide),  // PA
+    (9, Color::Kok1, Profession::Tuk2, AbsoluteSide::ASide),  // KE
+    (10, Color::Kok1, Profession::Gua2, AbsoluteSide::ASide), // LE
+    (12, Color::Kok1, Profession::Dau2, AbsoluteSide::ASide), // TE
+    (14, Color::Huok2, Profession::Dau2, AbsoluteSide::ASide), // XE
+    (16, Color::Huok2, Profession::Gua2, AbsoluteSide::ASide), // ME
+    (17, Color::Huok2, Profession::Tuk2, AbsoluteSide::ASide), // PE
+    (18, Color::Huok2, Profession::Kauk2, AbsoluteSide::ASide), // KI
+    (19, Color::Kok1, Profession::Kauk2, AbsoluteSide::ASide), // LI
+    (20, Color::Huok2, Profession::Kauk2, AbsoluteSide::ASide), // NI
+    (21, Color::Kok1, Profession::Kauk2, AbsoluteSide::ASide), // TI
+    (22, Color::Kok1, Profession::Nuak1, AbsoluteSide::ASide), // ZI
+    (23, Color::Kok1, Profession::Kauk2, AbsoluteSide::ASide), // XI
+    (24, Color::Huok2, Profession::Kauk2, AbsoluteSide::ASide), // CI
+    (25, Color::Kok1, Profession::Kauk2, AbsoluteSide::ASide), // MI
+    (26, Color::Huok2, Profession::Kauk2, AbsoluteSide::ASide), // PI
+    (54, Color::Huok2, Profession::Kauk2, AbsoluteSide::IASide), // KAI
+    (55, Color::Kok1, Profession::Kauk2, AbsoluteSide::IASide), // LAI
+    (56, Color::Huok2, Profession::Kauk2, AbsoluteSide::IASide), // NAI
+    (57, Color::Kok1, Profession::Kauk2, AbsoluteSide::IASide), // TAI
+    (58, Color::Huok2, Profession::Nuak1, AbsoluteSide::IASide), // ZAI
+    (59, Color::Kok1, Profession::Kauk2, AbsoluteSide::IASide), // XAI
+    (60, Color::Huok2, Profession::Kauk2, AbsoluteSide::IASide), // CAI
+    (61, Color::Kok1, Profession::Kauk2, AbsoluteSide::IASide), // MAI
+    (62, Color::Huok2, Profession::Kauk2, AbsoluteSide::IASide), // PAI
+    (63, Color::Huok2, Profession::Tuk2, AbsoluteSide::IASide), // KAU
+    (64, Color::Huok2, Profession::Gua2, AbsoluteSide::IASide), // LAU
+    (66, Color::Huok2, Profession::Dau2, AbsoluteSide::IASide), // TAU
+    (68, Color::Kok1, Profession::Dau2, AbsoluteSide::IASide), // XAU
+    (70, Color::Kok1, Profession::Gua2, AbsoluteSide::IASide), // MAU
+    (71, Color::Kok1, Profession::Tuk2, AbsoluteSide::IASide), // PAU
+    (72, Color::Kok1, Profession::Kua2, AbsoluteSide::IASide), // KIA
+    (73, Color::Kok1, Profession::Maun1, AbsoluteSide::IASide), // LIA
+    (74, Color::Kok1, Profession::Kaun1, AbsoluteSide::IASide), // NIA
+    (75, Color::Kok1, Profession::Uai1, AbsoluteSide::IASide), // TIA
+    (76, Color::Huok2, Profession::Io, AbsoluteSide::IASide), // ZIA
+    (77, Color::Huok2, Profession::Uai1, AbsoluteSide::IASide), // XIA
+    (78, Color::Huok2, Profession::Kaun1, AbsoluteSide::IASide), // CIA
+    (79, Color::Huok2, Profession::Maun1, AbsoluteSide::IASide), // MIA
+    (80, Color::Huok2, Profession::Kua2, AbsoluteSide::IASide), // PIA
+];
+
+/// Puts the pieces of the initial arrangement of the official (yhuap) rule on `board`, which the defaults of
+/// [`IsAbsoluteBoard::yhuap_initial`] and [`IsAbsoluteField::yhuap_initial`] call on an empty board
+///
+/// This goes through [`IsBoard::put`] rather than [`yhuap_initial_board`], since an implementation of `yhuap_initial` may call the latter.
+/// ／官定の初期配置の駒を `board` に置く。[`IsAbsoluteBoard::yhuap_initial`] と [`IsAbsoluteField::yhuap_initial`] の既定の実装が空の盤面に対して呼ぶ。
+/// `yhuap_initial` の実装が [`yhuap_initial_board`] を呼ぶこともあるので、後者ではなく [`IsBoard::put`] を通す。
+pub(crate) fn put_yhuap_initial<B: IsBoard>(board: &mut B)
+where
+    B::Coord: IsAbsoluteCoord,
+    B::PieceWithSide: IsPieceWithSide<Side = AbsoluteSide>,
+{
+    let coord =
+        |index| B::Coord::from_canonical_index(index).expect("a canonical index is below 81");
+    for &(index, color, prof, side) in YHUAP_INITIAL_PIECES {
+        board.put(
+            coord(index),
+            Some(B::PieceWithSide::non_tam2_piece(color, prof, side)),
+        );
+    }
+    board.put(coord(YHUAP_INITIAL_TAM2), Some(B::PieceWithSide::tam2()));
+}
+
+/// The board in the initial arrangement of the official (yhuap) rule, built from [`YHUAP_INITIAL_PIECES`]
+/// ／[`YHUAP_INITIAL_PIECES`] から組み立てた、官定の初期配置の盤面
+/// # Panics
+/// Panics if `R::index_to_coord` fails on a canonical index, or `R::AbsoluteBoard::from_piece_iter` rejects the arrangement.
+/// ／`R::index_to_coord` が正準な番号に対して失敗するか、`R::AbsoluteBoard::from_piece_iter` が配置を受け付けない場合にパニックする。
+#[must_use]
+pub fn yhuap_initial_board<R: CetkaikRepresentation>() -> R::AbsoluteBoard {
+    let coord = |index| R::index_to_coord(index).expect("a canonical index is below 81");
+    let pieces = YHUAP_INITIAL_PIECES
+        .iter()
+        .map(|&(index, color, prof, side)| {
+            (
+                coord(index),
+                R::AbsolutePiece::non_tam2_piece(color, prof, side),
+            )
+        })
+        .chain(core::iter::once((
+            coord(YHUAP_INITIAL_TAM2),
+            R::absolute_tam2(),
+        )));
+    R::AbsoluteBoard::from_piece_iter(pieces).expect("no two pieces share a square in yhuap")
+}
+
+/// The field in the initial arrangement of the official (yhuap) rule, with both hop1zuo1s empty
+/// ／官定の初期配置の `Field`。手駒はどちらも空である。
+/// # Panics
+/// Same as [`yhuap_initial_board`].
+/// ／[`yhuap_initial_board`] と同じ。
+#[must_use]
+pub fn yhuap_initial_field<R: CetkaikRepresentation>() -> R::AbsoluteField {
+    let mut field = R::AbsoluteField::empty();
+    *R::as_board_mut_absolute(&mut field) = yhuap_initial_board::<R>();
+    field
+}
+
+/// An initial arrangement written in canonical indices, from which [`CetkaikRepresentation::initial_with`] builds a field
+/// ／正準な番号で書かれた初期配置。[`CetkaikRepresentation::initial_with`] はこれから `Field` を組み立てる。
+///
+/// Besides the official [`ArrangementSpec::yhuap`], there are presets for handicap games and for teaching.
+/// ／官定の [`ArrangementSpec::yhuap`] のほかに、駒落ちや教習用のプリセットがある。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ArrangementSpec {
+    /// The square of Tam2
+    /// ／皇のマス
+    pub tam2: CanonicalCoord,
+    /// The pieces other than Tam2
+    /// ／皇以外の駒
+    pub pieces: Vec<(CanonicalCoord, Color, Profession, AbsoluteSide)>,
+}
+
+impl ArrangementSpec {
+    /// The initial arrangement of the official (yhuap) rule
+    /// ／官定の初期配置
+    #[must_use]
+    pub fn yhuap() -> Self {
+        Self {
+            tam2: YHUAP_INITIAL_TAM2,
+            pieces: YHUAP_INITIAL_PIECES.to_vec(),
+        }
+    }
+
+    /// The official arrangement in which `side` gives a handicap by starting without its pieces of the professions `profs`
+    /// ／`side` が `profs` の職種の駒を落として始める、官定の配置
+    #[must_use]
+    pub fn handicap(side: AbsoluteSide, profs: &[Profession]) -> Self {
+        Self::yhuap().retain(|_, _, prof, s| s != side || !profs.contains(&prof))
+    }
+
+    /// The official arrangement with only Io and Kauk2 left, for learning how the two move
+    /// ／王と兵だけを残した官定の配置。この二つの動きを学ぶためのもの。
+    #[must_use]
+    pub fn io_and_kauk2_only() -> Self {
+        Self::yhuap().retain(|_, _, prof, _| matches!(prof, Profession::Io | Profession::Kauk2))
+    }
+
+    /// Keeps only the pieces other than Tam2 for which `f` returns `true`
+    /// ／皇以外の駒のうち、`f` が `true` を返すものだけを残す
+    #[must_use]
+    pub fn retain(
+        mut self,
+        mut f: impl FnMut(CanonicalCoord, Color, Profession, AbsoluteSide) -> bool,
+    ) -> Self {
+        self.pieces
+            .retain(|&(coord, color, prof, side)| f(coord, color, prof, side));
+        self
+    }
+}
+
+/// Builds the field that `arrangement` describes, with both hop1zuo1s empty
+/// ／`arrangement` が表す `Field` を組み立てる。手駒はどちらも空である。
+/// # Errors
+/// Fails if a canonical index is out of range, or for the same reasons as [`FieldBuilder::build`].
+/// ／正準な番号が範囲外のとき、または [`FieldBuilder::build`] と同じ理由で失敗する。
+pub fn initial_with<R: CetkaikRepresentation>(
+    arrangement: &ArrangementSpec,
+) -> Result<R::AbsoluteField, SetupError> {
+    let coord = |index| R::index_to_coord(index).ok_or(SetupError::CoordOutOfRange(index));
+    let mut builder = FieldBuilder::<R>::new().tam2(coord(arrangement.tam2)?);
+    for &(index, color, prof, side) in &arrangement.pieces {
+        builder = builder.piece(coord(index)?, color, prof, side);
+    }
+    builder.build().map(|(field, _)| field)
+}
+
+/// How many pieces of each profession, in the order Nuak1, Kauk2, Gua2, Kaun1, Dau2, Maun1, Kua2, Tuk2, Uai1, Io, each color has
+const PIECES_PER_COLOR: [usize; 10] = [1, 8, 2, 2, 2, 2, 2, 2, 2, 1];
+
+/// The number of Tam2s, followed by the number of pieces of each color and profession, on the board and in the hop1zuo1s
+pub(crate) fn census<F>(field: &F) -> [usize; 21]
+where
+    F: IsAbsoluteField,
+    F::PieceWithSide: IsPieceWithSide,
+{
+    let mut census = [0; 21];
+    let board = field.as_board();
+    for c in board.occupied_squares() {
+        if let Some(piece) = board.peek(c) {
+            let kind = piece.match_on_piece_and_apply(&|| 0, &|color, prof, _| {
+                usize::from(1 + 10 * color_index(color) + prof_index(prof))
+            });
+            census[kind] += 1;
+        }
+    }
+    for side in [AbsoluteSide::ASide, AbsoluteSide::IASide] {
+        for ColorAndProf { color, prof } in field.hop1zuo1_of(side) {
+            census[usize::from(1 + 10 * color_index(color) + prof_index(prof))] += 1;
+        }
+    }
+    census
+}
+
+/// Checks a census as returned by [`census`]
+pub(crate) fn check_census(census: &[usize; 21]) -> Result<(), ValidationError> {
+    match census[0] {
+        0 => return Err(ValidationError::NoTam2),
+        1 => {}
+        _ => return Err(ValidationError::MoreThanOneTam2),
+    }
+    for (kind, &count) in (0_u8..).zip(&census[1..]) {
+        if count > PIECES_PER_COLOR[usize::from(kind % 10)] {
+            return Err(ValidationError::TooManyPieces {
+                color: color_from_index(kind / 10).expect("the census has two colors"),
+                prof: prof_from_index(kind % 10).expect("the census has ten professions"),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// A builder for arbitrary positions, which validates the pieces when built
+/// ／任意の局面を組み立てるビルダー。組み立てる際に駒の内訳を検証する。
+///
+/// The methods take and return the builder, so that calls can be chained.
+/// ／各メソッドはビルダーを受け取って返すので、呼び出しを連鎖できる。
+pub struct FieldBuilder<R: CetkaikRepresentation> {
+    pieces: Vec<(R::AbsoluteCoord, R::AbsolutePiece)>,
+    a_side_hop1zuo1: Vec<ColorAndProf>,
+    ia_side_hop1zuo1: Vec<ColorAndProf>,
+    whose_turn: AbsoluteSide,
+}
+
+impl<R: CetkaikRepresentation> Clone for FieldBuilder<R> {
+    fn clone(&self) -> Self {
+        Self {
+            pieces: self.pieces.clone(),
+            a_side_hop1zuo1: self.a_side_hop1zuo1.clone(),
+            ia_side_hop1zuo1: self.ia_side_hop1zuo1.clone(),
+            whose_turn: self.whose_turn,
+        }
+    }
+}
+
+impl<R: CetkaikRepresentation> core::fmt::Debug for FieldBuilder<R>
+where
+    R::AbsolutePiece: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FieldBuilder")
+            .field("pieces", &self.pieces)
+            .field("a_side_hop1zuo1", &self.a_side_hop1zuo1)
+            .field("ia_side_hop1zuo1", &self.ia_side_hop1zuo1)
+            .field("whose_turn", &self.whose_turn)
+            .finish()
+    }
+}
+
+impl<R: CetkaikRepresentation> Default for FieldBuilder<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: CetkaikRepresentation> FieldBuilder<R> {
+    /// A builder with an empty board, empty hop1zuo1s, and `IASide` to move
+    /// ／盤面も手駒も空で、`IASide` の手番であるビルダー
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            pieces: vec![],
+            a_side_hop1zuo1: vec![],
+            ia_side_hop1zuo1: vec![],
+            whose_turn: AbsoluteSide::IASide,
+        }
+    }
+
+    /// Places a piece on the board
+    /// ／盤上に駒を置く
+    #[must_use]
+    pub fn place(mut self, coord: R::AbsoluteCoord, piece: R::AbsolutePiece) -> Self {
+        self.pieces.push((coord, piece));
+        self
+    }
+
+    /// Places Tam2 on the board
+    /// ／盤上に皇を置く
+    #[must_use]
+    pub fn tam2(self, coord: R::AbsoluteCoord) -> Self {
+        self.place(coord, R::absolute_tam2())
+    }
+
+    /// Places a piece other than Tam2 on the board
+    /// ／盤上に皇以外の駒を置く
+    #[must_use]
+    pub fn piece(
+        self,
+        coord: R::AbsoluteCoord,
+        color: Color,
+        prof: Profession,
+        side: AbsoluteSide,
+    ) -> Self {
+        self.place(coord, R::AbsolutePiece::non_tam2_piece(color, prof, side))
+    }
+
+    /// Adds a piece to the hop1zuo1 of `side`
+    /// ／`side` の手駒に駒を加える
+    #[must_use]
+    pub fn hop1zuo1(mut self, side: AbsoluteSide, piece: ColorAndProf) -> Self {
+        match side {
+            AbsoluteSide::ASide => self.a_side_hop1zuo1.push(piece),
+            AbsoluteSide::IASide => self.ia_side_hop1zuo1.push(piece),
+        }
+        self
+    }
+
+    /// Chooses the side to move
+    /// ／手番の陣営を選ぶ
+    #[must_use]
+    pub const fn whose_turn(mut self, side: AbsoluteSide) -> Self {
+        self.whose_turn = side;
+        self
+    }
+
+    /// Builds the field, returning it together with the side to move
+    /// ／`Field` を組み立て、手番の陣営とともに返す
+    /// # Errors
+    /// Fails if more than one piece is placed on the same square, if there is not exactly one Tam2,
+    /// or if there are more pieces of a kind, counting both the board and the hop1zuo1s, than the game has.
+    /// ／同じマスに二つ以上の駒が置かれたとき、皇がちょうど一つでないとき、
+    /// 盤上と手駒を合わせて、ある種類の駒がゲームに存在する数より多いときに失敗する。
+    pub fn build(self) -> Result<(R::AbsoluteField, AbsoluteSide), SetupError> {
+        let mut field = R::AbsoluteField::empty();
+        *R::as_board_mut_absolute(&mut field) = R::AbsoluteBoard::from_piece_iter(self.pieces)?;
+        for piece in self.a_side_hop1zuo1 {
+            field.add_to_hop1zuo1(AbsoluteSide::ASide, piece);
+        }
+        for piece in self.ia_side_hop1zuo1 {
+            field.add_to_hop1zuo1(AbsoluteSide::IASide, piece);
+        }
+        field.validate()?;
+        Ok((field, self.whose_turn))
+    }
+}
@@ -0,0 +1,209 @@
+/// The iterator returned by [`IsBoard::coords_of`](crate::IsBoard::coords_of)
+/// ／[`IsBoard::coords_of`](crate::IsBoard::coords_of) が返すイテレータ
+///
+/// A representation that overrides `coords_of` with a bit scan can build one with [`FromIterator`].
+/// ／`coords_of` をビット走査で上書きする表現は、[`FromIterator`] を用いてこれを作ることができる。
+#[derive(Clone, Debug)]
+pub struct ProfIter<C>(std::vec::IntoIter<C>);
+
+impl<C> Iterator for ProfIter<C> {
+    type Item = C;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<C> DoubleEndedIterator for ProfIter<C> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<C> ExactSizeIterator for ProfIter<C> {}
+
+impl<C> core::iter::FusedIterator for ProfIter<C> {}
+
+impl<C> FromIterator<C> for ProfIter<C> {
+    fn from_iter<I: IntoIterator<Item = C>>(iter: I) -> Self {
+        Self(iter.into_iter().collect::<Vec<_>>().into_iter())
+    }
+}
+
+/// The iterator returned by [`IsMoveGenerator::guarded_squares`](crate::IsMoveGenerator::guarded_squares)
+/// ／[`IsMoveGenerator::guarded_squares`](crate::IsMoveGenerator::guarded_squares) が返すイテレータ
+///
+/// A move generator that overrides `guarded_squares` with a precomputed mask can build one with [`FromIterator`].
+/// ／`guarded_squares` を事前計算したマスクで上書きする指し手生成器は、[`FromIterator`] を用いてこれを作ることができる。
+#[derive(Clone, Debug)]
+pub struct GuardIter<C>(std::vec::IntoIter<C>);
+
+impl<C> Iterator for GuardIter<C> {
+    type Item = C;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<C> DoubleEndedIterator for GuardIter<C> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<C> ExactSizeIterator for GuardIter<C> {}
+
+impl<C> core::iter::FusedIterator for GuardIter<C> {}
+
+impl<C> FromIterator<C> for GuardIter<C> {
+    fn from_iter<I: IntoIterator<Item = C>>(iter: I) -> Self {
+        Self(iter.into_iter().collect::<Vec<_>>().into_iter())
+    }
+}
+
+/// The iterator returned by [`raw_destinations`](crate::raw_destinations)
+/// ／[`raw_destinations`](crate::raw_destinations) が返すイテレータ
+#[derive(Clone, Debug)]
+pub struct RawDestIter<C>(std::vec::IntoIter<C>);
+
+impl<C> Iterator for RawDestIter<C> {
+    type Item = C;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<C> DoubleEndedIterator for RawDestIter<C> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<C> ExactSizeIterator for RawDestIter<C> {}
+
+impl<C> core::iter::FusedIterator for RawDestIter<C> {}
+
+impl<C> FromIterator<C> for RawDestIter<C> {
+    fn from_iter<I: IntoIterator<Item = C>>(iter: I) -> Self {
+        Self(iter.into_iter().collect::<Vec<_>>().into_iter())
+    }
+}
+
+/// The iterator returned by [`StepDeclared::possible_finals`](crate::StepDeclared::possible_finals)
+/// ／[`StepDeclared::possible_finals`](crate::StepDeclared::possible_finals) が返すイテレータ
+#[derive(Clone, Debug)]
+pub struct FinalsIter<C>(std::vec::IntoIter<C>);
+
+impl<C> Iterator for FinalsIter<C> {
+    type Item = C;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<C> DoubleEndedIterator for FinalsIter<C> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<C> ExactSizeIterator for FinalsIter<C> {}
+
+impl<C> core::iter::FusedIterator for FinalsIter<C> {}
+
+impl<C> FromIterator<C> for FinalsIter<C> {
+    fn from_iter<I: IntoIterator<Item = C>>(iter: I) -> Self {
+        Self(iter.into_iter().collect::<Vec<_>>().into_iter())
+    }
+}
+
+/// The iterator returned by [`crate::CetkaikRepresentation::coords_in_display_order`]
+/// ／[`crate::CetkaikRepresentation::coords_in_display_order`] が返すイテレータ
+///
+/// A representation that overrides `coords_in_display_order` with a precomputed table can build one with [`FromIterator`].
+/// ／`coords_in_display_order` を事前計算した表で上書きする表現は、[`FromIterator`] を用いてこれを作ることができる。
+#[derive(Clone, Debug)]
+pub struct DisplayIter<C>(std::vec::IntoIter<C>);
+
+impl<C> Iterator for DisplayIter<C> {
+    type Item = C;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<C> DoubleEndedIterator for DisplayIter<C> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<C> ExactSizeIterator for DisplayIter<C> {}
+
+impl<C> core::iter::FusedIterator for DisplayIter<C> {}
+
+impl<C> FromIterator<C> for DisplayIter<C> {
+    fn from_iter<I: IntoIterator<Item = C>>(iter: I) -> Self {
+        Self(iter.into_iter().collect::<Vec<_>>().into_iter())
+    }
+}
+
+/// The iterator returned by [`crate::CetkaikRepresentation::coords_in_row`] and its relatives
+/// ／[`crate::CetkaikRepresentation::coords_in_row`] などが返すイテレータ
+///
+/// A representation that overrides them with precomputed tables can build one with [`FromIterator`].
+/// ／それらを事前計算した表で上書きする表現は、[`FromIterator`] を用いてこれを作ることができる。
+#[derive(Clone, Debug)]
+pub struct RegionIter<C>(std::vec::IntoIter<C>);
+
+impl<C> Iterator for RegionIter<C> {
+    type Item = C;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<C> DoubleEndedIterator for RegionIter<C> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<C> ExactSizeIterator for RegionIter<C> {}
+
+impl<C> core::iter::FusedIterator for RegionIter<C> {}
+
+impl<C> FromIterator<C> for RegionIter<C> {
+    fn from_iter<I: IntoIterator<Item = C>>(iter: I) -> Self {
+        Self(iter.into_iter().collect::<Vec<_>>().into_iter())
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{
+    apply_move_with_config, is_legal, CetkaikRepresentation, FinalsIter, IllegalMove, PureMove,
+    RuleConfig, SQUARE_COUNT,
+};
+use cetkaik_fundamental::{AbsoluteSide, PureMove_};
+
+/// A stepping move whose infinite movement has been declared but whose final destination is yet to be chosen
+/// ／踏越えて無限移動をすると宣言したが、最終的な移動先はまだ選んでいない手
+///
+/// An `InfAfterStep` is played in two phases: the mover declares `src`, `step` and a `planned_direction`, the sticks are cast,
+/// and then the mover picks a final destination in that direction no farther from `step` than the number of sticks that fell face up.
+/// The mover may also give up, leaving the piece at `src`.
+/// ／`InfAfterStep` は二段階で指される。指し手は `src`、`step`、`planned_direction` を宣言し、投げ棒を投げ、
+/// その方向で `step` からの距離が表の本数以下である最終的な移動先を選ぶ。指し手は諦めて駒を `src` に留めることもできる。
+pub struct StepDeclared<R: CetkaikRepresentation> {
+    field: R::AbsoluteField,
+    whose_turn: AbsoluteSide,
+    src: R::AbsoluteCoord,
+    step: R::AbsoluteCoord,
+    planned_direction: R::AbsoluteCoord,
+    config: RuleConfig,
+}
+
+impl<R: CetkaikRepresentation> Clone for StepDeclared<R> {
+    fn clone(&self) -> Self {
+        Self {
+            field: self.field.clone(),
+            ..*self
+        }
+    }
+}
+
+impl<R: CetkaikRepresentation> core::fmt::Debug for StepDeclared<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StepDeclared")
+            .field("field", &self.field)
+            .field("whose_turn", &self.whose_turn)
+            .field("src", &self.src)
+            .field("step", &self.step)
+            .field("planned_direction", &self.planned_direction)
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl<R: CetkaikRepresentation> StepDeclared<R> {
+    /// Declares the `InfAfterStep` move `mv` of `whose_turn` on `field`
+    /// ／`field` 上で `whose_turn` が `InfAfterStep` の手 `mv` を宣言する
+    /// # Errors
+    /// Fails with the reason given by [`is_legal`] if `mv` is illegal, and with [`IllegalMove::Unreachable`] if it is not an `InfAfterStep`.
+    /// ／`mv` が指せない手なら [`is_legal`] が与える理由で、`InfAfterStep` でなければ [`IllegalMove::Unreachable`] で失敗する。
+    pub fn new(
+        field: R::AbsoluteField,
+        mv: PureMove<R>,
+        whose_turn: AbsoluteSide,
+        config: &RuleConfig,
+    ) -> Result<Self, IllegalMove> {
+        let PureMove_::InfAfterStep {
+            src,
+            step,
+            planned_direction,
+        } = mv
+        else {
+            return Err(IllegalMove::Unreachable);
+        };
+        is_legal::<R>(&field, mv, whose_turn, config)?;
+        Ok(Self {
+            field,
+            whose_turn,
+            src,
+            step,
+            planned_direction,
+            config: *config,
+        })
+    }
+
+    /// The field before the move
+    /// ／指す前の `Field`
+    pub const fn field(&self) -> &R::AbsoluteField {
+        &self.field
+    }
+
+    /// The move as declared
+    /// ／宣言された手
+    pub const fn declared_move(&self) -> PureMove<R> {
+        self.toward(self.planned_direction)
+    }
+
+    const fn toward(&self, dest: R::AbsoluteCoord) -> PureMove<R> {
+        PureMove_::InfAfterStep {
+            src: self.src,
+            step: self.step,
+            planned_direction: dest,
+        }
+    }
+
+    /// Whether `dest` can be chosen as the final destination when `ciurl` sticks fell face up
+    /// ／表が `ciurl` 本のとき、`dest` を最終的な移動先に選べるかどうか
+    pub fn is_possible_final(&self, ciurl: usize, dest: R::AbsoluteCoord) -> bool {
+        usize::try_from(R::absolute_distance(self.step, dest)).is_ok_and(|d| d <= ciurl)
+            && R::absolute_same_direction(self.step, self.planned_direction, dest)
+            && is_legal::<R>(
+                &self.field,
+                self.toward(dest),
+                self.whose_turn,
+                &self.config,
+            )
+            .is_ok()
+    }
+
+    /// Lists the final destinations that can be chosen when `ciurl` sticks fell face up, in the order of canonical indices
+    /// ／表が `ciurl` 本のときに選べる最終的な移動先を、正準な番号の順に列挙する
+    pub fn possible_finals(&self, ciurl: usize) -> FinalsIter<R::AbsoluteCoord> {
+        (0..SQUARE_COUNT)
+            .filter_map(R::index_to_coord)
+            .filter(|&dest| self.is_possible_final(ciurl, dest))
+            .collect()
+    }
+
+    /// Completes the move with `ciurl` sticks face up, moving to `dest`, or leaving the piece at `src` if `dest` is `None`
+    /// ／表が `ciurl` 本の状態で手を完了する。`dest` に移動するか、`dest` が `None` なら駒を `src` に留める。
+    ///
+    /// If `dest` enters water, the water-entry cast is up to the caller, as it is for the other moves.
+    /// ／`dest` が入水となる場合の入水判定は、他の手と同様に呼び出し側に任せる。
+    /// # Errors
+    /// Fails with [`IllegalMove::Unreachable`] if `dest` is not one of [`StepDeclared::possible_finals`].
+    /// ／`dest` が [`StepDeclared::possible_finals`] のいずれでもなければ [`IllegalMove::Unreachable`] で失敗する。
+    pub fn finish(
+        self,
+        ciurl: usize,
+        dest: Option<R::AbsoluteCoord>,
+    ) -> Result<R::AbsoluteField, IllegalMove> {
+        let Some(dest) = dest else {
+            return Ok(self.field);
+        };
+        if !self.is_possible_final(ciurl, dest) {
+            return Err(IllegalMove::Unreachable);
+        }
+        apply_move_with_config::<R>(
+            &self.field,
+            self.toward(dest),
+            self.whose_turn,
+            &self.config,
+        )
+        .map_err(|_| IllegalMove::Unreachable)
+    }
+}
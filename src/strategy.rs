@@ -0,0 +1,101 @@
+use crate::{CetkaikRepresentation, IsAbsoluteField, IsField, IsMoveGenerator, PureMove};
+use cetkaik_fundamental::{AbsoluteSide, ColorAndProf};
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+fn side_strategy() -> impl Strategy<Value = AbsoluteSide> {
+    prop_oneof![Just(AbsoluteSide::ASide), Just(AbsoluteSide::IASide)]
+}
+
+fn play_out<R: CetkaikRepresentation, G: IsMoveGenerator<R>>(
+    rules: &G,
+    first_mover: AbsoluteSide,
+    choices: &[Index],
+) -> (R::AbsoluteField, AbsoluteSide) {
+    let mut choices = choices.iter();
+    let (field, whose_turn, _) = crate::movegen::play_out::<R, G>(
+        rules,
+        R::AbsoluteField::yhuap_initial(),
+        first_mover,
+        choices.len(),
+        |n| choices.next().map_or(0, |choice| choice.index(n)),
+    );
+    (field, whose_turn)
+}
+
+/// Generates a field reachable from the yhuap initial arrangement within `max_plies` moves, together with the side to move
+/// ／yhuap の初期配置から `max_plies` 手以内で到達できる `Field` を、手番の陣営とともに生成する
+///
+/// Since the field is obtained by actually playing the moves yielded by `rules`, it is always legal.
+/// ／`rules` が生成した手を実際に指して得られる `Field` なので、常に合法である。
+pub fn field_strategy<R, G>(
+    rules: G,
+    max_plies: usize,
+) -> impl Strategy<Value = (R::AbsoluteField, AbsoluteSide)>
+where
+    R: CetkaikRepresentation,
+    G: IsMoveGenerator<R>,
+{
+    (
+        side_strategy(),
+        proptest::collection::vec(any::<Index>(), 0..=max_plies),
+    )
+        .prop_map(move |(first_mover, choices)| play_out::<R, G>(&rules, first_mover, &choices))
+}
+
+/// Generates a board reachable from the yhuap initial arrangement within `max_plies` moves
+/// ／yhuap の初期配置から `max_plies` 手以内で到達できる盤面を生成する
+pub fn board_strategy<R, G>(rules: G, max_plies: usize) -> impl Strategy<Value = R::AbsoluteBoard>
+where
+    R: CetkaikRepresentation,
+    G: IsMoveGenerator<R>,
+{
+    field_strategy::<R, G>(rules, max_plies).prop_map(|(field, _)| field.as_board().clone())
+}
+
+/// Generates the hop1zuo1 of `side` in a field reachable from the yhuap initial arrangement within `max_plies` moves
+/// ／yhuap の初期配置から `max_plies` 手以内で到達できる `Field` における、`side` の手駒を生成する
+pub fn hop1zuo1_strategy<R, G>(
+    rules: G,
+    max_plies: usize,
+    side: AbsoluteSide,
+) -> impl Strategy<Value = Vec<ColorAndProf>>
+where
+    R: CetkaikRepresentation,
+    G: IsMoveGenerator<R>,
+{
+    field_strategy::<R, G>(rules, max_plies)
+        .prop_map(move |(field, _)| field.hop1zuo1_of(side).collect::<Vec<_>>())
+}
+
+/// Generates a field reachable within `max_plies` moves, the side to move, and one of the moves available to that side
+/// ／`max_plies` 手以内で到達できる `Field`、手番の陣営、およびその陣営が指せる手のひとつを生成する
+///
+/// Fields in which the side to move has no move are rejected.
+/// ／手番の陣営が指せる手が存在しない `Field` は棄却される。
+pub fn field_and_move_strategy<R, G>(
+    rules: G,
+    max_plies: usize,
+) -> impl Strategy<Value = (R::AbsoluteField, AbsoluteSide, PureMove<R>)>
+where
+    R: CetkaikRepresentation,
+    G: IsMoveGenerator<R>,
+{
+    (
+        side_strategy(),
+        proptest::collection::vec(any::<Index>(), 0..=max_plies),
+        any::<Index>(),
+    )
+        .prop_filter_map(
+            "the side to move has no move",
+            move |(first_mover, choices, choice)| {
+                let (field, whose_turn) = play_out::<R, G>(&rules, first_mover, &choices);
+                let moves = rules.all_moves(&field, whose_turn);
+                if moves.is_empty() {
+                    return None;
+                }
+                let mv = moves[choice.index(moves.len())];
+                Some((field, whose_turn, mv))
+            },
+        )
+}
@@ -0,0 +1,120 @@
+use crate::{
+    CanonicalPiece, CetkaikRepresentation, IsBoard, IsField, PureMove, COLS, COLS_U8, ROWS,
+    SQUARE_COUNT,
+};
+use cetkaik_fundamental::PureMove_;
+
+pub fn mirror_coord<R: CetkaikRepresentation>(coord: R::AbsoluteCoord) -> R::AbsoluteCoord {
+    let index = R::coord_to_index(coord);
+    R::index_to_coord(index - index % COLS_U8 + (COLS_U8 - 1 - index % COLS_U8))
+        .expect("the mirror image of a square is on the board")
+}
+
+pub fn mirror_board<R: CetkaikRepresentation>(board: &R::AbsoluteBoard) -> R::AbsoluteBoard {
+    let mut mirrored = board.clone();
+    for index in 0..SQUARE_COUNT {
+        let coord = R::index_to_coord(index).expect("every canonical index is on the board");
+        mirrored.put(mirror_coord::<R>(coord), board.peek(coord));
+    }
+    mirrored
+}
+
+pub fn mirror_field<R: CetkaikRepresentation>(field: &R::AbsoluteField) -> R::AbsoluteField {
+    let mut mirrored = field.clone();
+    *mirrored.as_board_mut() = mirror_board::<R>(field.as_board());
+    mirrored
+}
+
+/// The squares in the order of canonical indices, each as 0 if empty and `1 + code` otherwise
+fn board_key<R: CetkaikRepresentation>(board: &R::AbsoluteBoard) -> [u8; ROWS * COLS] {
+    let mut key = [0; ROWS * COLS];
+    for (index, k) in (0..).zip(&mut key) {
+        let coord = R::index_to_coord(index).expect("every canonical index is on the board");
+        *k = board
+            .peek(coord)
+            .map_or(0, |piece| 1 + CanonicalPiece::from_piece(piece).code());
+    }
+    key
+}
+
+pub fn canonicalize_field<R: CetkaikRepresentation>(
+    field: &R::AbsoluteField,
+) -> (R::AbsoluteField, bool) {
+    let mirrored = mirror_field::<R>(field);
+    if board_key::<R>(mirrored.as_board()) < board_key::<R>(field.as_board()) {
+        (mirrored, true)
+    } else {
+        (field.clone(), false)
+    }
+}
+
+pub fn mirror_move<R: CetkaikRepresentation>(mv: PureMove<R>) -> PureMove<R> {
+    let m = mirror_coord::<R>;
+    match mv {
+        PureMove_::InfAfterStep {
+            src,
+            step,
+            planned_direction,
+        } => PureMove_::InfAfterStep {
+            src: m(src),
+            step: m(step),
+            planned_direction: m(planned_direction),
+        },
+        PureMove_::NonTamMoveFromHopZuo { color, prof, dest } => PureMove_::NonTamMoveFromHopZuo {
+            color,
+            prof,
+            dest: m(dest),
+        },
+        PureMove_::NonTamMoveSrcDst {
+            src,
+            dest,
+            is_water_entry_ciurl,
+        } => PureMove_::NonTamMoveSrcDst {
+            src: m(src),
+            dest: m(dest),
+            is_water_entry_ciurl,
+        },
+        PureMove_::NonTamMoveSrcStepDstFinite {
+            src,
+            step,
+            dest,
+            is_water_entry_ciurl,
+        } => PureMove_::NonTamMoveSrcStepDstFinite {
+            src: m(src),
+            step: m(step),
+            dest: m(dest),
+            is_water_entry_ciurl,
+        },
+        PureMove_::TamMoveNoStep {
+            src,
+            first_dest,
+            second_dest,
+        } => PureMove_::TamMoveNoStep {
+            src: m(src),
+            first_dest: m(first_dest),
+            second_dest: m(second_dest),
+        },
+        PureMove_::TamMoveStepsDuringFormer {
+            src,
+            step,
+            first_dest,
+            second_dest,
+        } => PureMove_::TamMoveStepsDuringFormer {
+            src: m(src),
+            step: m(step),
+            first_dest: m(first_dest),
+            second_dest: m(second_dest),
+        },
+        PureMove_::TamMoveStepsDuringLatter {
+            src,
+            step,
+            first_dest,
+            second_dest,
+        } => PureMove_::TamMoveStepsDuringLatter {
+            src: m(src),
+            step: m(step),
+            first_dest: m(first_dest),
+            second_dest: m(second_dest),
+        },
+    }
+}
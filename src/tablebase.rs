@@ -0,0 +1,201 @@
+use crate::{
+    CanonicalPiece, CetkaikRepresentation, IsAbsoluteField, IsBoard, IsField, IsPieceWithSide,
+    MaterialCount, COLS, ROWS,
+};
+use cetkaik_fundamental::{AbsoluteSide, Color, ColorAndProf, Profession};
+
+/// The number of squares on the board
+const SQUARES: u64 = (ROWS * COLS) as u64;
+
+/// The binomial coefficient `n` choose `k`, for `n` up to 81
+fn choose(n: u64, k: u64) -> u64 {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    let mut result: u128 = 1;
+    for i in 0..k {
+        result = result * u128::from(n - i) / u128::from(i + 1);
+    }
+    u64::try_from(result).unwrap_or(u64::MAX)
+}
+
+/// The number of ways to place `count` identical pieces, each either on a distinct square or in the hop1zuo1
+fn group_size(count: u8) -> u64 {
+    (0..=u64::from(count)).map(|j| choose(SQUARES, j)).sum()
+}
+
+/// The pieces of a field, distinguished by color, profession and side, that a tablebase is built for
+/// ／テーブルベースが対象とする `Field` の駒の内訳。色・職種・所属側で区別する。
+///
+/// This refines [`IsAbsoluteField::material_signature`], which does not distinguish colors,
+/// so that every position with the same `TbMaterial` can be numbered by [`tb_index`] from 0 to [`TbMaterial::index_count`].
+/// A piece of a side counts whether it is on the board or in the hop1zuo1 of that side. Tam2 is not counted, as there is always exactly one.
+/// ／色を区別しない [`IsAbsoluteField::material_signature`] を細分したものであり、同じ `TbMaterial` を持つ局面はすべて
+/// [`tb_index`] によって 0 から [`TbMaterial::index_count`] までの番号が付けられる。
+/// ある陣営の駒は、盤上にあってもその陣営の手駒にあっても数える。皇は常にちょうど一つなので数えない。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct TbMaterial {
+    /// indexed by the canonical piece code minus one
+    counts: [u8; 40],
+}
+
+impl TbMaterial {
+    /// The material of `field`
+    /// ／`field` の駒の内訳
+    pub fn of<R: CetkaikRepresentation>(field: &R::AbsoluteField) -> Self {
+        let mut counts = [0; 40];
+        let board = field.as_board();
+        for c in board.occupied_squares() {
+            if let Some(CanonicalPiece::NonTam2Piece { color, prof, side }) =
+                board.peek(c).map(CanonicalPiece::from_piece)
+            {
+                counts[group_of(color, prof, side)] += 1;
+            }
+        }
+        for side in [AbsoluteSide::ASide, AbsoluteSide::IASide] {
+            for ColorAndProf { color, prof } in field.hop1zuo1_of(side) {
+                counts[group_of(color, prof, side)] += 1;
+            }
+        }
+        Self { counts }
+    }
+
+    /// The number of indices, or `None` if it does not fit in a `u64`
+    /// ／番号の個数。`u64` に収まらなければ `None`
+    ///
+    /// Some indices below this do not stand for a position, because two pieces would share a square.
+    /// ／これより小さい番号の中には、二つの駒が同じマスを占めることになるため局面を表さないものもある。
+    #[must_use]
+    pub fn index_count(&self) -> Option<u64> {
+        self.counts
+            .iter()
+            .filter(|&&count| count > 0)
+            .try_fold(SQUARES, |acc, &count| acc.checked_mul(group_size(count)))
+    }
+
+    /// The [`IsAbsoluteField::material_signature`] of the fields with this material
+    /// ／この駒の内訳を持つ `Field` の [`IsAbsoluteField::material_signature`]
+    #[must_use]
+    pub fn material_signature(&self) -> u64 {
+        let signature = |side: &[u8]| {
+            let mut counts = [0; 10];
+            for (i, &count) in side.iter().enumerate() {
+                counts[i % 10] += count;
+            }
+            MaterialCount::from_counts(counts).signature()
+        };
+        u64::from(signature(&self.counts[20..])) << 32 | u64::from(signature(&self.counts[..20]))
+    }
+}
+
+/// The index into `TbMaterial::counts` of the pieces of `color` and `prof` belonging to `side`
+fn group_of(color: Color, prof: Profession, side: AbsoluteSide) -> usize {
+    usize::from(CanonicalPiece::NonTam2Piece { color, prof, side }.code() - 1)
+}
+
+/// Numbers `field` densely among the positions with the same [`TbMaterial`]
+/// ／同じ [`TbMaterial`] を持つ局面の中で、`field` に詰めた番号を付ける
+///
+/// The index depends only on the canonical indices of the squares, so a table built with one representation can be probed with another.
+/// Whose turn it is is not part of the index; keep one table per side to move.
+/// ／番号はマスの正準な番号のみに依存するので、ある表現で作ったテーブルを別の表現で引くことができる。
+/// 手番は番号に含まれないので、手番ごとに別のテーブルを持つこと。
+///
+/// Returns `None` if there is not exactly one Tam2 on the board, or if [`TbMaterial::index_count`] does not fit in a `u64`.
+/// ／盤上の皇がちょうど一つでないか、[`TbMaterial::index_count`] が `u64` に収まらなければ `None` を返す。
+pub fn tb_index<R: CetkaikRepresentation>(field: &R::AbsoluteField) -> Option<u64> {
+    let material = TbMaterial::of::<R>(field);
+    material.index_count()?;
+    let board = field.as_board();
+    let mut tam2 = None;
+    let mut squares: [Vec<u64>; 40] = core::array::from_fn(|_| vec![]);
+    for c in board.occupied_squares() {
+        let index = u64::from(R::coord_to_index(c));
+        match board.peek(c).map(CanonicalPiece::from_piece) {
+            Some(CanonicalPiece::Tam2) if tam2.is_none() => tam2 = Some(index),
+            Some(CanonicalPiece::Tam2) => return None,
+            Some(CanonicalPiece::NonTam2Piece { color, prof, side }) => {
+                squares[group_of(color, prof, side)].push(index);
+            }
+            None => {}
+        }
+    }
+    let mut index = tam2?;
+    let mut radix = SQUARES;
+    for (group, &count) in squares.iter_mut().zip(&material.counts) {
+        if count == 0 {
+            continue;
+        }
+        group.sort_unstable();
+        let on_board = group.len() as u64;
+        let digit = (0..on_board).map(|j| choose(SQUARES, j)).sum::<u64>()
+            + (1..)
+                .zip(group.iter())
+                .map(|(i, &sq)| choose(sq, i))
+                .sum::<u64>();
+        index += digit * radix;
+        radix *= group_size(count);
+    }
+    Some(index)
+}
+
+/// The inverse of [`tb_index`]: the position with the material `material` numbered `index`
+/// ／[`tb_index`] の逆。駒の内訳が `material` で番号が `index` である局面
+///
+/// Returns `None` if `index` is out of range or does not stand for a position because two pieces would share a square.
+/// The pieces in each hop1zuo1 are listed in the canonical order.
+/// ／`index` が範囲外であるか、二つの駒が同じマスを占めることになり局面を表さなければ `None` を返す。
+/// 各手駒の駒は正準な順に並ぶ。
+#[must_use]
+pub fn tb_position<R: CetkaikRepresentation>(
+    material: &TbMaterial,
+    index: u64,
+) -> Option<R::AbsoluteField> {
+    if index >= material.index_count()? {
+        return None;
+    }
+    let mut field = R::AbsoluteField::empty();
+    let tam2 = R::index_to_coord(u8::try_from(index % SQUARES).ok()?)?;
+    field.as_board_mut().put(tam2, Some(R::absolute_tam2()));
+    let mut rest = index / SQUARES;
+    for (code, &count) in (1..).zip(&material.counts) {
+        if count == 0 {
+            continue;
+        }
+        let size = group_size(count);
+        let mut digit = rest % size;
+        rest /= size;
+        let mut on_board = 0;
+        while digit >= choose(SQUARES, on_board) {
+            digit -= choose(SQUARES, on_board);
+            on_board += 1;
+        }
+        let Some(CanonicalPiece::NonTam2Piece { color, prof, side }) =
+            CanonicalPiece::from_code(code)
+        else {
+            return None;
+        };
+        let piece = R::AbsolutePiece::non_tam2_piece(color, prof, side);
+        for i in (1..=on_board).rev() {
+            let mut sq = i - 1;
+            while choose(sq + 1, i) <= digit {
+                sq += 1;
+            }
+            digit -= choose(sq, i);
+            let coord = R::index_to_coord(u8::try_from(sq).ok()?)?;
+            if field.as_board().is_occupied(coord) {
+                return None;
+            }
+            field.as_board_mut().put(coord, Some(piece));
+        }
+        for _ in on_board..u64::from(count) {
+            field.add_to_hop1zuo1(side, ColorAndProf { color, prof });
+        }
+    }
+    Some(field)
+}
@@ -0,0 +1,126 @@
+use crate::{IsAbsoluteField, IsBoard};
+use cetkaik_fundamental::{AbsoluteSide, ColorAndProf};
+
+enum Undo<C, P> {
+    Square {
+        coord: C,
+        before: Option<P>,
+    },
+    Mov {
+        from: C,
+        to: C,
+    },
+    Added {
+        side: AbsoluteSide,
+        piece: ColorAndProf,
+    },
+    Removed {
+        side: AbsoluteSide,
+        piece: ColorAndProf,
+    },
+}
+
+/// A set of mutations on a field that can be undone, obtained by [`IsAbsoluteField::begin_transaction`]
+/// ／取り消すことのできる `Field` への一連の変更。[`IsAbsoluteField::begin_transaction`] によって得られる。
+///
+/// Every mutation made through the transaction is journaled, so [`Transaction::rollback`] restores the field
+/// without ever cloning it. Dropping the transaction without calling [`Transaction::commit`] also rolls it back.
+/// The hop1zuo1s are restored as multisets; the order in which their pieces are listed may change.
+/// ／トランザクションを通じた変更はすべて記録されるので、[`Transaction::rollback`] は `Field` を一度もクローンせずに元に戻す。
+/// [`Transaction::commit`] を呼ばずにトランザクションを破棄した場合も元に戻る。
+/// 手駒は多重集合として復元されるので、手駒が列挙される順序は変わりうる。
+pub struct Transaction<'a, F: IsAbsoluteField> {
+    field: &'a mut F,
+    journal: Vec<Undo<F::Coord, F::PieceWithSide>>,
+}
+
+impl<'a, F: IsAbsoluteField> Transaction<'a, F>
+where
+    F::Coord: Copy,
+    F::PieceWithSide: Copy,
+{
+    pub(crate) const fn new(field: &'a mut F) -> Self {
+        Self {
+            field,
+            journal: vec![],
+        }
+    }
+
+    /// The field in its current state
+    /// ／現在の状態の `Field`
+    #[must_use]
+    pub const fn field(&self) -> &F {
+        self.field
+    }
+
+    /// Looks at a square
+    /// ／マスを見る
+    pub fn peek(&self, c: F::Coord) -> Option<F::PieceWithSide> {
+        self.field.as_board().peek(c)
+    }
+
+    /// Removes the piece at a square and returns it
+    /// ／マスの駒を取り除いて返す
+    pub fn pop(&mut self, c: F::Coord) -> Option<F::PieceWithSide> {
+        let before = self.field.as_board_mut().pop(c);
+        self.journal.push(Undo::Square { coord: c, before });
+        before
+    }
+
+    /// Puts either a piece or a `None` at a square
+    /// ／マスに駒または `None` を置く
+    pub fn put(&mut self, c: F::Coord, p: Option<F::PieceWithSide>) {
+        let before = self.peek(c);
+        self.field.as_board_mut().put(c, p);
+        self.journal.push(Undo::Square { coord: c, before });
+    }
+
+    /// Moves the piece located at `from` to an empty square `to`, as in [`IsBoard::mov`]
+    /// ／[`IsBoard::mov`] と同様に、`from` にある駒を空きマス `to` に動かす
+    pub fn mov(&mut self, from: F::Coord, to: F::Coord) {
+        self.field.as_board_mut().mov(from, to);
+        self.journal.push(Undo::Mov { from, to });
+    }
+
+    /// Adds a piece to the hop1zuo1 of `side`
+    /// ／`side` の手駒に駒を加える
+    pub fn add_to_hop1zuo1(&mut self, side: AbsoluteSide, piece: ColorAndProf) {
+        self.field.add_to_hop1zuo1(side, piece);
+        self.journal.push(Undo::Added { side, piece });
+    }
+
+    /// Removes one copy of a piece from the hop1zuo1 of `side`, returning whether it was found
+    /// ／`side` の手駒から駒をひとつ取り除き、見つかったかどうかを返す
+    pub fn remove_from_hop1zuo1(&mut self, side: AbsoluteSide, piece: ColorAndProf) -> bool {
+        let found = self.field.remove_from_hop1zuo1(side, piece);
+        if found {
+            self.journal.push(Undo::Removed { side, piece });
+        }
+        found
+    }
+
+    /// Keeps every mutation made so far
+    /// ／これまでの変更をすべて確定する
+    pub fn commit(mut self) {
+        self.journal.clear();
+    }
+
+    /// Undoes every mutation made so far
+    /// ／これまでの変更をすべて取り消す
+    pub fn rollback(self) {}
+}
+
+impl<F: IsAbsoluteField> Drop for Transaction<'_, F> {
+    fn drop(&mut self) {
+        while let Some(undo) = self.journal.pop() {
+            match undo {
+                Undo::Square { coord, before } => self.field.as_board_mut().put(coord, before),
+                Undo::Mov { from, to } => self.field.as_board_mut().mov(to, from),
+                Undo::Added { side, piece } => {
+                    self.field.remove_from_hop1zuo1(side, piece);
+                }
+                Undo::Removed { side, piece } => self.field.add_to_hop1zuo1(side, piece),
+            }
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// The largest number of shards a [`TranspositionTable`] is split into
+const MAX_SHARDS: usize = 64;
+
+/// The slots of a shard, each holding an entry together with the key it was stored under
+type Shard<K, E> = Vec<Option<(K, E)>>;
+
+/// A trait that signifies that you can use it as the key of a [`TranspositionTable`]
+/// ／[`TranspositionTable`] のキーとして使える型を表すトレイト
+///
+/// The table picks the slot of a key from the low bits of [`TableKey::slot_bits`], so they should be well mixed, as those of a Zobrist hash are.
+/// ／表はキーの枠を [`TableKey::slot_bits`] の下位ビットから選ぶので、Zobrist ハッシュのようによく混ざっているべきである。
+pub trait TableKey: Copy + Eq {
+    /// The bits from which the slot is picked
+    /// ／枠を選ぶのに用いるビット
+    fn slot_bits(self) -> u64;
+}
+
+/// A Zobrist hash such as [`crate::HashedField::hash`], used as it is
+/// ／[`crate::HashedField::hash`] のような Zobrist ハッシュ。そのまま用いる。
+impl TableKey for u64 {
+    fn slot_bits(self) -> u64 {
+        self
+    }
+}
+
+/// A wider key such as [`crate::IsGameState::book_key`], whose two halves are folded together
+/// ／[`crate::IsGameState::book_key`] のような幅の広いキー。上下の半分を畳み込む。
+#[allow(clippy::cast_possible_truncation)]
+impl TableKey for u128 {
+    fn slot_bits(self) -> u64 {
+        self as u64 ^ (self >> 64) as u64
+    }
+}
+
+/// Decides whether an entry stored in a [`TranspositionTable`] gives way to a new one that falls into the same slot
+/// ／[`TranspositionTable`] に格納された項目が、同じ枠に入る新しい項目に場所を譲るかどうかを決める
+///
+/// Any `Fn(&E, &E) -> bool` taking the old entry and then the new one is a policy.
+/// ／古い項目、新しい項目の順に受け取る任意の `Fn(&E, &E) -> bool` は方針として使える。
+pub trait ReplacementPolicy<E> {
+    /// Whether `new` replaces `old`, which was stored under a different or the same key
+    /// ／（異なる、または同じキーで格納された）`old` を `new` で置き換えるかどうか
+    fn replaces(&self, old: &E, new: &E) -> bool;
+}
+
+impl<E, F: Fn(&E, &E) -> bool> ReplacementPolicy<E> for F {
+    fn replaces(&self, old: &E, new: &E) -> bool {
+        self(old, new)
+    }
+}
+
+/// The policy under which a new entry always replaces the old one
+/// ／新しい項目が常に古い項目を置き換える方針
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AlwaysReplace;
+
+impl<E> ReplacementPolicy<E> for AlwaysReplace {
+    fn replaces(&self, _old: &E, _new: &E) -> bool {
+        true
+    }
+}
+
+/// A fixed-size hash table of search results, which can be shared between threads
+/// ／探索結果を格納する固定サイズのハッシュ表。スレッド間で共有できる。
+///
+/// The table is keyed by a [`TableKey`] `K`, by default a 64-bit hash of a position such as [`crate::HashedField::hash`] or [`crate::CetkaikRepresentation::repetition_key`],
+/// and keeps at most one entry per slot; which of two colliding entries is kept is decided by the [`ReplacementPolicy`] `P`.
+/// The slots are split into shards, each behind its own lock, so threads rarely wait for each other.
+/// ／表は [`TableKey`] `K`（既定では [`crate::HashedField::hash`] や [`crate::CetkaikRepresentation::repetition_key`] などの局面の 64 ビットのハッシュ）をキーとし、
+/// 一つの枠に高々一つの項目を保持する。衝突した二つの項目のどちらを残すかは [`ReplacementPolicy`] `P` が決める。
+/// 枠はそれぞれ独自のロックを持つシャードに分かれているので、スレッドが互いを待つことはまれである。
+#[derive(Debug)]
+pub struct TranspositionTable<E, P = AlwaysReplace, K = u64> {
+    shards: Box<[Mutex<Shard<K, E>>]>,
+    mask: u64,
+    policy: P,
+}
+
+impl<E, P: Default, K: TableKey> TranspositionTable<E, P, K> {
+    /// A table with room for at least `capacity` entries, rounded up to a power of two, under the default policy
+    /// ／少なくとも `capacity` 個（2 の冪に切り上げる）の項目を格納できる、既定の方針の表
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self::with_policy(capacity, P::default())
+    }
+}
+
+impl<E, P, K: TableKey> TranspositionTable<E, P, K> {
+    /// A table with room for at least `capacity` entries, rounded up to a power of two, under `policy`
+    /// ／少なくとも `capacity` 個（2 の冪に切り上げる）の項目を格納できる、方針 `policy` の表
+    pub fn with_policy(capacity: usize, policy: P) -> Self {
+        let capacity = capacity.max(1).next_power_of_two();
+        let shard_count = capacity.min(MAX_SHARDS);
+        let shards = (0..shard_count)
+            .map(|_| {
+                Mutex::new(
+                    std::iter::repeat_with(|| None)
+                        .take(capacity / shard_count)
+                        .collect(),
+                )
+            })
+            .collect();
+        Self {
+            shards,
+            mask: capacity as u64 - 1,
+            policy,
+        }
+    }
+
+    /// The number of slots
+    /// ／枠の数
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.shards.len() * self.lock(0).len()
+    }
+
+    /// Locks the shard `shard`, ignoring the poison left by a thread that panicked, since every entry is valid on its own
+    fn lock(&self, shard: usize) -> MutexGuard<'_, Shard<K, E>> {
+        self.shards[shard]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The shard and the position in it of the slot for `key`
+    #[allow(clippy::cast_possible_truncation)]
+    fn slot(&self, key: K) -> (usize, usize) {
+        let index = (key.slot_bits() & self.mask) as usize;
+        (index % self.shards.len(), index / self.shards.len())
+    }
+
+    /// The entry stored under `key`, if any
+    /// ／`key` で格納された項目。なければ `None`
+    pub fn get(&self, key: K) -> Option<E>
+    where
+        E: Clone,
+    {
+        let (shard, pos) = self.slot(key);
+        match &self.lock(shard)[pos] {
+            Some((stored, entry)) if *stored == key => Some(entry.clone()),
+            _ => None,
+        }
+    }
+
+    /// Stores `entry` under `key` unless the policy keeps the entry already in its slot, and returns whether it was stored
+    /// ／方針が枠にある既存の項目を残すのでない限り `entry` を `key` で格納し、格納したかどうかを返す
+    pub fn insert(&self, key: K, entry: E) -> bool
+    where
+        P: ReplacementPolicy<E>,
+    {
+        let (shard, pos) = self.slot(key);
+        let slot = &mut self.lock(shard)[pos];
+        if slot
+            .as_ref()
+            .is_some_and(|(_, old)| !self.policy.replaces(old, &entry))
+        {
+            return false;
+        }
+        *slot = Some((key, entry));
+        true
+    }
+
+    /// Removes every entry
+    /// ／すべての項目を取り除く
+    pub fn clear(&self) {
+        for shard in 0..self.shards.len() {
+            self.lock(shard).fill_with(|| None);
+        }
+    }
+}
@@ -0,0 +1,491 @@
+use crate::dynamic::sort_hop1zuo1;
+use crate::{
+    CanonicalPiece, CetkaikRepresentation, DynField, ErasedField, COLS_U8, ROWS_U8, SQUARE_COUNT,
+};
+use cetkaik_fundamental::{AbsoluteSide, Color, ColorAndProf, Profession, PureMove_};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::{wasm_bindgen, JsValue};
+
+/// A square written as its canonical index
+/// ／正準な番号で書かれたマス
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WasmCoord {
+    index: u8,
+}
+
+// `#[wasm_bindgen]` cannot export a `const fn`
+#[allow(clippy::missing_const_for_fn)]
+#[wasm_bindgen]
+impl WasmCoord {
+    /// The square at row `row` and column `col`, each counted from 0, or `undefined` if out of the board
+    /// ／0 から数えて `row` 行 `col` 列目のマス。盤外なら `undefined`
+    #[wasm_bindgen(constructor)]
+    #[must_use]
+    pub fn new(row: u8, col: u8) -> Option<Self> {
+        if row < ROWS_U8 && col < COLS_U8 {
+            Some(Self {
+                index: COLS_U8 * row + col,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// The square with the canonical index `index`, or `undefined` if it is 81 or more
+    /// ／正準な番号が `index` のマス。81 以上なら `undefined`
+    #[must_use]
+    pub fn from_index(index: u8) -> Option<Self> {
+        if index < SQUARE_COUNT {
+            Some(Self { index })
+        } else {
+            None
+        }
+    }
+
+    /// The canonical index／正準な番号
+    #[must_use]
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// The row, counted from 0／0 から数えた行
+    #[must_use]
+    pub fn row(&self) -> u8 {
+        self.index / COLS_U8
+    }
+
+    /// The column, counted from 0／0 から数えた列
+    #[must_use]
+    pub fn col(&self) -> u8 {
+        self.index % COLS_U8
+    }
+}
+
+/// A move whose squares are written as canonical indices
+/// ／マスを正準な番号で書いた指し手
+///
+/// Its JSON form is an object tagged by `"type"` with the name of the variant of [`PureMove_`], the other keys being the fields of that variant.
+/// ／JSON 形式では、`"type"` に [`PureMove_`] の列挙子名を持ち、残りのキーにその列挙子のフィールドを持つオブジェクトになる。
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WasmMove(PureMove_<u8>);
+
+#[wasm_bindgen]
+impl WasmMove {
+    /// Parses the JSON form, returning `undefined` if it is malformed
+    /// ／JSON 形式を読む。不正であれば `undefined` を返す。
+    #[must_use]
+    pub fn from_json(json: &str) -> Option<Self> {
+        serde_json::from_str::<MoveJson>(json)
+            .ok()
+            .map(|mv| Self(mv.into()))
+    }
+
+    /// Writes out the JSON form／JSON 形式で書き出す
+    /// # Errors
+    /// Throws the message of the serializer if serialization fails.
+    /// ／書き出しに失敗すれば、シリアライザのメッセージを投げる。
+    pub fn to_json(&self) -> Result<String, JsValue> {
+        to_json_string(&MoveJson::from(self.0))
+    }
+}
+
+impl From<PureMove_<u8>> for WasmMove {
+    fn from(mv: PureMove_<u8>) -> Self {
+        Self(mv)
+    }
+}
+
+impl From<WasmMove> for PureMove_<u8> {
+    fn from(mv: WasmMove) -> Self {
+        mv.0
+    }
+}
+
+/// A field of any representation
+/// ／任意の表現の `Field`
+///
+/// Since a `#[wasm_bindgen]` function cannot be generic, export a constructor of your own that converts from [`ErasedField`], e.g. `ErasedField::<R>::yhuap_initial().into()`.
+/// ／`#[wasm_bindgen]` な関数はジェネリックにできないので、`ErasedField::<R>::yhuap_initial().into()` のように [`ErasedField`] から変換するコンストラクタを各自で公開すること。
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct WasmField(Box<dyn DynField>);
+
+#[wasm_bindgen]
+impl WasmField {
+    /// Looks at a square, returning the JSON form of the piece, or `null` if the square is empty
+    /// ／マスを見て、駒の JSON 形式を返す。空きマスなら `null` を返す。
+    /// # Errors
+    /// Throws the message of the serializer if serialization fails.
+    /// ／書き出しに失敗すれば、シリアライザのメッセージを投げる。
+    pub fn peek(&self, coord: &WasmCoord) -> Result<String, JsValue> {
+        to_json_string(&self.0.peek(coord.index).map(PieceJson::from))
+    }
+
+    /// Applies a move in the manner of [`crate::apply_move`], returning `undefined` if it cannot be applied
+    /// ／指し手を [`crate::apply_move`] と同様に適用する。適用できなければ `undefined` を返す。
+    #[must_use]
+    pub fn apply_move(&self, mv: &WasmMove, is_ia_side: bool) -> Option<Self> {
+        let whose_turn = if is_ia_side {
+            AbsoluteSide::IASide
+        } else {
+            AbsoluteSide::ASide
+        };
+        self.0.apply_move(mv.0, whose_turn).ok().map(Self)
+    }
+
+    /// Writes out the JSON form／JSON 形式で書き出す
+    ///
+    /// The form is an object with `"board"`, an array of the 81 squares in the order of canonical indices, and `"a_side_hop1zuo1"` and `"ia_side_hop1zuo1"`,
+    /// each listing the pieces in the canonical order (see [`crate::IsAbsoluteField::hop1zuo1_sorted`]).
+    /// ／形式は、81 マスを正準な番号順に並べた配列 `"board"` と、駒を正準な順序（[`crate::IsAbsoluteField::hop1zuo1_sorted`] を参照）で並べた
+    /// `"a_side_hop1zuo1"`・`"ia_side_hop1zuo1"` を持つオブジェクトである。
+    /// # Errors
+    /// Throws the message of the serializer if serialization fails.
+    /// ／書き出しに失敗すれば、シリアライザのメッセージを投げる。
+    pub fn to_json(&self) -> Result<String, JsValue> {
+        let sorted = |side| {
+            let mut hop1zuo1 = self.0.hop1zuo1_of(side);
+            sort_hop1zuo1(&mut hop1zuo1);
+            hop1zuo1
+        };
+        to_json_string(&FieldJson {
+            board: (0..SQUARE_COUNT)
+                .map(|i| self.0.peek(i).map(PieceJson::from))
+                .collect(),
+            a_side_hop1zuo1: sorted(AbsoluteSide::ASide),
+            ia_side_hop1zuo1: sorted(AbsoluteSide::IASide),
+        })
+    }
+}
+
+impl From<Box<dyn DynField>> for WasmField {
+    fn from(field: Box<dyn DynField>) -> Self {
+        Self(field)
+    }
+}
+
+impl<R: CetkaikRepresentation + 'static> From<ErasedField<R>> for WasmField {
+    fn from(field: ErasedField<R>) -> Self {
+        Self(Box::new(field))
+    }
+}
+
+/// Serializes `value` into JSON, turning a failure into an exception
+fn to_json_string<T: Serialize>(value: &T) -> Result<String, JsValue> {
+    serde_json::to_string(value).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+struct FieldJson {
+    board: Vec<Option<PieceJson>>,
+    #[cfg_attr(feature = "schemars", schemars(schema_with = "hop1zuo1_schema"))]
+    a_side_hop1zuo1: Vec<ColorAndProf>,
+    #[cfg_attr(feature = "schemars", schemars(schema_with = "hop1zuo1_schema"))]
+    ia_side_hop1zuo1: Vec<ColorAndProf>,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(
+    feature = "schemars",
+    derive(schemars::JsonSchema),
+    schemars(rename = "Piece")
+)]
+#[serde(tag = "type")]
+enum PieceJson {
+    Tam2,
+    NonTam2Piece {
+        #[cfg_attr(feature = "schemars", schemars(schema_with = "color_schema"))]
+        color: Color,
+        #[cfg_attr(feature = "schemars", schemars(schema_with = "prof_schema"))]
+        prof: Profession,
+        #[cfg_attr(feature = "schemars", schemars(schema_with = "side_schema"))]
+        side: AbsoluteSide,
+    },
+}
+
+impl From<CanonicalPiece> for PieceJson {
+    fn from(piece: CanonicalPiece) -> Self {
+        match piece {
+            CanonicalPiece::Tam2 => Self::Tam2,
+            CanonicalPiece::NonTam2Piece { color, prof, side } => {
+                Self::NonTam2Piece { color, prof, side }
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type")]
+enum MoveJson {
+    InfAfterStep {
+        src: u8,
+        step: u8,
+        planned_direction: u8,
+    },
+    NonTamMoveFromHopZuo {
+        #[cfg_attr(feature = "schemars", schemars(schema_with = "color_schema"))]
+        color: Color,
+        #[cfg_attr(feature = "schemars", schemars(schema_with = "prof_schema"))]
+        prof: Profession,
+        dest: u8,
+    },
+    NonTamMoveSrcDst {
+        src: u8,
+        dest: u8,
+        is_water_entry_ciurl: bool,
+    },
+    NonTamMoveSrcStepDstFinite {
+        src: u8,
+        step: u8,
+        dest: u8,
+        is_water_entry_ciurl: bool,
+    },
+    TamMoveNoStep {
+        src: u8,
+        first_dest: u8,
+        second_dest: u8,
+    },
+    TamMoveStepsDuringFormer {
+        src: u8,
+        step: u8,
+        first_dest: u8,
+        second_dest: u8,
+    },
+    TamMoveStepsDuringLatter {
+        src: u8,
+        step: u8,
+        first_dest: u8,
+        second_dest: u8,
+    },
+}
+
+impl From<PureMove_<u8>> for MoveJson {
+    fn from(mv: PureMove_<u8>) -> Self {
+        match mv {
+            PureMove_::InfAfterStep {
+                src,
+                step,
+                planned_direction,
+            } => Self::InfAfterStep {
+                src,
+                step,
+                planned_direction,
+            },
+            PureMove_::NonTamMoveFromHopZuo { color, prof, dest } => {
+                Self::NonTamMoveFromHopZuo { color, prof, dest }
+            }
+            PureMove_::NonTamMoveSrcDst {
+                src,
+                dest,
+                is_water_entry_ciurl,
+            } => Self::NonTamMoveSrcDst {
+                src,
+                dest,
+                is_water_entry_ciurl,
+            },
+            PureMove_::NonTamMoveSrcStepDstFinite {
+                src,
+                step,
+                dest,
+                is_water_entry_ciurl,
+            } => Self::NonTamMoveSrcStepDstFinite {
+                src,
+                step,
+                dest,
+                is_water_entry_ciurl,
+            },
+            PureMove_::TamMoveNoStep {
+                src,
+                first_dest,
+                second_dest,
+            } => Self::TamMoveNoStep {
+                src,
+                first_dest,
+                second_dest,
+            },
+            PureMove_::TamMoveStepsDuringFormer {
+                src,
+                step,
+                first_dest,
+                second_dest,
+            } => Self::TamMoveStepsDuringFormer {
+                src,
+                step,
+                first_dest,
+                second_dest,
+            },
+            PureMove_::TamMoveStepsDuringLatter {
+                src,
+                step,
+                first_dest,
+                second_dest,
+            } => Self::TamMoveStepsDuringLatter {
+                src,
+                step,
+                first_dest,
+                second_dest,
+            },
+        }
+    }
+}
+
+impl From<MoveJson> for PureMove_<u8> {
+    fn from(mv: MoveJson) -> Self {
+        match mv {
+            MoveJson::InfAfterStep {
+                src,
+                step,
+                planned_direction,
+            } => Self::InfAfterStep {
+                src,
+                step,
+                planned_direction,
+            },
+            MoveJson::NonTamMoveFromHopZuo { color, prof, dest } => {
+                Self::NonTamMoveFromHopZuo { color, prof, dest }
+            }
+            MoveJson::NonTamMoveSrcDst {
+                src,
+                dest,
+                is_water_entry_ciurl,
+            } => Self::NonTamMoveSrcDst {
+                src,
+                dest,
+                is_water_entry_ciurl,
+            },
+            MoveJson::NonTamMoveSrcStepDstFinite {
+                src,
+                step,
+                dest,
+                is_water_entry_ciurl,
+            } => Self::NonTamMoveSrcStepDstFinite {
+                src,
+                step,
+                dest,
+                is_water_entry_ciurl,
+            },
+            MoveJson::TamMoveNoStep {
+                src,
+                first_dest,
+                second_dest,
+            } => Self::TamMoveNoStep {
+                src,
+                first_dest,
+                second_dest,
+            },
+            MoveJson::TamMoveStepsDuringFormer {
+                src,
+                step,
+                first_dest,
+                second_dest,
+            } => Self::TamMoveStepsDuringFormer {
+                src,
+                step,
+                first_dest,
+                second_dest,
+            },
+            MoveJson::TamMoveStepsDuringLatter {
+                src,
+                step,
+                first_dest,
+                second_dest,
+            } => Self::TamMoveStepsDuringLatter {
+                src,
+                step,
+                first_dest,
+                second_dest,
+            },
+        }
+    }
+}
+
+#[cfg(feature = "schemars")]
+fn string_enum(values: &[&str]) -> schemars::Schema {
+    schemars::json_schema!({ "type": "string", "enum": values })
+}
+
+#[cfg(feature = "schemars")]
+fn color_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    string_enum(&[Color::Kok1, Color::Huok2].map(cetkaik_fundamental::serialize_color))
+}
+
+#[cfg(feature = "schemars")]
+fn prof_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    use Profession::{Dau2, Gua2, Io, Kauk2, Kaun1, Kua2, Maun1, Nuak1, Tuk2, Uai1};
+    string_enum(
+        &[Nuak1, Kauk2, Gua2, Kaun1, Dau2, Maun1, Kua2, Tuk2, Uai1, Io]
+            .map(cetkaik_fundamental::serialize_prof),
+    )
+}
+
+#[cfg(feature = "schemars")]
+fn side_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    string_enum(&["ASide", "IASide"])
+}
+
+#[cfg(feature = "schemars")]
+fn hop1zuo1_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    schemars::json_schema!({
+        "type": "array",
+        "items": {
+            "type": "object",
+            "properties": {
+                "color": color_schema(generator),
+                "prof": prof_schema(generator),
+            },
+            "required": ["color", "prof"],
+        },
+    })
+}
+
+/// The canonical index, an integer from 0 to 80
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for WasmCoord {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Coord".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({ "type": "integer", "minimum": 0, "maximum": 80 })
+    }
+}
+
+/// The form read by [`WasmMove::from_json`] and written by [`WasmMove::to_json`]
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for WasmMove {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Move".into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        MoveJson::json_schema(generator)
+    }
+}
+
+/// The form written by [`WasmField::peek`], without the `null` for an empty square
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for CanonicalPiece {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Piece".into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        PieceJson::json_schema(generator)
+    }
+}
+
+/// The form written by [`WasmField::to_json`]
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for WasmField {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Field".into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        FieldJson::json_schema(generator)
+    }
+}
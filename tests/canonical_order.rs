@@ -0,0 +1,116 @@
+mod common;
+
+use cetkaik_fundamental::{AbsoluteSide, Color, ColorAndProf, Profession};
+use cetkaik_traits::{
+    CetkaikRepresentation, Hop1Zuo1Counts, IsAbsoluteBoard, IsAbsoluteField, IsBoard, IsField,
+    IsHop1Zuo1, IsPieceWithSide,
+};
+use common::{sq, Field, Mock, Piece};
+
+/// The professions in the canonical order
+const PROFESSIONS: [Profession; 10] = [
+    Profession::Nuak1,
+    Profession::Kauk2,
+    Profession::Gua2,
+    Profession::Kaun1,
+    Profession::Dau2,
+    Profession::Maun1,
+    Profession::Kua2,
+    Profession::Tuk2,
+    Profession::Uai1,
+    Profession::Io,
+];
+
+const fn piece(color: Color, prof: Profession) -> ColorAndProf {
+    ColorAndProf { color, prof }
+}
+
+#[test]
+fn empty_squares_sorted_ascends_by_canonical_index() {
+    let mut board = <Mock as CetkaikRepresentation>::AbsoluteBoard::empty();
+    for i in 0..81 {
+        board.put(common::Coord(i), Some(Piece::tam2()));
+    }
+    for name in ["PIA", "ZO", "LE", "KA"] {
+        board.put(sq(name), None);
+    }
+
+    // the mock lists its squares from the last canonical index to the first
+    assert_eq!(
+        board.empty_squares().collect::<Vec<_>>(),
+        [sq("PIA"), sq("ZO"), sq("LE"), sq("KA")]
+    );
+    assert_eq!(
+        Mock::empty_squares_sorted(&board),
+        [sq("KA"), sq("LE"), sq("ZO"), sq("PIA")]
+    );
+}
+
+#[test]
+fn empty_squares_sorted_of_the_initial_board() {
+    let field = Mock::yhuap_initial_field();
+    let squares = Mock::empty_squares_sorted(field.as_board());
+    assert_eq!(squares.len(), field.as_board().empty_squares().len());
+    assert!(squares
+        .windows(2)
+        .all(|w| Mock::coord_to_index(w[0]) < Mock::coord_to_index(w[1])));
+    assert_eq!(squares.first(), Some(&sq("NE")));
+    assert_eq!(squares.last(), Some(&sq("CAU")));
+}
+
+#[test]
+fn hop1zuo1_sorted_is_by_color_then_profession() {
+    let mut field = Field::empty();
+    let added = [
+        piece(Color::Huok2, Profession::Io),
+        piece(Color::Kok1, Profession::Nuak1),
+        piece(Color::Huok2, Profession::Kauk2),
+        piece(Color::Kok1, Profession::Io),
+        piece(Color::Kok1, Profession::Kauk2),
+        piece(Color::Kok1, Profession::Nuak1),
+        piece(Color::Huok2, Profession::Nuak1),
+        piece(Color::Kok1, Profession::Dau2),
+    ];
+    for p in added {
+        field.add_to_hop1zuo1(AbsoluteSide::ASide, p);
+    }
+
+    // the mock keeps the pieces in the order they were added
+    assert_eq!(
+        field.hop1zuo1_of(AbsoluteSide::ASide).collect::<Vec<_>>(),
+        added
+    );
+    assert_eq!(
+        field.hop1zuo1_sorted(AbsoluteSide::ASide),
+        [
+            piece(Color::Kok1, Profession::Nuak1),
+            piece(Color::Kok1, Profession::Nuak1),
+            piece(Color::Kok1, Profession::Kauk2),
+            piece(Color::Kok1, Profession::Dau2),
+            piece(Color::Kok1, Profession::Io),
+            piece(Color::Huok2, Profession::Nuak1),
+            piece(Color::Huok2, Profession::Kauk2),
+            piece(Color::Huok2, Profession::Io),
+        ]
+    );
+    assert!(field.hop1zuo1_sorted(AbsoluteSide::IASide).is_empty());
+}
+
+#[test]
+fn hop1zuo1_sorted_covers_every_kind() {
+    let canonical = [Color::Kok1, Color::Huok2]
+        .into_iter()
+        .flat_map(|color| PROFESSIONS.map(|prof| piece(color, prof)))
+        .collect::<Vec<_>>();
+
+    let mut field = Field::empty();
+    for &p in canonical.iter().rev() {
+        field.add_to_hop1zuo1(AbsoluteSide::IASide, p);
+    }
+    assert_eq!(field.hop1zuo1_sorted(AbsoluteSide::IASide), canonical);
+
+    // a counted hop1zuo1 yields the same order without sorting
+    let counts = canonical.iter().rev().copied().collect::<Hop1Zuo1Counts>();
+    assert_eq!(counts.iter().collect::<Vec<_>>(), canonical);
+    assert_eq!(counts.sorted(), canonical);
+}
@@ -0,0 +1,546 @@
+//! A minimal representation for the tests, deliberately storing things in non-canonical orders:
+//! the board lists its squares from the last canonical index to the first,
+//! and each hop1zuo1 keeps its pieces in the order they were added.
+
+#![allow(dead_code)]
+
+pub mod reference;
+
+use cetkaik_fundamental::{AbsoluteSide, Color, ColorAndProf, Profession, PureMove_};
+use cetkaik_traits::{
+    policy_index_to_move, CetkaikRepresentation, IsAbsoluteBoard, IsAbsoluteCoord, IsAbsoluteField,
+    IsBitBoard, IsBoard, IsCoord, IsField, IsHop1Zuo1, IsPieceWithSide, NotationStyle, PureMove,
+    COLS, POLICY_SIZE, ROWS, YHUAP_INITIAL_TAM2,
+};
+
+/// An absolute square, holding its canonical index
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Coord(pub u8);
+
+impl IsCoord for Coord {
+    fn is_water(self) -> bool {
+        to_rc(self.0).is_water()
+    }
+}
+
+impl IsAbsoluteCoord for Coord {
+    fn from_canonical_index(index: u8) -> Option<Self> {
+        (usize::from(index) < ROWS * COLS).then_some(Self(index))
+    }
+}
+
+fn to_rc(index: u8) -> [usize; 2] {
+    [usize::from(index) / COLS, usize::from(index) % COLS]
+}
+
+fn from_rc([row, column]: [usize; 2]) -> u8 {
+    u8::try_from(COLS * row + column).unwrap()
+}
+
+/// Converts a coordinate to and from the position in [`Board`]
+pub trait Square: Copy + Eq + core::fmt::Debug {
+    fn slot(self) -> usize;
+    fn from_slot(slot: usize) -> Self;
+}
+
+impl Square for Coord {
+    fn slot(self) -> usize {
+        usize::from(self.0)
+    }
+    fn from_slot(slot: usize) -> Self {
+        Self(u8::try_from(slot).unwrap())
+    }
+}
+
+impl Square for [usize; 2] {
+    fn slot(self) -> usize {
+        COLS * self[0] + self[1]
+    }
+    fn from_slot(slot: usize) -> Self {
+        [slot / COLS, slot % COLS]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Piece<S> {
+    Tam2,
+    NonTam2Piece {
+        color: Color,
+        prof: Profession,
+        side: S,
+    },
+}
+
+impl<S: Copy + Eq> IsPieceWithSide for Piece<S> {
+    type Side = S;
+
+    fn match_on_piece_and_apply<U>(
+        self,
+        f_tam: &dyn Fn() -> U,
+        f_piece: &dyn Fn(Color, Profession, Self::Side) -> U,
+    ) -> U {
+        match self {
+            Self::Tam2 => f_tam(),
+            Self::NonTam2Piece { color, prof, side } => f_piece(color, prof, side),
+        }
+    }
+    fn non_tam2_piece(color: Color, prof: Profession, side: Self::Side) -> Self {
+        Self::NonTam2Piece { color, prof, side }
+    }
+    fn tam2() -> Self {
+        Self::Tam2
+    }
+}
+
+impl Piece<AbsoluteSide> {
+    fn key(self) -> u8 {
+        self.to_canonical_u8()
+    }
+}
+
+impl Piece<Side> {
+    fn key(self) -> u8 {
+        match self {
+            Self::Tam2 => 0,
+            Self::NonTam2Piece { color, prof, side } => {
+                1 + color as u8 * 20 + prof as u8 * 2 + u8::from(side == Side::Downward)
+            }
+        }
+    }
+}
+
+impl PartialOrd for Piece<AbsoluteSide> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Piece<AbsoluteSide> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl PartialOrd for Piece<Side> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Piece<Side> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Board<C, P> {
+    squares: [Option<P>; ROWS * COLS],
+    _coord: core::marker::PhantomData<C>,
+}
+
+impl<C, P: Copy> Board<C, P> {
+    const fn new() -> Self {
+        Self {
+            squares: [None; ROWS * COLS],
+            _coord: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<C: Square, P: Copy> Board<C, P> {
+    /// The squares satisfying `f`, from the last canonical index to the first
+    fn squares_where(&self, f: impl Fn(Option<P>) -> bool) -> std::vec::IntoIter<C> {
+        (0..ROWS * COLS)
+            .rev()
+            .filter(|&slot| f(self.squares[slot]))
+            .map(C::from_slot)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+impl<C: Square, P: Copy> IsBoard for Board<C, P> {
+    type PieceWithSide = P;
+    type Coord = C;
+
+    fn peek(&self, c: C) -> Option<P> {
+        self.squares[c.slot()]
+    }
+    fn pop(&mut self, c: C) -> Option<P> {
+        self.squares[c.slot()].take()
+    }
+    fn put(&mut self, c: C, p: Option<P>) {
+        self.squares[c.slot()] = p;
+    }
+
+    type EmptySquaresIter = std::vec::IntoIter<C>;
+    fn empty_squares(&self) -> Self::EmptySquaresIter {
+        self.squares_where(|p| p.is_none())
+    }
+
+    type OccupiedSquaresIter = std::vec::IntoIter<C>;
+    fn occupied_squares(&self) -> Self::OccupiedSquaresIter {
+        self.squares_where(|p| p.is_some())
+    }
+}
+
+impl IsAbsoluteBoard for Board<Coord, Piece<AbsoluteSide>> {
+    fn empty() -> Self {
+        Self::new()
+    }
+}
+
+/// A hop1zuo1 that keeps its pieces in the order they were added
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Hand(Vec<ColorAndProf>);
+
+impl IsHop1Zuo1 for Hand {
+    type Iter = std::vec::IntoIter<ColorAndProf>;
+
+    fn iter(&self) -> Self::Iter {
+        self.0.clone().into_iter()
+    }
+    fn push(&mut self, piece: ColorAndProf) {
+        self.0.push(piece);
+    }
+    fn remove(&mut self, piece: ColorAndProf) -> bool {
+        let Some(i) = self.0.iter().position(|&p| p == piece) else {
+            return false;
+        };
+        self.0.remove(i);
+        true
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    pub board: Board<Coord, Piece<AbsoluteSide>>,
+    pub a_side_hop1zuo1: Hand,
+    pub ia_side_hop1zuo1: Hand,
+}
+
+impl Field {
+    fn hop1zuo1_mut(&mut self, side: AbsoluteSide) -> &mut Hand {
+        match side {
+            AbsoluteSide::ASide => &mut self.a_side_hop1zuo1,
+            AbsoluteSide::IASide => &mut self.ia_side_hop1zuo1,
+        }
+    }
+}
+
+impl IsField for Field {
+    type Board = Board<Coord, Piece<AbsoluteSide>>;
+    type Coord = Coord;
+    type PieceWithSide = Piece<AbsoluteSide>;
+    type Side = AbsoluteSide;
+
+    fn move_nontam_piece_from_src_to_dest_while_taking_opponent_piece_if_needed(
+        &self,
+        from: Coord,
+        to: Coord,
+        whose_turn: AbsoluteSide,
+    ) -> Result<Self, &'static str> {
+        let mut field = self.clone();
+        let Some(Piece::NonTam2Piece { side, .. }) = field.board.peek(from) else {
+            return Err("no movable piece at the source");
+        };
+        if side != whose_turn {
+            return Err("the piece belongs to the opponent");
+        }
+        if from == to {
+            // a stepping move may come back to where it started
+            return Ok(field);
+        }
+        match field.board.peek(to) {
+            None => {}
+            Some(Piece::NonTam2Piece { color, prof, side }) if side != whose_turn => {
+                field.add_to_hop1zuo1(whose_turn, ColorAndProf { color, prof });
+            }
+            Some(_) => return Err("the destination is occupied"),
+        }
+        let piece = field.board.pop(from);
+        field.board.put(to, piece);
+        Ok(field)
+    }
+
+    fn search_from_hop1zuo1_and_parachute_at(
+        &self,
+        color: Color,
+        prof: Profession,
+        side: AbsoluteSide,
+        dest: Coord,
+    ) -> Option<Self> {
+        let mut field = self.clone();
+        if field.board.is_occupied(dest)
+            || !field.remove_from_hop1zuo1(side, ColorAndProf { color, prof })
+        {
+            return None;
+        }
+        field
+            .board
+            .put(dest, Some(Piece::non_tam2_piece(color, prof, side)));
+        Some(field)
+    }
+
+    fn as_board(&self) -> &Self::Board {
+        &self.board
+    }
+    fn as_board_mut(&mut self) -> &mut Self::Board {
+        &mut self.board
+    }
+}
+
+impl IsAbsoluteField for Field {
+    fn empty() -> Self {
+        Self {
+            board: Board::new(),
+            a_side_hop1zuo1: Hand::default(),
+            ia_side_hop1zuo1: Hand::default(),
+        }
+    }
+
+    type Hop1Zuo1 = Hand;
+    fn hop1zuo1(&self, side: AbsoluteSide) -> &Hand {
+        match side {
+            AbsoluteSide::ASide => &self.a_side_hop1zuo1,
+            AbsoluteSide::IASide => &self.ia_side_hop1zuo1,
+        }
+    }
+    fn add_to_hop1zuo1(&mut self, side: AbsoluteSide, piece: ColorAndProf) {
+        self.hop1zuo1_mut(side).push(piece);
+    }
+    fn remove_from_hop1zuo1(&mut self, side: AbsoluteSide, piece: ColorAndProf) -> bool {
+        self.hop1zuo1_mut(side).remove(piece)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
+    Upward,
+    Downward,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RelativeField {
+    pub board: Board<[usize; 2], Piece<Side>>,
+    pub upward_hop1zuo1: Hand,
+    pub downward_hop1zuo1: Hand,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Perspective {
+    IaIsDownAndPointsUpward,
+    IaIsUpAndPointsDownward,
+}
+
+/// The representation under test
+#[derive(Clone, Copy, Debug)]
+pub struct Mock;
+
+impl CetkaikRepresentation for Mock {
+    const TAM2_INITIAL: Coord = Coord(YHUAP_INITIAL_TAM2);
+
+    type Perspective = Perspective;
+    type AbsoluteCoord = Coord;
+    type RelativeCoord = [usize; 2];
+    type AbsoluteBoard = Board<Coord, Piece<AbsoluteSide>>;
+    type RelativeBoard = Board<[usize; 2], Piece<Side>>;
+    type AbsolutePiece = Piece<AbsoluteSide>;
+    type RelativePiece = Piece<Side>;
+    type AbsoluteField = Field;
+    type RelativeField = RelativeField;
+    type RelativeSide = Side;
+
+    fn to_absolute_coord(coord: [usize; 2], p: Perspective) -> Coord {
+        match p {
+            Perspective::IaIsDownAndPointsUpward => Coord(from_rc(coord)),
+            Perspective::IaIsUpAndPointsDownward => {
+                Coord(from_rc([ROWS - 1 - coord[0], COLS - 1 - coord[1]]))
+            }
+        }
+    }
+    fn to_relative_coord(coord: Coord, p: Perspective) -> [usize; 2] {
+        let [row, column] = to_rc(coord.0);
+        match p {
+            Perspective::IaIsDownAndPointsUpward => [row, column],
+            Perspective::IaIsUpAndPointsDownward => [ROWS - 1 - row, COLS - 1 - column],
+        }
+    }
+    fn add_delta(coord: [usize; 2], row_delta: isize, col_delta: isize) -> Option<[usize; 2]> {
+        let row = coord[0]
+            .checked_add_signed(row_delta)
+            .filter(|&r| r < ROWS)?;
+        let column = coord[1]
+            .checked_add_signed(col_delta)
+            .filter(|&c| c < COLS)?;
+        Some([row, column])
+    }
+    fn is_tam_hue_by_default(coord: [usize; 2]) -> bool {
+        matches!(
+            coord,
+            [2, 2] | [2, 6] | [3, 3] | [3, 5] | [4, 4] | [5, 3] | [5, 5] | [6, 2] | [6, 6]
+        )
+    }
+    fn relative_tam2() -> Piece<Side> {
+        Piece::Tam2
+    }
+    fn absolute_tam2() -> Piece<AbsoluteSide> {
+        Piece::Tam2
+    }
+    fn is_upward(s: Side) -> bool {
+        s == Side::Upward
+    }
+    fn as_board_absolute(field: &Field) -> &Self::AbsoluteBoard {
+        &field.board
+    }
+    fn as_board_mut_absolute(field: &mut Field) -> &mut Self::AbsoluteBoard {
+        &mut field.board
+    }
+    fn as_board_relative(field: &RelativeField) -> &Self::RelativeBoard {
+        &field.board
+    }
+    fn as_board_mut_relative(field: &mut RelativeField) -> &mut Self::RelativeBoard {
+        &mut field.board
+    }
+    fn loop_over_one_side_and_tam(
+        board: &Self::RelativeBoard,
+        side: Side,
+        f_tam_or_piece: &mut dyn FnMut([usize; 2], Option<Profession>),
+    ) {
+        for c in board.occupied_squares() {
+            match board.peek(c) {
+                Some(Piece::Tam2) => f_tam_or_piece(c, None),
+                Some(Piece::NonTam2Piece { prof, side: s, .. }) if s == side => {
+                    f_tam_or_piece(c, Some(prof));
+                }
+                _ => {}
+            }
+        }
+    }
+    fn to_relative_field(field: Field, p: Perspective) -> RelativeField {
+        let mut board = Board::new();
+        for c in field.board.occupied_squares() {
+            let piece = match field.board.peek(c) {
+                Some(Piece::NonTam2Piece { color, prof, side }) => Piece::NonTam2Piece {
+                    color,
+                    prof,
+                    side: Self::to_relative_side(side, p),
+                },
+                _ => Piece::Tam2,
+            };
+            board.put(Self::to_relative_coord(c, p), Some(piece));
+        }
+        let upward = Self::to_absolute_side(Side::Upward, p);
+        RelativeField {
+            board,
+            upward_hop1zuo1: field.hop1zuo1(upward).clone(),
+            downward_hop1zuo1: field.hop1zuo1(!upward).clone(),
+        }
+    }
+    fn to_absolute_field(field: RelativeField, p: Perspective) -> Field {
+        let mut absolute = Field::empty();
+        for c in field.board.occupied_squares() {
+            let piece = match field.board.peek(c) {
+                Some(Piece::NonTam2Piece { color, prof, side }) => Piece::NonTam2Piece {
+                    color,
+                    prof,
+                    side: Self::to_absolute_side(side, p),
+                },
+                _ => Piece::Tam2,
+            };
+            absolute
+                .board
+                .put(Self::to_absolute_coord(c, p), Some(piece));
+        }
+        let upward = Self::to_absolute_side(Side::Upward, p);
+        *absolute.hop1zuo1_mut(upward) = field.upward_hop1zuo1;
+        *absolute.hop1zuo1_mut(!upward) = field.downward_hop1zuo1;
+        absolute
+    }
+    fn to_relative_side(side: AbsoluteSide, p: Perspective) -> Side {
+        match (side, p) {
+            (AbsoluteSide::IASide, Perspective::IaIsDownAndPointsUpward)
+            | (AbsoluteSide::ASide, Perspective::IaIsUpAndPointsDownward) => Side::Upward,
+            _ => Side::Downward,
+        }
+    }
+    fn to_absolute_side(side: Side, p: Perspective) -> AbsoluteSide {
+        match (side, p) {
+            (Side::Upward, Perspective::IaIsDownAndPointsUpward)
+            | (Side::Downward, Perspective::IaIsUpAndPointsDownward) => AbsoluteSide::IASide,
+            _ => AbsoluteSide::ASide,
+        }
+    }
+    fn get_one_perspective() -> Perspective {
+        Perspective::IaIsDownAndPointsUpward
+    }
+    fn flip_perspective(p: Perspective) -> Perspective {
+        match p {
+            Perspective::IaIsDownAndPointsUpward => Perspective::IaIsUpAndPointsDownward,
+            Perspective::IaIsUpAndPointsDownward => Perspective::IaIsDownAndPointsUpward,
+        }
+    }
+    fn coord_to_index(coord: Coord) -> u8 {
+        coord.0
+    }
+    fn absolute_distance(a: Coord, b: Coord) -> i32 {
+        let ([r1, c1], [r2, c2]) = (to_rc(a.0), to_rc(b.0));
+        i32::try_from(r1.abs_diff(r2).max(c1.abs_diff(c2))).unwrap()
+    }
+    fn absolute_same_direction(origin: Coord, a: Coord, b: Coord) -> bool {
+        let delta = |c: Coord| {
+            let ([r0, c0], [r, c]) = (to_rc(origin.0), to_rc(c.0));
+            [r as isize - r0 as isize, c as isize - c0 as isize]
+        };
+        let ([a_u, a_v], [b_u, b_v]) = (delta(a), delta(b));
+        a_u * b_u + a_v * b_v > 0 && a_u * b_v - a_v * b_u == 0
+    }
+}
+
+/// The masks are scanned square by square
+impl IsBitBoard for Mock {}
+
+/// The square with the Latin name `name`, the column followed by the row, e.g. `"LAU"`
+pub fn sq(name: &str) -> Coord {
+    const COLUMNS: [&str; COLS] = ["K", "L", "N", "T", "Z", "X", "C", "M", "P"];
+    const ROW_NAMES: [&str; ROWS] = ["A", "E", "I", "U", "O", "Y", "AI", "AU", "IA"];
+    let (column, row) = name.split_at(1);
+    let column = COLUMNS.iter().position(|&c| c == column).unwrap();
+    let row = ROW_NAMES.iter().position(|&r| r == row).unwrap();
+    Coord(from_rc([row, column]))
+}
+
+/// Every style of writing the squares
+pub const NOTATION_STYLES: [NotationStyle; 3] = [
+    NotationStyle::Latin,
+    NotationStyle::Kanji,
+    NotationStyle::Numeric,
+];
+
+/// The moves decoded from every `step`-th policy index, together with a parachute of every kind
+pub fn sample_moves(step: usize) -> Vec<PureMove<Mock>> {
+    let parachutes = [Color::Kok1, Color::Huok2].into_iter().flat_map(|color| {
+        [
+            Profession::Nuak1,
+            Profession::Kauk2,
+            Profession::Gua2,
+            Profession::Kaun1,
+            Profession::Dau2,
+            Profession::Maun1,
+            Profession::Kua2,
+            Profession::Tuk2,
+            Profession::Uai1,
+        ]
+        .map(|prof| PureMove_::NonTamMoveFromHopZuo {
+            color,
+            prof,
+            dest: sq("LAU"),
+        })
+    });
+    (0..POLICY_SIZE)
+        .step_by(step)
+        .filter_map(policy_index_to_move::<Mock>)
+        .chain(parachutes)
+        .collect()
+}
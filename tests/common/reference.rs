@@ -0,0 +1,176 @@
+//! The move generator of `cetkaik_yhuap_move_candidates`, run on a copy of the mock field in `cetkaik_naive_representation`
+
+use super::{Coord, Field, Mock, Piece};
+use cetkaik_fundamental::{AbsoluteSide, PureMove_};
+use cetkaik_naive_representation::absolute::{self, Column, Row};
+use cetkaik_naive_representation::CetkaikNaive;
+use cetkaik_traits::{
+    IsAbsoluteField, IsBoard, IsField, IsMoveGenerator, MoveList, PureMove, RuleConfig, COLS, ROWS,
+};
+use cetkaik_yhuap_move_candidates::{
+    from_hop1zuo1_candidates_vec, not_from_hop1zuo1_candidates_vec, AllowKut2Tam2,
+};
+
+const ROW_NAMES: [Row; ROWS] = [
+    Row::A,
+    Row::E,
+    Row::I,
+    Row::U,
+    Row::O,
+    Row::Y,
+    Row::AI,
+    Row::AU,
+    Row::IA,
+];
+
+const COLUMN_NAMES: [Column; COLS] = [
+    Column::K,
+    Column::L,
+    Column::N,
+    Column::T,
+    Column::Z,
+    Column::X,
+    Column::C,
+    Column::M,
+    Column::P,
+];
+
+fn to_naive_coord(coord: Coord) -> absolute::Coord {
+    let index = usize::from(coord.0);
+    absolute::Coord(ROW_NAMES[index / COLS], COLUMN_NAMES[index % COLS])
+}
+
+fn from_naive_coord(absolute::Coord(row, column): absolute::Coord) -> Coord {
+    let row = ROW_NAMES.iter().position(|&r| r == row).unwrap();
+    let column = COLUMN_NAMES.iter().position(|&c| c == column).unwrap();
+    Coord(u8::try_from(COLS * row + column).unwrap())
+}
+
+fn to_naive_field(field: &Field) -> absolute::Field {
+    let board = (0..u8::try_from(ROWS * COLS).unwrap())
+        .filter_map(|index| {
+            let piece = match field.as_board().peek(Coord(index))? {
+                Piece::Tam2 => absolute::Piece::Tam2,
+                Piece::NonTam2Piece { color, prof, side } => {
+                    absolute::Piece::NonTam2Piece { color, prof, side }
+                }
+            };
+            Some((to_naive_coord(Coord(index)), piece))
+        })
+        .collect();
+    absolute::Field {
+        board: absolute::Board(board),
+        a_side_hop1zuo1: field.hop1zuo1_of(AbsoluteSide::ASide).collect(),
+        ia_side_hop1zuo1: field.hop1zuo1_of(AbsoluteSide::IASide).collect(),
+    }
+}
+
+fn from_naive_move(mv: PureMove_<absolute::Coord>) -> PureMove<Mock> {
+    let c = from_naive_coord;
+    match mv {
+        PureMove_::NonTamMoveSrcDst {
+            src,
+            dest,
+            is_water_entry_ciurl,
+        } => PureMove_::NonTamMoveSrcDst {
+            src: c(src),
+            dest: c(dest),
+            is_water_entry_ciurl,
+        },
+        PureMove_::NonTamMoveSrcStepDstFinite {
+            src,
+            step,
+            dest,
+            is_water_entry_ciurl,
+        } => PureMove_::NonTamMoveSrcStepDstFinite {
+            src: c(src),
+            step: c(step),
+            dest: c(dest),
+            is_water_entry_ciurl,
+        },
+        PureMove_::InfAfterStep {
+            src,
+            step,
+            planned_direction,
+        } => PureMove_::InfAfterStep {
+            src: c(src),
+            step: c(step),
+            planned_direction: c(planned_direction),
+        },
+        PureMove_::NonTamMoveFromHopZuo { color, prof, dest } => PureMove_::NonTamMoveFromHopZuo {
+            color,
+            prof,
+            dest: c(dest),
+        },
+        PureMove_::TamMoveNoStep {
+            src,
+            first_dest,
+            second_dest,
+        } => PureMove_::TamMoveNoStep {
+            src: c(src),
+            first_dest: c(first_dest),
+            second_dest: c(second_dest),
+        },
+        PureMove_::TamMoveStepsDuringFormer {
+            src,
+            step,
+            first_dest,
+            second_dest,
+        } => PureMove_::TamMoveStepsDuringFormer {
+            src: c(src),
+            step: c(step),
+            first_dest: c(first_dest),
+            second_dest: c(second_dest),
+        },
+        PureMove_::TamMoveStepsDuringLatter {
+            src,
+            step,
+            first_dest,
+            second_dest,
+        } => PureMove_::TamMoveStepsDuringLatter {
+            src: c(src),
+            step: c(step),
+            first_dest: c(first_dest),
+            second_dest: c(second_dest),
+        },
+    }
+}
+
+/// The generator that [`cetkaik_traits::perft::PERFT_YHUAP_INITIAL`] was counted with, under [`RuleConfig::yhuap`]
+pub struct Yhuap;
+
+impl IsMoveGenerator<Mock> for Yhuap {
+    fn generate_moves<L: MoveList<PureMove<Mock>>>(
+        &self,
+        field: &Field,
+        whose_turn: AbsoluteSide,
+        out: &mut L,
+    ) {
+        let config = self.config();
+        let naive = to_naive_field(field);
+        let moves = not_from_hop1zuo1_candidates_vec::<CetkaikNaive>(
+            &AllowKut2Tam2 {
+                allow_kut2tam2: config.allow_kut2tam2,
+            },
+            config.tam_itself_is_tam_hue,
+            whose_turn,
+            &naive,
+        )
+        .into_iter()
+        .chain(from_hop1zuo1_candidates_vec::<CetkaikNaive>(
+            whose_turn, &naive,
+        ));
+        for mv in moves {
+            out.push(from_naive_move(mv));
+        }
+    }
+
+    fn config(&self) -> RuleConfig {
+        RuleConfig::yhuap()
+    }
+}
+
+/// Every move the reference generator yields for `whose_turn` on `field`
+pub fn all_moves(field: &Field, whose_turn: AbsoluteSide) -> Vec<PureMove<Mock>> {
+    <Yhuap as IsMoveGenerator<Mock>>::all_moves(&Yhuap, field, whose_turn)
+}
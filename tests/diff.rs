@@ -0,0 +1,167 @@
+mod common;
+
+use cetkaik_fundamental::{AbsoluteSide, Color, ColorAndProf, Profession, PureMove_};
+use cetkaik_traits::{
+    apply_move, captures_between, Hop1Zuo1Diff, IsAbsoluteField, IsBoard, IsField, IsPieceWithSide,
+    SquareDiff,
+};
+use common::{sq, Field, Mock, Piece};
+
+const IA_KAUK2: Piece<AbsoluteSide> = Piece::NonTam2Piece {
+    color: Color::Kok1,
+    prof: Profession::Kauk2,
+    side: AbsoluteSide::IASide,
+};
+
+const A_DAU2: Piece<AbsoluteSide> = Piece::NonTam2Piece {
+    color: Color::Huok2,
+    prof: Profession::Dau2,
+    side: AbsoluteSide::ASide,
+};
+
+/// A field where the 兵 of `IASide` on LO can take the 虎 of `ASide` on LU, and the field after it does
+fn before_and_after_a_capture() -> (Field, Field) {
+    let mut before = Field::empty();
+    before.as_board_mut().put(sq("ZO"), Some(Piece::tam2()));
+    before.as_board_mut().put(sq("LO"), Some(IA_KAUK2));
+    before.as_board_mut().put(sq("LU"), Some(A_DAU2));
+    let after = apply_move::<Mock>(
+        &before,
+        PureMove_::NonTamMoveSrcDst {
+            src: sq("LO"),
+            dest: sq("LU"),
+            is_water_entry_ciurl: false,
+        },
+        AbsoluteSide::IASide,
+    )
+    .unwrap();
+    (before, after)
+}
+
+#[test]
+fn board_diff_lists_the_changed_squares() {
+    let (before, after) = before_and_after_a_capture();
+    let mut squares = before.as_board().diff(after.as_board()).collect::<Vec<_>>();
+    squares.sort_by_key(|d| d.coord.0);
+    assert_eq!(
+        squares,
+        [
+            SquareDiff {
+                coord: sq("LU"),
+                before: Some(A_DAU2),
+                after: Some(IA_KAUK2),
+            },
+            SquareDiff {
+                coord: sq("LO"),
+                before: Some(IA_KAUK2),
+                after: None,
+            },
+        ]
+    );
+    assert_eq!(before.as_board().diff(before.as_board()).count(), 0);
+}
+
+#[test]
+fn field_diff_includes_the_hop1zuo1s() {
+    let (before, after) = before_and_after_a_capture();
+    let diff = before.diff(&after);
+    assert_eq!(diff.squares.len(), 2);
+    assert!(diff.a_side_hop1zuo1.is_empty());
+    assert_eq!(
+        diff.ia_side_hop1zuo1,
+        Hop1Zuo1Diff {
+            added: vec![ColorAndProf {
+                color: Color::Huok2,
+                prof: Profession::Dau2,
+            }],
+            removed: vec![],
+        }
+    );
+
+    let back = after.diff(&before);
+    assert_eq!(back.ia_side_hop1zuo1.removed, diff.ia_side_hop1zuo1.added);
+    assert!(before.diff(&before).is_empty());
+}
+
+#[test]
+fn applying_the_diff_of_a_capture_reproduces_it() {
+    let (before, after) = before_and_after_a_capture();
+    let mut patched = before.clone();
+    patched.apply_diff(&before.diff(&after)).unwrap();
+    assert_eq!(patched, after);
+    patched.apply_diff(&after.diff(&before)).unwrap();
+    assert_eq!(patched, before);
+
+    let mut board = before.board;
+    board
+        .apply_diff(&before.as_board().diff(after.as_board()).collect::<Vec<_>>())
+        .unwrap();
+    assert_eq!(&board, after.as_board());
+}
+
+#[test]
+fn a_diff_that_does_not_match_is_rejected_without_changes() {
+    let (before, after) = before_and_after_a_capture();
+    let diff = before.diff(&after);
+
+    // the patch has already been applied
+    let mut patched = after.clone();
+    assert!(patched.apply_diff(&diff).is_err());
+    assert_eq!(patched, after);
+
+    // the board matches, but the piece to be given back is not in the hop1zuo1
+    let mut emptied = after.clone();
+    emptied.remove_from_hop1zuo1(
+        AbsoluteSide::IASide,
+        ColorAndProf {
+            color: Color::Huok2,
+            prof: Profession::Dau2,
+        },
+    );
+    assert!(emptied.apply_diff(&after.diff(&before)).is_err());
+    assert_eq!(emptied.as_board(), after.as_board());
+}
+
+#[test]
+fn captures_between_lists_the_taken_pieces_in_the_canonical_order() {
+    let (before, after) = before_and_after_a_capture();
+    assert_eq!(
+        captures_between(&before, &after).collect::<Vec<_>>(),
+        [ColorAndProf {
+            color: Color::Huok2,
+            prof: Profession::Dau2,
+        }]
+    );
+    assert_eq!(captures_between(&after, &before).count(), 0);
+
+    let mut later = after.clone();
+    for piece in [
+        ColorAndProf {
+            color: Color::Kok1,
+            prof: Profession::Io,
+        },
+        ColorAndProf {
+            color: Color::Kok1,
+            prof: Profession::Nuak1,
+        },
+    ] {
+        later.add_to_hop1zuo1(AbsoluteSide::ASide, piece);
+    }
+    assert_eq!(
+        captures_between(&before, &later).collect::<Vec<_>>(),
+        [
+            ColorAndProf {
+                color: Color::Kok1,
+                prof: Profession::Nuak1,
+            },
+            ColorAndProf {
+                color: Color::Kok1,
+                prof: Profession::Io,
+            },
+            ColorAndProf {
+                color: Color::Huok2,
+                prof: Profession::Dau2,
+            },
+        ]
+    );
+}
@@ -0,0 +1,147 @@
+mod common;
+
+use cetkaik_fundamental::{AbsoluteSide, Color, ColorAndProf, Profession, PureMove_};
+use cetkaik_traits::{
+    CetkaikRepresentation, HashedField, IsAbsoluteField, IsBoard, IsField, RuleConfig,
+};
+use common::reference::all_moves;
+use common::{sq, Field, Mock, Piece};
+
+const IA_KAUK2: Piece<AbsoluteSide> = Piece::NonTam2Piece {
+    color: Color::Kok1,
+    prof: Profession::Kauk2,
+    side: AbsoluteSide::IASide,
+};
+
+const KOK1_KAUK2: ColorAndProf = ColorAndProf {
+    color: Color::Kok1,
+    prof: Profession::Kauk2,
+};
+
+const HUOK2_IO: ColorAndProf = ColorAndProf {
+    color: Color::Huok2,
+    prof: Profession::Io,
+};
+
+/// The initial arrangement with a 兵 of `IASide` on LU, right in front of the 兵 of `ASide` on LI,
+/// and a few pieces in both hop1zuo1s, so that the first two plies include captures and parachutes
+fn skirmish() -> Field {
+    let mut field = Mock::yhuap_initial_field();
+    field.as_board_mut().put(sq("LU"), Some(IA_KAUK2));
+    for side in [AbsoluteSide::ASide, AbsoluteSide::IASide] {
+        field.add_to_hop1zuo1(side, KOK1_KAUK2);
+        field.add_to_hop1zuo1(side, KOK1_KAUK2);
+        field.add_to_hop1zuo1(side, HUOK2_IO);
+    }
+    field
+}
+
+fn is_capture(field: &Field, mv: PureMove_<common::Coord>) -> bool {
+    match mv {
+        PureMove_::NonTamMoveSrcDst { src, dest, .. }
+        | PureMove_::NonTamMoveSrcStepDstFinite { src, dest, .. } => {
+            src != dest && field.as_board().peek(dest).is_some()
+        }
+        _ => false,
+    }
+}
+
+#[test]
+fn the_hash_follows_put_pop_and_the_hop1zuo1s() {
+    let mut hashed = HashedField::<Mock>::new(skirmish());
+    let check = |hashed: &HashedField<Mock>| {
+        assert_eq!(hashed.hash(), Mock::canonical_hash(hashed.field()));
+    };
+
+    let taken = hashed.pop(sq("LI"));
+    assert!(taken.is_some());
+    check(&hashed);
+    hashed.put(sq("LI"), Some(IA_KAUK2));
+    check(&hashed);
+    hashed.put(sq("ZO"), None);
+    check(&hashed);
+    hashed.add_to_hop1zuo1(AbsoluteSide::IASide, KOK1_KAUK2);
+    check(&hashed);
+    assert!(hashed.remove_from_hop1zuo1(AbsoluteSide::ASide, HUOK2_IO));
+    check(&hashed);
+    assert!(!hashed.remove_from_hop1zuo1(AbsoluteSide::ASide, HUOK2_IO));
+    check(&hashed);
+}
+
+#[test]
+fn the_hash_after_apply_move_equals_the_hash_computed_from_scratch() {
+    let config = RuleConfig::yhuap();
+    let field = skirmish();
+    let (mut captures, mut parachutes) = (0, 0);
+    for first in all_moves(&field, AbsoluteSide::ASide) {
+        captures += usize::from(is_capture(&field, first));
+        let mut hashed = HashedField::<Mock>::new(field.clone());
+        hashed
+            .apply_move(first, AbsoluteSide::ASide, &config)
+            .unwrap();
+        assert_eq!(
+            hashed.hash(),
+            Mock::canonical_hash(hashed.field()),
+            "after {first:?}"
+        );
+        for second in all_moves(hashed.field(), AbsoluteSide::IASide) {
+            captures += usize::from(is_capture(hashed.field(), second));
+            parachutes += usize::from(matches!(second, PureMove_::NonTamMoveFromHopZuo { .. }));
+            let mut next = hashed.clone();
+            next.apply_move(second, AbsoluteSide::IASide, &config)
+                .unwrap();
+            assert_eq!(
+                next.hash(),
+                Mock::canonical_hash(next.field()),
+                "after {first:?} and {second:?}"
+            );
+        }
+    }
+    assert!(captures > 0 && parachutes > 0);
+}
+
+#[test]
+fn unmake_move_restores_the_field_and_its_hash() {
+    let config = RuleConfig::yhuap();
+    let mut hashed = HashedField::<Mock>::new(skirmish());
+    let (field, hash) = (hashed.field().clone(), hashed.hash());
+    for first in all_moves(&field, AbsoluteSide::ASide) {
+        let undo_first = hashed
+            .make_move(first, AbsoluteSide::ASide, &config)
+            .unwrap();
+        assert_eq!(hashed.hash(), Mock::canonical_hash(hashed.field()));
+        let (after_first, hash_after_first) = (hashed.field().clone(), hashed.hash());
+        for second in all_moves(&after_first, AbsoluteSide::IASide) {
+            let undo = hashed
+                .make_move(second, AbsoluteSide::IASide, &config)
+                .unwrap();
+            assert_eq!(hashed.hash(), Mock::canonical_hash(hashed.field()));
+            hashed.unmake_move(undo);
+            assert_eq!(hashed.hash(), hash_after_first, "{first:?} then {second:?}");
+            assert!(
+                hashed.field().semantically_equal(&after_first),
+                "{first:?} then {second:?}"
+            );
+        }
+        hashed.unmake_move(undo_first);
+        assert_eq!(hashed.hash(), hash, "{first:?}");
+        assert!(hashed.field().semantically_equal(&field), "{first:?}");
+    }
+}
+
+#[test]
+fn a_failed_make_move_changes_nothing() {
+    let config = RuleConfig::yhuap();
+    let mut hashed = HashedField::<Mock>::new(skirmish());
+    let (field, hash) = (hashed.field().clone(), hashed.hash());
+    let empty_src = PureMove_::NonTamMoveSrcDst {
+        src: sq("ZU"),
+        dest: sq("ZY"),
+        is_water_entry_ciurl: false,
+    };
+    assert!(hashed
+        .make_move(empty_src, AbsoluteSide::ASide, &config)
+        .is_err());
+    assert_eq!(hashed.hash(), hash);
+    assert_eq!(hashed.field(), &field);
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use cetkaik_fundamental::AbsoluteSide;
+use cetkaik_traits::{
+    parse_kifu, CetkaikRepresentation, GameRecord, KifuNotation, MoveDelimiter, PieceNames,
+    PlayedMove, RecordStyle, RuleConfig,
+};
+use common::{sample_moves, Mock, NOTATION_STYLES};
+
+#[test]
+fn kifu_round_trip() {
+    let mut record = GameRecord::<Mock>::new(
+        Mock::yhuap_initial_field(),
+        AbsoluteSide::IASide,
+        RuleConfig::default(),
+    );
+    for (i, mv) in sample_moves(9973).into_iter().enumerate() {
+        record.push(mv, (i % 2 == 0).then_some(u8::try_from(i % 6).unwrap()));
+    }
+    for names in [PieceNames::Kanji, PieceNames::Pekzep, PieceNames::English] {
+        for squares in NOTATION_STYLES {
+            for delimiter in [
+                MoveDelimiter::Space,
+                MoveDelimiter::Newline,
+                MoveDelimiter::Numbered,
+            ] {
+                for ciurl in [true, false] {
+                    let style = RecordStyle {
+                        names,
+                        squares,
+                        delimiter,
+                        ciurl,
+                    };
+                    let written = KifuNotation::new(&record).style(style).to_string();
+                    let expected = record
+                        .moves
+                        .iter()
+                        .map(|&PlayedMove { mv, ciurl: sticks }| PlayedMove {
+                            mv,
+                            ciurl: sticks.filter(|_| ciurl),
+                        })
+                        .collect::<Vec<_>>();
+                    assert_eq!(parse_kifu::<Mock>(&written), Some(expected), "{written}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use cetkaik_fundamental::{AbsoluteSide, Color, Profession, PureMove_};
+use cetkaik_traits::{
+    apply_move, is_legal, CetkaikRepresentation, IllegalMove, IsBoard, IsField, PureMove,
+    RuleConfig,
+};
+use common::{reference, sq, Field, Mock};
+
+fn check(mv: PureMove<Mock>) -> Result<(), IllegalMove> {
+    is_legal::<Mock>(
+        &Mock::yhuap_initial_field(),
+        mv,
+        AbsoluteSide::IASide,
+        &RuleConfig::default(),
+    )
+}
+
+/// Whether `mv` is legal, where `cetkaik_yhuap_move_candidates` decides the water-entry flag of a capture
+/// by the captured piece instead of the moving one, so a capture may carry either flag
+fn is_legal_up_to_the_water_entry_flag_of_a_capture(
+    field: &Field,
+    mv: PureMove<Mock>,
+    side: AbsoluteSide,
+) -> Result<(), IllegalMove> {
+    let config = RuleConfig::default();
+    let result = is_legal::<Mock>(field, mv, side, &config);
+    let flipped = match mv {
+        PureMove_::NonTamMoveSrcDst {
+            src,
+            dest,
+            is_water_entry_ciurl,
+        } if field.as_board().is_occupied(dest) => PureMove_::NonTamMoveSrcDst {
+            src,
+            dest,
+            is_water_entry_ciurl: !is_water_entry_ciurl,
+        },
+        PureMove_::NonTamMoveSrcStepDstFinite {
+            src,
+            step,
+            dest,
+            is_water_entry_ciurl,
+        } if field.as_board().is_occupied(dest) => PureMove_::NonTamMoveSrcStepDstFinite {
+            src,
+            step,
+            dest,
+            is_water_entry_ciurl: !is_water_entry_ciurl,
+        },
+        _ => return result,
+    };
+    result.or_else(|_| is_legal::<Mock>(field, flipped, side, &config))
+}
+
+#[test]
+fn every_move_of_the_reference_generator_is_legal() {
+    let initial = Mock::yhuap_initial_field();
+    for first in reference::all_moves(&initial, AbsoluteSide::IASide) {
+        assert_eq!(
+            is_legal_up_to_the_water_entry_flag_of_a_capture(&initial, first, AbsoluteSide::IASide),
+            Ok(()),
+            "{first:?}"
+        );
+        let field = apply_move::<Mock>(&initial, first, AbsoluteSide::IASide).unwrap();
+        for second in reference::all_moves(&field, AbsoluteSide::ASide) {
+            assert_eq!(
+                is_legal_up_to_the_water_entry_flag_of_a_capture(
+                    &field,
+                    second,
+                    AbsoluteSide::ASide
+                ),
+                Ok(()),
+                "{first:?} then {second:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn illegal_moves_are_reported_with_their_reasons() {
+    let step = |src, dest| PureMove_::NonTamMoveSrcDst {
+        src: sq(src),
+        dest: sq(dest),
+        is_water_entry_ciurl: false,
+    };
+    assert_eq!(check(step("KU", "KO")), Err(IllegalMove::NoPieceAtSrc));
+    assert_eq!(check(step("KI", "KU")), Err(IllegalMove::NotOwnPiece));
+    assert_eq!(check(step("KAI", "KY")), Ok(()));
+    assert_eq!(check(step("KAI", "KU")), Err(IllegalMove::Unreachable));
+    assert_eq!(check(step("KIA", "KAU")), Err(IllegalMove::CapturingAlly));
+    assert_eq!(check(step("LAU", "LY")), Err(IllegalMove::BlockedPath));
+    assert_eq!(
+        check(PureMove_::NonTamMoveFromHopZuo {
+            color: Color::Kok1,
+            prof: Profession::Kauk2,
+            dest: sq("KU"),
+        }),
+        Err(IllegalMove::NotInHop1Zuo1)
+    );
+    assert_eq!(
+        check(PureMove_::TamMoveNoStep {
+            src: sq("ZU"),
+            first_dest: sq("ZI"),
+            second_dest: sq("ZU"),
+        }),
+        Err(IllegalMove::NoTam2AtSrc)
+    );
+}
@@ -0,0 +1,14 @@
+mod common;
+
+use cetkaik_traits::{parse_move, MoveNotation};
+use common::{sample_moves, Mock, NOTATION_STYLES};
+
+#[test]
+fn move_notation_round_trip() {
+    for mv in sample_moves(97) {
+        for style in NOTATION_STYLES {
+            let written = MoveNotation::<Mock>::new(mv).style(style).to_string();
+            assert_eq!(parse_move::<Mock>(&written), Some(mv), "{written}");
+        }
+    }
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use cetkaik_fundamental::{AbsoluteSide, Color, ColorAndProf, Profession, PureMove_};
+use cetkaik_traits::{
+    generate_parachutes, CetkaikRepresentation, IsAbsoluteField, IsBoard, IsField,
+};
+use common::reference::all_moves;
+use common::{sq, Field, Mock};
+
+fn with_hop1zuo1(pieces: &[ColorAndProf]) -> Field {
+    let mut field = Mock::yhuap_initial_field();
+    field.as_board_mut().put(sq("ZO"), None);
+    for &piece in pieces {
+        field.add_to_hop1zuo1(AbsoluteSide::IASide, piece);
+    }
+    field
+}
+
+#[test]
+fn empty_mask_has_a_bit_for_each_empty_square() {
+    let field = with_hop1zuo1(&[]);
+    let mask = field.as_board().empty_mask::<Mock>();
+    let expected = field
+        .as_board()
+        .empty_squares()
+        .fold(0u128, |mask, c| mask | 1 << Mock::coord_to_index(c));
+    assert_eq!(mask, expected);
+    assert_eq!(
+        mask.count_ones() as usize,
+        field.as_board().empty_square_count()
+    );
+    assert_ne!(mask & 1 << Mock::coord_to_index(sq("ZO")), 0);
+    assert_eq!(mask >> 81, 0);
+}
+
+#[test]
+fn parachutes_match_the_reference_generator() {
+    let pieces = [
+        ColorAndProf {
+            color: Color::Huok2,
+            prof: Profession::Io,
+        },
+        ColorAndProf {
+            color: Color::Kok1,
+            prof: Profession::Kauk2,
+        },
+        ColorAndProf {
+            color: Color::Kok1,
+            prof: Profession::Kauk2,
+        },
+    ];
+    let field = with_hop1zuo1(&pieces);
+    let mut generated = Vec::new();
+    generate_parachutes::<Mock, _>(&field, AbsoluteSide::IASide, &mut generated);
+    // one move per kind of piece, not per piece
+    assert_eq!(generated.len(), 2 * field.as_board().empty_square_count());
+
+    let mut expected = all_moves(&field, AbsoluteSide::IASide)
+        .into_iter()
+        .filter(|mv| matches!(mv, PureMove_::NonTamMoveFromHopZuo { .. }))
+        .collect::<Vec<_>>();
+    let key = |mv: &PureMove_<common::Coord>| format!("{mv:?}");
+    generated.sort_by_key(key);
+    expected.sort_by_key(key);
+    expected.dedup();
+    assert_eq!(generated, expected);
+}
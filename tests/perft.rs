@@ -0,0 +1,34 @@
+mod common;
+
+use cetkaik_fundamental::AbsoluteSide;
+use cetkaik_traits::perft::PERFT_YHUAP_INITIAL;
+use cetkaik_traits::{perft, perft_with_pool, CetkaikRepresentation, FieldPool};
+use common::reference::Yhuap;
+use common::Mock;
+
+/// The deepest level counted here, since the next one takes far longer without optimizations
+const DEPTH: u32 = 2;
+
+#[test]
+fn perft_of_the_initial_arrangement() {
+    let field = Mock::yhuap_initial_field();
+    for side in [AbsoluteSide::ASide, AbsoluteSide::IASide] {
+        for depth in 0..=DEPTH {
+            assert_eq!(
+                perft::<Mock, _>(&field, side, depth, &Yhuap),
+                PERFT_YHUAP_INITIAL[depth as usize],
+                "{side:?} at depth {depth}"
+            );
+        }
+    }
+}
+
+#[test]
+fn perft_with_pool_of_the_initial_arrangement() {
+    let field = Mock::yhuap_initial_field();
+    let mut pool = FieldPool::new();
+    assert_eq!(
+        perft_with_pool::<Mock, _>(&field, AbsoluteSide::IASide, DEPTH, &Yhuap, &mut pool),
+        PERFT_YHUAP_INITIAL[DEPTH as usize]
+    );
+}
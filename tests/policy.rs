@@ -0,0 +1,72 @@
+mod common;
+
+use cetkaik_fundamental::{Color, Profession, PureMove_};
+use cetkaik_traits::{
+    move_to_policy_index, policy_index_to_move, CetkaikRepresentation, PureMove, COLS, POLICY_SIZE,
+    ROWS,
+};
+use common::{sq, Mock};
+
+#[test]
+fn policy_index_round_trip() {
+    let mut decoded = 0;
+    for index in 0..POLICY_SIZE {
+        if let Some(mv) = policy_index_to_move::<Mock>(index) {
+            assert_eq!(move_to_policy_index::<Mock>(mv), index, "{mv:?}");
+            decoded += 1;
+        }
+    }
+    assert!(decoded > 0);
+}
+
+#[test]
+fn every_move_is_mapped_into_the_policy() {
+    let moves: [PureMove<Mock>; 4] = [
+        // a leg along none of the eight directions
+        PureMove_::NonTamMoveSrcDst {
+            src: sq("KA"),
+            dest: sq("LI"),
+            is_water_entry_ciurl: false,
+        },
+        PureMove_::NonTamMoveSrcStepDstFinite {
+            src: sq("KA"),
+            step: sq("PIA"),
+            dest: sq("KA"),
+            is_water_entry_ciurl: true,
+        },
+        // a Tam2 leg going further than a neighboring square
+        PureMove_::TamMoveNoStep {
+            src: sq("ZO"),
+            first_dest: sq("ZA"),
+            second_dest: sq("ZO"),
+        },
+        PureMove_::NonTamMoveFromHopZuo {
+            color: Color::Huok2,
+            prof: Profession::Io,
+            dest: sq("PIA"),
+        },
+    ];
+    for mv in moves {
+        assert!(move_to_policy_index::<Mock>(mv) < POLICY_SIZE, "{mv:?}");
+    }
+}
+
+#[test]
+fn distinct_moves_along_the_eight_directions_have_distinct_indices() {
+    let squares = (0..ROWS).flat_map(|row| (0..COLS).map(move |column| [row, column]));
+    let mut seen = std::collections::HashSet::new();
+    for [r1, c1] in squares.clone() {
+        for [r2, c2] in squares.clone() {
+            let (dr, dc) = (r1.abs_diff(r2), c1.abs_diff(c2));
+            if (dr, dc) == (0, 0) || !(dr == 0 || dc == 0 || dr == dc) {
+                continue;
+            }
+            let mv: PureMove<Mock> = PureMove_::NonTamMoveSrcDst {
+                src: Mock::index_to_coord(u8::try_from(COLS * r1 + c1).unwrap()).unwrap(),
+                dest: Mock::index_to_coord(u8::try_from(COLS * r2 + c2).unwrap()).unwrap(),
+                is_water_entry_ciurl: false,
+            };
+            assert!(seen.insert(move_to_policy_index::<Mock>(mv)), "{mv:?}");
+        }
+    }
+}
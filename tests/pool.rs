@@ -0,0 +1,107 @@
+mod common;
+
+use cetkaik_fundamental::AbsoluteSide;
+use cetkaik_traits::{
+    search, search_with_pool, CetkaikRepresentation, FieldPool, IsAbsoluteField, MaterialEvaluator,
+    MctsTree, SearchLimits, SearchState,
+};
+use common::reference::Yhuap;
+use common::{Field, Mock};
+
+/// A linear congruential generator, so that both trees make the same choices
+fn choices(mut seed: u64) -> impl FnMut(usize) -> usize {
+    move |n| {
+        seed = seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        usize::try_from(seed >> 33).unwrap() % n
+    }
+}
+
+fn reward(field: &Field, side: AbsoluteSide) -> f64 {
+    let (own, other) = (
+        field.hop1zuo1_of(side).count(),
+        field.hop1zuo1_of(!side).count(),
+    );
+    match own.cmp(&other) {
+        core::cmp::Ordering::Greater => 1.0,
+        core::cmp::Ordering::Equal => 0.5,
+        core::cmp::Ordering::Less => 0.0,
+    }
+}
+
+fn visits(tree: &MctsTree<Mock>) -> Vec<u32> {
+    (0..u32::try_from(tree.node_count()).unwrap())
+        .map(|id| tree.node(id).visits())
+        .collect()
+}
+
+#[test]
+fn search_with_pool_finds_what_search_finds_and_returns_its_field() {
+    let field = Mock::yhuap_initial_field();
+    let mut limits = SearchLimits::new();
+    limits.depth = Some(2);
+    let expected = search::<Mock, _, _, _>(
+        &Yhuap,
+        &mut SearchState::new(MaterialEvaluator),
+        &field,
+        AbsoluteSide::IASide,
+        &limits,
+    );
+    assert!(expected.is_some());
+
+    let mut pool = FieldPool::new();
+    let mut state = SearchState::new(MaterialEvaluator);
+    for _ in 0..2 {
+        let found = search_with_pool::<Mock, _, _, _>(
+            &Yhuap,
+            &mut state,
+            &field,
+            AbsoluteSide::IASide,
+            &limits,
+            &mut pool,
+        );
+        assert_eq!(found, expected);
+        assert_eq!(pool.len(), 1);
+    }
+}
+
+#[test]
+fn iterate_with_pool_grows_the_tree_that_iterate_grows() {
+    const ITERATIONS: u32 = 60;
+    let field = Mock::yhuap_initial_field();
+    let mut plain = MctsTree::<Mock>::new(field.clone(), AbsoluteSide::ASide);
+    let mut pooled = MctsTree::<Mock>::new(field, AbsoluteSide::ASide);
+    let (mut plain_choices, mut pooled_choices) = (choices(12345), choices(12345));
+    let mut pool = FieldPool::new();
+    for _ in 0..ITERATIONS {
+        let plain_id = plain
+            .iterate(&Yhuap, 1.4, 8, &mut plain_choices, reward)
+            .unwrap();
+        let pooled_id = pooled
+            .iterate_with_pool(&Yhuap, 1.4, 8, &mut pooled_choices, reward, &mut pool)
+            .unwrap();
+        assert_eq!(pooled_id, plain_id);
+        assert_eq!(pool.len(), 1);
+    }
+    assert_eq!(visits(&pooled), visits(&plain));
+    assert_eq!(pooled.node(MctsTree::<Mock>::ROOT).visits(), ITERATIONS);
+    assert_eq!(pooled.best_move(), plain.best_move());
+}
+
+#[test]
+fn field_at_with_pool_replays_the_path_of_the_node() {
+    let mut tree = MctsTree::<Mock>::new(Mock::yhuap_initial_field(), AbsoluteSide::IASide);
+    let mut choose = choices(7);
+    for _ in 0..30 {
+        tree.iterate(&Yhuap, 1.4, 0, &mut choose, reward).unwrap();
+    }
+    let mut pool = FieldPool::new();
+    for id in 0..u32::try_from(tree.node_count()).unwrap() {
+        let expected = tree.field_at(&Yhuap, id).unwrap();
+        let (field, whose_turn) = tree.field_at_with_pool(&Yhuap, id, &mut pool).unwrap();
+        assert_eq!((&field, whose_turn), (&expected.0, expected.1), "node {id}");
+        pool.release(field);
+    }
+    assert_eq!(pool.len(), 1);
+}
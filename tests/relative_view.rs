@@ -0,0 +1,122 @@
+mod common;
+
+use cetkaik_fundamental::{AbsoluteSide, Color, ColorAndProf, Profession};
+use cetkaik_traits::{CetkaikRepresentation, IsAbsoluteField, IsBoard, IsField, COLS, ROWS};
+use common::{sq, Mock, Perspective, Piece, Side};
+
+/// The perspective in which the relative coordinates differ from the absolute ones
+const P: Perspective = Perspective::IaIsUpAndPointsDownward;
+
+const KOK1_KAUK2: ColorAndProf = ColorAndProf {
+    color: Color::Kok1,
+    prof: Profession::Kauk2,
+};
+
+fn relative_squares() -> impl Iterator<Item = [usize; 2]> {
+    (0..ROWS).flat_map(|row| (0..COLS).map(move |column| [row, column]))
+}
+
+/// Counts the pieces of `side` through nothing but the relative traits
+fn count_of<F: IsField<Coord = [usize; 2], PieceWithSide = Piece<Side>>>(
+    field: &F,
+    side: Side,
+) -> usize {
+    relative_squares()
+        .filter(|&c| matches!(field.as_board().peek(c), Some(Piece::NonTam2Piece { side: s, .. }) if s == side))
+        .count()
+}
+
+#[test]
+fn the_mutable_view_reads_as_the_read_only_one() {
+    let mut field = Mock::yhuap_initial_field();
+    let copy = field.clone();
+    let view = Mock::to_relative_view(&copy, P);
+    let view_mut = Mock::to_relative_view_mut(&mut field, P);
+    for c in relative_squares() {
+        assert_eq!(view_mut.peek(c), view.peek(c), "{c:?}");
+    }
+    assert_eq!(view_mut.occupied_count(), view.occupied_squares().count());
+    assert_eq!(count_of(&view_mut, Side::Upward), 24);
+    assert_eq!(count_of(&view_mut, Side::Downward), 24);
+}
+
+#[test]
+fn pop_and_put_write_through_to_the_absolute_field() {
+    let mut field = Mock::yhuap_initial_field();
+    let mut view = Mock::to_relative_view_mut(&mut field, P);
+    // seen from `IASide` upward, the far right corner is KA
+    let piece = view.pop([8, 8]);
+    assert!(matches!(
+        piece,
+        Some(Piece::NonTam2Piece {
+            side: Side::Upward,
+            ..
+        })
+    ));
+    view.put([3, 3], piece);
+    assert_eq!(field.as_board().peek(sq("KA")), None);
+    assert_eq!(
+        field.as_board().peek(sq("XY")),
+        Mock::yhuap_initial_field().as_board().peek(sq("KA"))
+    );
+}
+
+#[test]
+fn a_move_returns_a_view_of_a_new_field() {
+    let mut field = Mock::yhuap_initial_field();
+    let view = Mock::to_relative_view_mut(&mut field, P);
+    // the 兵 of `IASide` on LAI steps forward to LY
+    let moved = view
+        .move_nontam_piece_from_src_to_dest_while_taking_opponent_piece_if_needed(
+            [2, 7],
+            [3, 7],
+            Side::Downward,
+        )
+        .unwrap();
+    assert_eq!(moved.perspective(), P);
+    assert!(moved.is_empty([2, 7]));
+    let moved = moved.into_absolute();
+    assert_eq!(
+        moved,
+        Mock::yhuap_initial_field()
+            .move_nontam_piece_from_src_to_dest_while_taking_opponent_piece_if_needed(
+                sq("LAI"),
+                sq("LY"),
+                AbsoluteSide::IASide,
+            )
+            .unwrap()
+    );
+    assert_eq!(field, Mock::yhuap_initial_field());
+}
+
+#[test]
+fn a_parachute_goes_through_the_relative_side() {
+    let mut field = Mock::yhuap_initial_field();
+    field.add_to_hop1zuo1(AbsoluteSide::ASide, KOK1_KAUK2);
+    let view = Mock::to_relative_view_mut(&mut field, P);
+    assert_eq!(
+        view.hop1zuo1_of(Side::Upward).collect::<Vec<_>>(),
+        [KOK1_KAUK2]
+    );
+    assert!(view
+        .search_from_hop1zuo1_and_parachute_at(
+            Color::Kok1,
+            Profession::Kauk2,
+            Side::Downward,
+            [3, 3]
+        )
+        .is_none());
+    let dropped = view
+        .search_from_hop1zuo1_and_parachute_at(Color::Kok1, Profession::Kauk2, Side::Upward, [3, 3])
+        .unwrap()
+        .into_absolute();
+    assert_eq!(dropped.hop1zuo1_of(AbsoluteSide::ASide).count(), 0);
+    assert_eq!(
+        dropped.as_board().peek(sq("XY")),
+        Some(Piece::NonTam2Piece {
+            color: Color::Kok1,
+            prof: Profession::Kauk2,
+            side: AbsoluteSide::ASide,
+        })
+    );
+}
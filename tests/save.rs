@@ -0,0 +1,95 @@
+mod common;
+
+use cetkaik_fundamental::{AbsoluteSide, Color, ColorAndProf, Profession};
+use cetkaik_traits::{
+    load, save, CetkaikRepresentation, GameRecord, IsAbsoluteField, LoadError, RuleConfig,
+    SavedGame, SAVE_MAGIC, SEASON_COUNT,
+};
+use common::{sample_moves, Mock};
+
+/// A game whose `IASide` holds `hop1zuo1`, added in the order given
+fn saved_game(hop1zuo1: &[ColorAndProf]) -> SavedGame<Mock> {
+    let mut initial = Mock::yhuap_initial_field();
+    for &piece in hop1zuo1 {
+        initial.add_to_hop1zuo1(AbsoluteSide::IASide, piece);
+    }
+    let mut record = GameRecord::new(initial, AbsoluteSide::ASide, RuleConfig::default());
+    for (i, mv) in sample_moves(99991).into_iter().take(20).enumerate() {
+        record.push(mv, (i % 3 != 0).then_some(u8::try_from(i % 6).unwrap()));
+    }
+    SavedGame {
+        season_index: 2,
+        a_side_score: 25,
+        ia_side_score: 15,
+        rate: 2,
+        record,
+    }
+}
+
+const HOP1ZUO1: [ColorAndProf; 3] = [
+    ColorAndProf {
+        color: Color::Huok2,
+        prof: Profession::Kauk2,
+    },
+    ColorAndProf {
+        color: Color::Kok1,
+        prof: Profession::Uai1,
+    },
+    ColorAndProf {
+        color: Color::Kok1,
+        prof: Profession::Nuak1,
+    },
+];
+
+#[test]
+fn save_load_round_trip() {
+    let game = saved_game(&HOP1ZUO1);
+    let bytes = save(&game);
+    let loaded = load::<Mock>(&bytes).unwrap();
+    assert_eq!(loaded.season_index, game.season_index);
+    assert_eq!(loaded.a_side_score, game.a_side_score);
+    assert_eq!(loaded.ia_side_score, game.ia_side_score);
+    assert_eq!(loaded.rate, game.rate);
+    assert_eq!(loaded.record.first_mover, game.record.first_mover);
+    assert_eq!(loaded.record.config, game.record.config);
+    assert_eq!(loaded.record.moves, game.record.moves);
+    assert!(loaded
+        .record
+        .initial
+        .semantically_equal(&game.record.initial));
+    assert_eq!(save(&loaded), bytes);
+}
+
+#[test]
+fn save_writes_the_hop1zuo1_in_the_canonical_order() {
+    let mut reversed = HOP1ZUO1;
+    reversed.reverse();
+    let bytes = save(&saved_game(&HOP1ZUO1));
+    assert_eq!(save(&saved_game(&reversed)), bytes);
+
+    let loaded = load::<Mock>(&bytes).unwrap();
+    assert_eq!(
+        loaded
+            .record
+            .initial
+            .hop1zuo1_of(AbsoluteSide::IASide)
+            .collect::<Vec<_>>(),
+        loaded.record.initial.hop1zuo1_sorted(AbsoluteSide::IASide)
+    );
+}
+
+#[test]
+fn load_rejects_an_out_of_range_season() {
+    let mut bytes = save(&saved_game(&HOP1ZUO1));
+    // after the magic, the version, the rule flags and the first mover
+    bytes[SAVE_MAGIC.len() + 4] = u8::try_from(SEASON_COUNT).unwrap();
+    assert_eq!(load::<Mock>(&bytes).err(), Some(LoadError::Malformed));
+}
+
+#[test]
+fn load_rejects_an_out_of_range_number_of_sticks() {
+    let mut bytes = save(&saved_game(&HOP1ZUO1));
+    // the sticks of the last move, which were recorded
+    *bytes.last_mut().unwrap() = 6;
+    assert_eq!(load::<Mock>(&bytes).err(), Some(LoadError::Malformed));
+}
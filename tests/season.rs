@@ -0,0 +1,164 @@
+mod common;
+
+use cetkaik_fundamental::{AbsoluteSide, ColorAndProf};
+use cetkaik_traits::{
+    CapturedIter, CetkaikRepresentation, IsGameState, IsSeasonState, SeasonOutcome, INITIAL_SCORE,
+};
+use common::{Field, Mock};
+
+/// Just enough of a game to carry the scores over
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct State {
+    field: Field,
+    whose_turn: AbsoluteSide,
+    season_index: usize,
+    a_side_score: i32,
+    ia_side_score: i32,
+    rate: i32,
+}
+
+impl State {
+    fn new(whose_turn: AbsoluteSide, a_side_score: i32, rate: i32) -> Self {
+        Self {
+            field: Mock::yhuap_initial_field(),
+            whose_turn,
+            season_index: 0,
+            a_side_score,
+            ia_side_score: 2 * INITIAL_SCORE - a_side_score,
+            rate,
+        }
+    }
+}
+
+impl IsGameState for State {
+    type Field = Field;
+    type Config = ();
+
+    fn field(&self) -> &Field {
+        &self.field
+    }
+    fn field_mut(&mut self) -> &mut Field {
+        &mut self.field
+    }
+    fn config(&self) -> &() {
+        &()
+    }
+    fn whose_turn(&self) -> AbsoluteSide {
+        self.whose_turn
+    }
+    fn season_index(&self) -> usize {
+        self.season_index
+    }
+    fn score_of(&self, side: AbsoluteSide) -> i32 {
+        match side {
+            AbsoluteSide::ASide => self.a_side_score,
+            AbsoluteSide::IASide => self.ia_side_score,
+        }
+    }
+    fn rate(&self) -> i32 {
+        self.rate
+    }
+    fn repetition_key(&self) -> u64 {
+        0
+    }
+    fn captured_this_season(&self, _: AbsoluteSide) -> CapturedIter {
+        core::iter::empty().collect()
+    }
+    fn tam2_moved_this_season(&self, _: AbsoluteSide) -> bool {
+        false
+    }
+    fn captured_by_last_move(&self) -> Option<ColorAndProf> {
+        None
+    }
+}
+
+impl IsSeasonState for State {
+    fn next_season(self, a_side_score: i32, ia_side_score: i32, whose_turn: AbsoluteSide) -> Self {
+        Self {
+            field: Mock::yhuap_initial_field(),
+            whose_turn,
+            season_index: self.season_index + 1,
+            a_side_score,
+            ia_side_score,
+            rate: 1,
+        }
+    }
+}
+
+fn next_season(outcome: SeasonOutcome<State>) -> State {
+    match outcome {
+        SeasonOutcome::NextSeason(state) => state,
+        SeasonOutcome::GameOver { .. } => panic!("the game should go on: {outcome:?}"),
+    }
+}
+
+#[test]
+fn the_declarer_takes_the_score_times_the_rate() {
+    let next =
+        next_season(State::new(AbsoluteSide::IASide, 20, 2).end_season(5, AbsoluteSide::ASide));
+    assert_eq!((next.a_side_score, next.ia_side_score), (10, 30));
+    assert_eq!(next.season_index, 1);
+}
+
+#[test]
+fn the_first_mover_of_the_next_season_is_the_one_given() {
+    for first_mover in [AbsoluteSide::ASide, AbsoluteSide::IASide] {
+        let next = next_season(State::new(AbsoluteSide::IASide, 20, 1).end_season(3, first_mover));
+        assert_eq!(next.whose_turn, first_mover);
+    }
+}
+
+#[test]
+fn the_rate_is_reset_in_the_next_season() {
+    let next =
+        next_season(State::new(AbsoluteSide::ASide, 20, 4).end_season(1, AbsoluteSide::ASide));
+    assert_eq!((next.a_side_score, next.ia_side_score), (24, 16));
+    assert_eq!(next.rate, 1);
+}
+
+#[test]
+fn a_negative_score_is_given_to_the_opponent() {
+    let next =
+        next_season(State::new(AbsoluteSide::ASide, 20, 2).end_season(-3, AbsoluteSide::IASide));
+    assert_eq!((next.a_side_score, next.ia_side_score), (14, 26));
+}
+
+#[test]
+fn the_scores_are_clamped_at_40() {
+    assert_eq!(
+        State::new(AbsoluteSide::IASide, 10, 2).end_season(7, AbsoluteSide::ASide),
+        SeasonOutcome::GameOver {
+            a_side_score: 0,
+            ia_side_score: 40,
+            winner: Some(AbsoluteSide::IASide),
+        }
+    );
+}
+
+#[test]
+fn the_scores_are_clamped_at_0() {
+    assert_eq!(
+        State::new(AbsoluteSide::ASide, 5, 4).end_season(-2, AbsoluteSide::ASide),
+        SeasonOutcome::GameOver {
+            a_side_score: 0,
+            ia_side_score: 40,
+            winner: Some(AbsoluteSide::IASide),
+        }
+    );
+}
+
+#[test]
+fn the_game_is_over_after_winter() {
+    let winter = State {
+        season_index: 3,
+        ..State::new(AbsoluteSide::ASide, 20, 1)
+    };
+    assert_eq!(
+        winter.end_season(0, AbsoluteSide::ASide),
+        SeasonOutcome::GameOver {
+            a_side_score: 20,
+            ia_side_score: 20,
+            winner: None,
+        }
+    );
+}
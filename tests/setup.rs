@@ -0,0 +1,25 @@
+mod common;
+
+use cetkaik_fundamental::AbsoluteSide;
+use cetkaik_traits::{
+    CetkaikRepresentation, IsAbsoluteBoard, IsAbsoluteField, IsBoard, IsField, YHUAP_INITIAL_PIECES,
+};
+use common::{sq, Board, Coord, Field, Mock, Piece};
+
+#[test]
+fn the_default_initial_board_is_the_yhuap_arrangement() {
+    let board = <Board<Coord, Piece<AbsoluteSide>> as IsAbsoluteBoard>::yhuap_initial();
+    assert_eq!(board, Mock::yhuap_initial_board());
+    assert_eq!(board.occupied_count(), YHUAP_INITIAL_PIECES.len() + 1);
+    assert_eq!(board.peek(sq("ZO")), Some(Piece::Tam2));
+}
+
+#[test]
+fn the_default_initial_field_has_empty_hop1zuo1s() {
+    let field = <Field as IsAbsoluteField>::yhuap_initial();
+    assert_eq!(field, Mock::yhuap_initial_field());
+    assert_eq!(*field.as_board(), Mock::yhuap_initial_board());
+    for side in [AbsoluteSide::ASide, AbsoluteSide::IASide] {
+        assert_eq!(field.hop1zuo1_of(side).count(), 0);
+    }
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use cetkaik_fundamental::{AbsoluteSide, Color, ColorAndProf, Profession, PureMove_};
+use cetkaik_traits::{
+    apply_move_with_config, CetkaikRepresentation, IllegalMove, IsAbsoluteField, IsBoard, IsField,
+    PureMove, RuleConfig, StepDeclared,
+};
+use common::{sq, Coord, Mock};
+
+/// The 弓 of `IASide` on LAU stepping over the 兵 on LAI, heading for LI
+fn gua2_over_kauk2(planned_direction: &str) -> PureMove<Mock> {
+    PureMove_::InfAfterStep {
+        src: sq("LAU"),
+        step: sq("LAI"),
+        planned_direction: sq(planned_direction),
+    }
+}
+
+fn declare(mv: PureMove<Mock>) -> Result<StepDeclared<Mock>, IllegalMove> {
+    StepDeclared::new(
+        Mock::yhuap_initial_field(),
+        mv,
+        AbsoluteSide::IASide,
+        &RuleConfig::yhuap(),
+    )
+}
+
+fn finals(declared: &StepDeclared<Mock>, ciurl: usize) -> Vec<Coord> {
+    declared.possible_finals(ciurl).collect()
+}
+
+#[test]
+fn the_finals_are_at_most_ciurl_away_from_the_step() {
+    let declared = declare(gua2_over_kauk2("LI")).unwrap();
+    assert_eq!(declared.declared_move(), gua2_over_kauk2("LI"));
+    assert_eq!(finals(&declared, 0), []);
+    assert_eq!(finals(&declared, 1), [sq("LY")]);
+    assert_eq!(finals(&declared, 2), [sq("LO"), sq("LY")]);
+    assert_eq!(finals(&declared, 3), [sq("LU"), sq("LO"), sq("LY")]);
+    // the 兵 of `ASide` on LI can be taken, but nothing beyond it can be reached
+    for ciurl in 4..=5 {
+        assert_eq!(
+            finals(&declared, ciurl),
+            [sq("LI"), sq("LU"), sq("LO"), sq("LY")]
+        );
+    }
+    assert!(declared.is_possible_final(2, sq("LO")));
+    assert!(!declared.is_possible_final(2, sq("LU")));
+    assert!(!declared.is_possible_final(5, sq("LE")));
+}
+
+#[test]
+fn the_finals_lie_in_the_planned_direction() {
+    // heading back, the only square in that direction is the one the 弓 came from
+    let back = declare(gua2_over_kauk2("LAU")).unwrap();
+    for ciurl in 1..=5 {
+        assert_eq!(finals(&back, ciurl), [sq("LAU")]);
+    }
+    assert!(!back.is_possible_final(5, sq("LY")));
+}
+
+#[test]
+fn finishing_applies_the_move_to_the_chosen_square() {
+    let declared = declare(gua2_over_kauk2("LI")).unwrap();
+    let field = declared.clone().finish(4, Some(sq("LI"))).unwrap();
+    assert_eq!(
+        field,
+        apply_move_with_config::<Mock>(
+            declared.field(),
+            gua2_over_kauk2("LI"),
+            AbsoluteSide::IASide,
+            &RuleConfig::yhuap(),
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        field.as_board().peek(sq("LI")),
+        declared.field().as_board().peek(sq("LAU"))
+    );
+    assert_eq!(field.as_board().peek(sq("LAU")), None);
+    assert_eq!(
+        field.hop1zuo1_of(AbsoluteSide::IASide).collect::<Vec<_>>(),
+        [ColorAndProf {
+            color: Color::Kok1,
+            prof: Profession::Kauk2,
+        }]
+    );
+}
+
+#[test]
+fn giving_up_leaves_the_field_as_it_was() {
+    let declared = declare(gua2_over_kauk2("LI")).unwrap();
+    assert_eq!(declared.clone().finish(0, None).unwrap(), *declared.field());
+}
+
+#[test]
+fn finishing_out_of_reach_is_rejected() {
+    let declared = declare(gua2_over_kauk2("LI")).unwrap();
+    assert_eq!(
+        declared.clone().finish(1, Some(sq("LO"))).unwrap_err(),
+        IllegalMove::Unreachable
+    );
+    assert_eq!(
+        declared.finish(5, Some(sq("KAI"))).unwrap_err(),
+        IllegalMove::Unreachable
+    );
+}
+
+#[test]
+fn only_a_legal_inf_after_step_can_be_declared() {
+    let src_dst = PureMove_::NonTamMoveSrcDst {
+        src: sq("LAU"),
+        dest: sq("LY"),
+        is_water_entry_ciurl: false,
+    };
+    assert_eq!(declare(src_dst).unwrap_err(), IllegalMove::Unreachable);
+    let from_nowhere = PureMove_::InfAfterStep {
+        src: sq("LO"),
+        step: sq("LI"),
+        planned_direction: sq("LE"),
+    };
+    assert_eq!(
+        declare(from_nowhere).unwrap_err(),
+        IllegalMove::NoPieceAtSrc
+    );
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use cetkaik_fundamental::{AbsoluteSide, Color, ColorAndProf, Profession};
+use cetkaik_traits::{
+    tb_index, tb_position, IsAbsoluteField, IsBoard, IsField, IsPieceWithSide, TbMaterial,
+};
+use common::{sq, Field, Mock, Piece};
+
+#[test]
+fn tb_index_round_trip() {
+    let mut field = Field::empty();
+    let board = field.as_board_mut();
+    board.put(sq("ZO"), Some(Piece::tam2()));
+    board.put(
+        sq("LAU"),
+        Some(Piece::non_tam2_piece(
+            Color::Kok1,
+            Profession::Kauk2,
+            AbsoluteSide::IASide,
+        )),
+    );
+    board.put(
+        sq("TE"),
+        Some(Piece::non_tam2_piece(
+            Color::Huok2,
+            Profession::Io,
+            AbsoluteSide::ASide,
+        )),
+    );
+    field.add_to_hop1zuo1(
+        AbsoluteSide::ASide,
+        ColorAndProf {
+            color: Color::Kok1,
+            prof: Profession::Kauk2,
+        },
+    );
+
+    let material = TbMaterial::of::<Mock>(&field);
+    let index = tb_index::<Mock>(&field).unwrap();
+    let position = tb_position::<Mock>(&material, index).unwrap();
+    assert!(position.semantically_equal(&field));
+
+    let count = material.index_count().unwrap();
+    let mut positions = 0;
+    for index in (0..count).step_by(101) {
+        if let Some(position) = tb_position::<Mock>(&material, index) {
+            assert_eq!(TbMaterial::of::<Mock>(&position), material);
+            assert_eq!(tb_index::<Mock>(&position), Some(index));
+            positions += 1;
+        }
+    }
+    assert!(positions > 0);
+}
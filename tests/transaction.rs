@@ -0,0 +1,72 @@
+mod common;
+
+use cetkaik_fundamental::{AbsoluteSide, Color, ColorAndProf, Profession};
+use cetkaik_traits::{CetkaikRepresentation, IsAbsoluteField, IsBoard, IsField};
+use common::{sq, Field, Mock};
+
+const KAUK2: ColorAndProf = ColorAndProf {
+    color: Color::Kok1,
+    prof: Profession::Kauk2,
+};
+
+/// Takes the 兵 of `ASide` on LI with the one of `IASide` on LAI; a transaction does not look at the rules
+fn capture(field: &mut Field) {
+    let mut transaction = field.begin_transaction();
+    let taken = transaction.pop(sq("LI"));
+    assert!(taken.is_some());
+    transaction.add_to_hop1zuo1(AbsoluteSide::IASide, KAUK2);
+    transaction.mov(sq("LAI"), sq("LI"));
+    transaction.commit();
+}
+
+#[test]
+fn rollback_restores_the_field() {
+    let initial = Mock::yhuap_initial_field();
+    let mut field = initial.clone();
+    let mut transaction = field.begin_transaction();
+    transaction.pop(sq("LI"));
+    transaction.add_to_hop1zuo1(AbsoluteSide::IASide, KAUK2);
+    transaction.mov(sq("LAI"), sq("LI"));
+    transaction.put(sq("KU"), transaction.peek(sq("ZO")));
+    assert!(transaction.field().as_board().is_occupied(sq("KU")));
+    transaction.rollback();
+    assert_eq!(field, initial);
+}
+
+#[test]
+fn dropping_without_commit_rolls_back() {
+    let initial = Mock::yhuap_initial_field();
+    let mut field = initial.clone();
+    {
+        let mut transaction = field.begin_transaction();
+        transaction.mov(sq("LAI"), sq("LY"));
+        transaction.add_to_hop1zuo1(AbsoluteSide::ASide, KAUK2);
+    }
+    assert_eq!(field, initial);
+}
+
+#[test]
+fn commit_keeps_the_mutations() {
+    let initial = Mock::yhuap_initial_field();
+    let mut field = initial.clone();
+    capture(&mut field);
+    assert_ne!(field, initial);
+    assert!(field.as_board().is_empty(sq("LAI")));
+    assert_eq!(
+        field.hop1zuo1_of(AbsoluteSide::IASide).collect::<Vec<_>>(),
+        [KAUK2]
+    );
+}
+
+#[test]
+fn a_removal_that_fails_is_not_undone() {
+    let mut field = Mock::yhuap_initial_field();
+    capture(&mut field);
+    let captured = field.clone();
+
+    let mut transaction = field.begin_transaction();
+    assert!(!transaction.remove_from_hop1zuo1(AbsoluteSide::ASide, KAUK2));
+    assert!(transaction.remove_from_hop1zuo1(AbsoluteSide::IASide, KAUK2));
+    transaction.rollback();
+    assert_eq!(field, captured);
+}
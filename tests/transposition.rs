@@ -0,0 +1,27 @@
+use cetkaik_traits::{TableKey, TranspositionTable};
+
+#[test]
+fn u64_keys_pick_their_slots_as_they_are() {
+    assert_eq!(0x1234_u64.slot_bits(), 0x1234);
+    let table = TranspositionTable::<&str>::new(16);
+    assert!(table.insert(3, "three"));
+    assert_eq!(table.get(3), Some("three"));
+    // 19 falls into the same slot as 3, and the default policy replaces it
+    assert!(table.insert(19, "nineteen"));
+    assert_eq!(table.get(3), None);
+    assert_eq!(table.get(19), Some("nineteen"));
+}
+
+#[test]
+fn u128_keys_are_compared_in_full() {
+    let table = TranspositionTable::<u32, _, u128>::with_policy(16, |_: &u32, _: &u32| false);
+    let key = 7 << 64 | 5;
+    assert!(table.insert(key, 1));
+    assert_eq!(table.get(key), Some(1));
+    // the same low half with another high half is another key
+    assert_eq!(table.get(5), None);
+    // and the high half moves the slot, so this one does not collide
+    assert!(table.insert(8 << 64 | 5, 2));
+    assert_eq!(table.get(8 << 64 | 5), Some(2));
+    assert_eq!(table.get(key), Some(1));
+}